@@ -1,8 +1,12 @@
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
 use syn::{Ident, LitStr, Result};
 
-use crate::model::{ContainerKind, Field, FieldType, FloatKind, IntegerKind, Module, ScalarKind};
+use crate::model::{
+    BytesEncoding, ContainerKind, Field, FieldType, FloatKind, IntegerKind, Module, NetKind, ScalarKind,
+    SchemaVersion, VERSION_KEY,
+};
 
 /// Produces the async load/save implementation for a parsed module description.
 pub fn generate(module: Module, crate_path: syn::Path) -> Result<TokenStream> {
@@ -10,111 +14,535 @@ pub fn generate(module: Module, crate_path: syn::Path) -> Result<TokenStream> {
         ident,
         generics,
         section,
+        version,
+        shared_section,
+        post_load,
+        pre_save,
         fields,
     } = module;
 
-    let section_lit = LitStr::new(&section, Span::call_site());
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let init_fields = fields.iter().map(|field| {
+    let init_fields: Vec<_> = fields.iter().map(|field| {
         let ident = &field.ident;
-        if let Some(init) = &field.init {
+        if let Some(ty) = &field.flatten {
+            quote! { #ident: <#ty as #crate_path::__private::ConferFields>::confer_defaults() }
+        } else if let Some(init) = &field.init {
             quote! { #ident: #init }
         } else if let Some(default) = &field.default {
             quote! { #ident: #default }
         } else {
             quote! { #ident: ::core::default::Default::default() }
         }
-    });
+    }).collect();
+
+    let take_loaded: Vec<_> = fields
+        .iter()
+        .filter(|field| !field.ignore && !field.skip_load)
+        .map(|field| {
+            let ident = &field.ident;
+            match &field.flatten {
+                Some(ty) => quote! {
+                    <#ty as #crate_path::__private::ConferFields>::take_loaded(&mut self.#ident, &mut loaded.#ident);
+                },
+                None => quote! { ::core::mem::swap(&mut self.#ident, &mut loaded.#ident); },
+            }
+        })
+        .collect();
 
+    let loaded_param = if take_loaded.is_empty() {
+        quote! { _loaded }
+    } else {
+        quote! { loaded }
+    };
+
+    let field_section = quote! { section };
     let load_blocks: Vec<_> = fields
         .iter()
-        .filter(|field| !field.ignore)
-        .map(|field| generate_load(field, &section_lit, &crate_path))
+        .filter(|field| !field.ignore && !field.skip_load)
+        .map(|field| generate_load(field, &field_section, &crate_path))
         .collect::<Result<_>>()?;
 
+    let deprecation_blocks: Vec<_> = fields
+        .iter()
+        .filter(|field| field.is_keyed() && !field.skip_load)
+        .filter_map(|field| {
+            let message = field.deprecated.as_ref()?;
+            let key = LitStr::new(&field.key, field.span);
+            Some(quote! {
+                if store.contains_key(section, #key).await {
+                    #crate_path::__private::warn_deprecated(section, #key, #message);
+                }
+            })
+        })
+        .collect();
+
+    let save_method = format_ident!("save_fields");
     let save_blocks: Vec<_> = fields
+        .iter()
+        .filter(|field| !field.ignore && !field.skip_save)
+        .map(|field| generate_save(field, &field_section, &crate_path, &save_method))
+        .collect::<Result<_>>()?;
+    let render_method = format_ident!("render_fields");
+    let render_blocks: Vec<_> = fields
         .iter()
         .filter(|field| !field.ignore)
-        .map(|field| generate_save(field, &section_lit, &crate_path))
+        .map(|field| generate_save(field, &field_section, &crate_path, &render_method))
         .collect::<Result<_>>()?;
 
-    let owned_keys: Vec<_> = fields
+    let own_keys: Vec<_> = fields
         .iter()
-        .filter(|field| !field.ignore)
+        .filter(|field| field.is_keyed())
         .map(|field| LitStr::new(&field.key, field.span))
         .collect();
+    let saved_keys: Vec<_> = fields
+        .iter()
+        .filter(|field| field.is_saved())
+        .map(|field| LitStr::new(&field.key, field.span))
+        .collect();
+    let flattened: Vec<_> = fields.iter().filter_map(|field| field.flatten.as_ref()).collect();
+
+    let crate_private = quote! { #crate_path::__private };
+    let fields_trait = quote! { #crate_private::ConferFields };
+    let version_key = version
+        .as_ref()
+        .map(|_| LitStr::new(VERSION_KEY, Span::call_site()));
+    let version_key_len = usize::from(version_key.is_some());
+    let version_key_option = match &version_key {
+        Some(key) => quote! { ::core::option::Option::Some(#key) },
+        None => quote! { ::core::option::Option::None },
+    };
+    let version_key_match = version_key
+        .as_ref()
+        .map(|key| quote! { || key == #key });
 
-    let known_keys_expr = if owned_keys.is_empty() {
-        quote! { &[] as &[&str] }
+    let prune_block = if shared_section {
+        TokenStream::new()
     } else {
-        quote! { &[#(#owned_keys),*] }
+        quote! {
+            #crate_path::SectionGuard::new(Self::owned_keys().iter().copied())
+                .reconcile(&store, #section)
+                .await?;
+        }
     };
 
-    let clone_block = generate_clone_block(&fields);
+    let schema_entries: Vec<_> = fields
+        .iter()
+        .filter(|field| !field.ignore)
+        .map(|field| generate_schema_entry(field, &crate_path, &crate_private))
+        .collect();
+    let clone_block = generate_clone_block(&fields, Field::is_saved);
+    let render_clone_block = generate_clone_block(&fields, Field::is_keyed);
+    let debug_impl = generate_redacted_debug(&ident, &generics, &fields);
+    let reload_body = generate_reload(&fields, &section, &crate_path);
+    let local_overlap_checks: Vec<_> = fields
+        .iter()
+        .filter(|field| field.is_keyed())
+        .flat_map(|field| {
+            fields
+                .iter()
+                .filter_map(|flat| Some((&flat.ident, flat.flatten.as_ref()?)))
+                .map(|(flat, ty)| {
+                    let key = LitStr::new(&field.key, field.span);
+                    let message = LitStr::new(
+                        &format!(
+                            "TOML key `{}` of field `{}` is also owned by flattened field `{flat}`",
+                            field.key, field.ident
+                        ),
+                        field.span,
+                    );
+                    quote_spanned! {field.span=>
+                        ::core::assert!(!<#ty as #fields_trait>::KEY_SET.contains(#key), #message);
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let overlap_prefix = LitStr::new(
+        &format!("flattened fields of `{ident}` share the TOML key `"),
+        ident.span(),
+    );
+    let eager_overlap_check = generics.params.is_empty().then(|| {
+        quote! { const _: () = <#ident as #fields_trait>::KEYS_DISJOINT; }
+    });
+
+    let post_load = post_load.map(|hook| quote! { #hook(&mut *guard)?; });
+    let pre_save = pre_save.map(|hook| {
+        quote! {
+            {
+                let mut guard = module.write().await;
+                #hook(&mut *guard)?;
+            }
+        }
+    });
+
+    let (version_load, version_save) = match &version {
+        Some(version) => generate_version_blocks(version, &section, &crate_private),
+        None => (TokenStream::new(), TokenStream::new()),
+    };
+    let known_keys = if shared_section {
+        quote! { ::core::option::Option::None }
+    } else {
+        quote! { ::core::option::Option::Some(Self::owned_keys()) }
+    };
+    // Renders the fields `save` writes into `rendered`, without touching the store.
+    let render_saved = quote! {
+        #pre_save
+        let scratch = #crate_path::Confer::default();
+        {
+            let guard = module.read().await;
+            <Self as #fields_trait>::save_fields(&*guard, &scratch, #section).await?;
+        }
+        {
+            let store = &scratch;
+            #version_save
+        }
+        let rendered = scratch.get_section_table(#section).await.unwrap_or_default();
+    };
 
-    let crate_private = quote! { #crate_path::__private };
     let shared_confer = quote! { #crate_path::SharedConfer };
     let shared_module = quote! { #crate_path::confer_module::SharedConferModule<Self> };
     let result_type = quote! { #crate_path::Result };
 
     Ok(quote! {
+        #[#crate_private::async_trait]
+        impl #impl_generics #fields_trait for #ident #ty_generics #where_clause {
+            const KEY_SET: #crate_private::KeySet = #crate_private::KeySet {
+                keys: &[#(#own_keys),*],
+                nested: &[#(&<#flattened as #fields_trait>::KEY_SET),*],
+            };
+            const SAVED_KEYS: #crate_private::KeySet = #crate_private::KeySet {
+                keys: &[#(#saved_keys),*],
+                nested: &[#(&<#flattened as #fields_trait>::SAVED_KEYS),*],
+            };
+            const KEYS_DISJOINT: () = {
+                #( #local_overlap_checks )*
+                if let ::core::option::Option::Some(key) = <Self as #fields_trait>::KEY_SET.first_shared_key() {
+                    ::core::panic!("{}", #crate_private::ConstMessage::new(&[#overlap_prefix, key, "`"]).as_str());
+                }
+            };
+
+            fn confer_defaults() -> Self {
+                Self { #(#init_fields),* }
+            }
+
+            async fn load_fields(&mut self, store: &#crate_path::Confer, section: &str) -> #result_type<()> {
+                let () = <Self as #fields_trait>::KEYS_DISJOINT;
+                #( #deprecation_blocks )*
+                #( #load_blocks )*
+                Ok(())
+            }
+
+            fn take_loaded(&mut self, #loaded_param: &mut Self) {
+                #( #take_loaded )*
+            }
+
+            async fn save_fields(&self, store: &#crate_path::Confer, section: &str) -> #result_type<()> {
+                #clone_block
+                #( #save_blocks )*
+                Ok(())
+            }
+
+            async fn render_fields(&self, store: &#crate_path::Confer, section: &str) -> #result_type<()> {
+                #render_clone_block
+                #( #render_blocks )*
+                Ok(())
+            }
+
+            fn field_schema(section: &'static str) -> ::std::vec::Vec<#crate_path::FieldSchema> {
+                let mut fields = ::std::vec::Vec::new();
+                #( #schema_entries )*
+                fields
+            }
+        }
+
         #[#crate_private::async_trait]
         impl #impl_generics #crate_path::confer_module::ConferModule for #ident #ty_generics #where_clause {
+            const SECTION: &'static str = #section;
+
+            fn owned_keys() -> &'static [&'static str] {
+                const SET: #crate_private::KeySet = #crate_private::KeySet {
+                    keys: &[#(#own_keys),*],
+                    nested: &[#(&<#flattened as #fields_trait>::KEY_SET),*],
+                };
+                const KEYS: [&str; SET.len() + #version_key_len] = #crate_private::owned_keys(&SET, #version_key_option);
+                &KEYS
+            }
+
             async fn from_confer(store: #shared_confer) -> #result_type<#shared_module> {
-                let value = Self { #(#init_fields),* };
-                let module = #crate_private::new_shared_module(value);
+                #crate_private::claim_module_section(
+                    &store,
+                    ::core::any::type_name::<Self>(),
+                    #section,
+                    Self::owned_keys(),
+                )?;
+                let module = #crate_private::new_shared_module(<Self as #fields_trait>::confer_defaults());
                 Self::load(&module, store).await?;
                 Ok(module)
             }
 
             async fn load(module: &#shared_module, store: #shared_confer) -> #result_type<()> {
-                #( #load_blocks )*
-                Ok(())
+                let trace = #crate_private::ModuleTrace::start(
+                    "load",
+                    ::core::any::type_name::<Self>(),
+                    #section,
+                    &<Self as #fields_trait>::KEY_SET,
+                );
+                trace.instrument(async {
+                    #version_load
+                    let snapshot = store.snapshot_section(#section).await;
+                    #crate_private::record_snapshot_of(&store, module, #section, &snapshot).await;
+                    let mut loaded = <Self as #fields_trait>::confer_defaults();
+                    <Self as #fields_trait>::load_fields(&mut loaded, &snapshot, #section).await?;
+                    let mut guard = module.write().await;
+                    <Self as #fields_trait>::take_loaded(&mut *guard, &mut loaded);
+                    #post_load
+                    Ok(())
+                }).await
             }
 
-            async fn save(module: &#shared_module, store: #shared_confer) -> #result_type<()> {
-                store.add_section(#section_lit).await?;
-                #clone_block
-                #( #save_blocks )*
+            async fn load_strict(module: &#shared_module, store: #shared_confer) -> #result_type<()> {
+                #version_load
+                #crate_private::reject_unknown_keys(&store, #section, Self::owned_keys()).await?;
+                Self::load(module, store).await
+            }
 
-                let existing = store.list_keys(#section_lit).await?;
-                for key in existing {
-                    if !(#known_keys_expr).contains(&key.as_str()) {
-                        store.remove_key(#section_lit, &key).await?;
+            async fn save(module: &#shared_module, store: #shared_confer) -> #result_type<()> {
+                let trace = #crate_private::ModuleTrace::start(
+                    "save",
+                    ::core::any::type_name::<Self>(),
+                    #section,
+                    &<Self as #fields_trait>::KEY_SET,
+                );
+                trace.instrument(async {
+                    #pre_save
+                    store.add_section(#section).await?;
+                    {
+                        let guard = module.read().await;
+                        <Self as #fields_trait>::save_fields(&*guard, &store, #section).await?;
                     }
-                }
+                    #version_save
+                    #prune_block
+                    #crate_private::record_load_snapshot(&store, module, #section).await;
+                    Ok(())
+                }).await
+            }
+
+            async fn save_changes(module: &#shared_module, store: #shared_confer) -> #result_type<()> {
+                #render_saved
+                store.add_section(#section).await?;
+                #crate_private::save_changes(&store, module, #section, rendered, |key| {
+                    <Self as #fields_trait>::SAVED_KEYS.contains(key) #version_key_match
+                })
+                .await?;
+                #crate_private::record_load_snapshot(&store, module, #section).await;
                 Ok(())
             }
+
+            fn schema() -> ::std::vec::Vec<#crate_path::FieldSchema> {
+                <Self as #fields_trait>::field_schema(#section)
+            }
+
+            async fn reload(module: &#shared_module, store: #shared_confer) -> #result_type<::std::vec::Vec<&'static str>> {
+                #reload_body
+            }
+
+            async fn save_if_unchanged(module: &#shared_module, store: #shared_confer) -> #result_type<bool> {
+                #render_saved
+                #crate_private::save_if_unchanged(&store, module, #section, rendered, |key| {
+                    <Self as #fields_trait>::SAVED_KEYS.contains(key) #version_key_match
+                }, #known_keys)
+                .await
+            }
         }
+
+        #eager_overlap_check
+
+        #debug_impl
     })
 }
 
-/// Produces the `let (...) = { ... }` block cloning mutable fields for persistence.
-fn generate_clone_block(fields: &[Field]) -> TokenStream {
+/// Produces the schema-version handling emitted at the start of `load` and end of `save`.
+fn generate_version_blocks(
+    version: &SchemaVersion,
+    section: &TokenStream,
+    crate_private: &TokenStream,
+) -> (TokenStream, TokenStream) {
+    let number = version.number;
+    let key = LitStr::new(VERSION_KEY, Span::call_site());
+    let migrate = match &version.migrate {
+        Some(migrate) => quote! {
+            ::core::option::Option::Some(|table: &mut #crate_private::toml::Table, from: u32| #migrate(table, from))
+        },
+        None => quote! {
+            ::core::option::Option::None::<fn(&mut #crate_private::toml::Table, u32) -> _>
+        },
+    };
+
+    let load = quote! {
+        #crate_private::apply_schema_version(&store, #section, #key, #number, #migrate).await?;
+    };
+    let save = quote! {
+        store.set_integer(#section, #key, i64::from(#number)).await?;
+    };
+    (load, save)
+}
+
+/// Emits a `Debug` impl masking `#[confer(secret)]` fields, or nothing when no field is secret.
+fn generate_redacted_debug(ident: &Ident, generics: &syn::Generics, fields: &[Field]) -> TokenStream {
+    if !fields.iter().any(|field| field.secret) {
+        return TokenStream::new();
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name = LitStr::new(&ident.to_string(), ident.span());
+    let entries = fields.iter().map(|field| {
+        let field_ident = &field.ident;
+        let label = LitStr::new(&field_ident.to_string(), field.span);
+        if field.secret {
+            quote! { .field(#label, &::core::format_args!("***")) }
+        } else {
+            quote! { .field(#label, &self.#field_ident) }
+        }
+    });
+
+    quote! {
+        impl #impl_generics ::core::fmt::Debug for #ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_struct(#name)
+                    #(#entries)*
+                    .finish()
+            }
+        }
+    }
+}
+
+/// Produces the `reload` body: render persisted fields before and after loading, and report the
+/// ones whose TOML values differ.
+fn generate_reload(fields: &[Field], section: &TokenStream, crate_path: &syn::Path) -> TokenStream {
+    let persisted: Vec<_> = fields.iter().filter(|field| !field.ignore).collect();
+    if persisted.is_empty() {
+        return quote! {
+            Self::load(module, store).await?;
+            Ok(::std::vec::Vec::new())
+        };
+    }
+
+    let crate_private = quote! { #crate_path::__private };
+    let names: Vec<_> = persisted
+        .iter()
+        .map(|field| LitStr::new(&field.ident.to_string(), field.span))
+        .collect();
+    let checks = persisted.iter().map(|field| match &field.flatten {
+        Some(ty) => quote! {
+            #crate_private::keys_changed(&before, &after, &<#ty as #crate_private::ConferFields>::KEY_SET)
+        },
+        None => {
+            let key = LitStr::new(&field.key, field.span);
+            quote! { before.get(#key) != after.get(#key) }
+        }
+    });
+
+    quote! {
+        let before = #crate_private::render_fields(&*module.read().await, #section).await?;
+        Self::load(module, store).await?;
+        let after = #crate_private::render_fields(&*module.read().await, #section).await?;
+        let mut changed = ::std::vec::Vec::new();
+        #(
+            if #checks {
+                changed.push(#names);
+            }
+        )*
+        Ok(changed)
+    }
+}
+
+/// Pushes the schema entry of one field, or the entries of a flattened field's type.
+fn generate_schema_entry(field: &Field, crate_path: &syn::Path, crate_private: &TokenStream) -> TokenStream {
+    if let Some(ty) = &field.flatten {
+        return quote! {
+            fields.extend(<#ty as #crate_private::ConferFields>::field_schema(section));
+        };
+    }
+    let key = LitStr::new(&field.key, field.span);
+    let type_name = LitStr::new(&field.type_name, field.span);
+    let required = field.is_required();
+    let default_repr = match &field.default_repr {
+        Some(repr) => {
+            let repr = LitStr::new(repr, field.span);
+            quote! { ::core::option::Option::Some(#repr) }
+        }
+        None => quote! { ::core::option::Option::None },
+    };
+    let value_type = match &field.kind {
+        Some(kind) => schema_type(kind, crate_path),
+        None if field.with.is_some() => quote! { #crate_path::SchemaType::Custom },
+        None => return TokenStream::new(),
+    };
+    quote! {
+        fields.push(#crate_private::field_schema(
+            section,
+            #key,
+            #type_name,
+            #required,
+            #default_repr,
+            #value_type,
+        ));
+    }
+}
+
+/// Maps a classified field type onto the runtime `SchemaType` describing its TOML value.
+fn schema_type(kind: &FieldType, crate_path: &syn::Path) -> TokenStream {
+    let schema_type = quote! { #crate_path::SchemaType };
+    let scalar = match kind.scalar {
+        ScalarKind::String | ScalarKind::Net(_) | ScalarKind::Bytes(_) => quote! { #schema_type::String },
+        ScalarKind::Bool => quote! { #schema_type::Boolean },
+        ScalarKind::Integer(_) | ScalarKind::NonZero(_) => quote! { #schema_type::Integer },
+        ScalarKind::Float(_) => quote! { #schema_type::Float },
+        ScalarKind::Datetime | ScalarKind::Date | ScalarKind::Time => quote! { #schema_type::Datetime },
+        ScalarKind::ByteSize => quote! { #schema_type::ByteSize },
+        ScalarKind::Value => quote! { #schema_type::Table },
+    };
+    let len = match kind.container {
+        ContainerKind::Plain | ContainerKind::Option | ContainerKind::Map => return scalar,
+        ContainerKind::Vec | ContainerKind::OptionVec => quote! { ::core::option::Option::None },
+        ContainerKind::Tuple(len) | ContainerKind::Array(len) => quote! { ::core::option::Option::Some(#len) },
+    };
+    quote! { #schema_type::Array { items: &#scalar, len: #len } }
+}
+
+/// Produces the `let (...) = (...)` statement cloning the fields selected by `include` out of
+/// `self` for persistence.
+fn generate_clone_block(fields: &[Field], include: fn(&Field) -> bool) -> TokenStream {
     let locals: Vec<_> = fields
         .iter()
-        .filter(|field| !field.ignore)
+        .filter(|field| include(field))
         .map(|field| field.ident.clone())
         .collect();
 
     if locals.is_empty() {
-        return quote! {
-            let guard = module.read().await;
-            drop(guard);
-        };
+        return TokenStream::new();
     }
 
     quote! {
-        let (#(#locals),*) = {
-            let guard = module.read().await;
-            ( #(guard.#locals.clone()),* )
-        };
+        let (#(#locals),*) = ( #(self.#locals.clone()),* );
     }
 }
 
 /// Generates the load logic for a single field, including defaults and conversions.
-fn generate_load(field: &Field, section: &LitStr, crate_path: &syn::Path) -> Result<TokenStream> {
+fn generate_load(field: &Field, section: &TokenStream, crate_path: &syn::Path) -> Result<TokenStream> {
+    if let Some(ty) = &field.flatten {
+        let ident = &field.ident;
+        let bounds = quote_spanned! { ty.span()=> #crate_path::__private::assert_flatten_field::<#ty>(); };
+        return Ok(quote! {
+            #bounds
+            <#ty as #crate_path::__private::ConferFields>::load_fields(&mut self.#ident, store, #section).await?;
+        });
+    }
+    if let Some(with) = &field.with {
+        return Ok(load_with(field, with, section, crate_path));
+    }
+
     let Field {
         ident,
         key,
@@ -131,13 +559,16 @@ fn generate_load(field: &Field, section: &LitStr, crate_path: &syn::Path) -> Res
     let fetch = fetch_expression(kind, section, &key_lit);
     let converted = convert_from_store(kind, section, &key_lit, crate_path);
     let assign = assign_converted(kind, ident);
-    let on_missing = missing_behavior(kind, ident, default.as_ref(), section, &key_lit, crate_path);
+    let on_missing = if field.required {
+        quote! { return Err(#crate_path::ConferError::missing_key(#section, #key_lit)); }
+    } else {
+        missing_behavior(kind, ident, default.as_ref(), section, &key_lit, crate_path)
+    };
 
     Ok(quote! {
         match #fetch {
             Ok(value) => {
                 let converted = { #converted };
-                let mut guard = module.write().await;
                 #assign
             }
             Err(err) => match err {
@@ -148,8 +579,23 @@ fn generate_load(field: &Field, section: &LitStr, crate_path: &syn::Path) -> Res
     })
 }
 
-/// Generates the save logic for a single field, respecting optionality and vectors.
-fn generate_save(field: &Field, section: &LitStr, crate_path: &syn::Path) -> Result<TokenStream> {
+/// Generates the save logic for a single field, respecting optionality and vectors. Flattened
+/// fields delegate to the `nested` `ConferFields` method.
+fn generate_save(field: &Field, section: &TokenStream, crate_path: &syn::Path, nested: &Ident) -> Result<TokenStream> {
+    if let Some(ty) = &field.flatten {
+        let ident = &field.ident;
+        return Ok(quote! {
+            <#ty as #crate_path::__private::ConferFields>::#nested(&self.#ident, store, #section).await?;
+        });
+    }
+    if let Some(with) = &field.with {
+        let ident = &field.ident;
+        let key_lit = LitStr::new(&field.key, field.span);
+        return Ok(quote! {
+            store.set_value(#section, #key_lit, #with::store(&#ident)).await?;
+        });
+    }
+
     let Field { ident, key, kind, .. } = field;
 
     let kind = kind
@@ -163,24 +609,64 @@ fn generate_save(field: &Field, section: &LitStr, crate_path: &syn::Path) -> Res
         ContainerKind::Vec => save_vec(kind, ident, section, &key_lit, crate_path),
         ContainerKind::Option => save_option(kind, ident, section, &key_lit, crate_path),
         ContainerKind::OptionVec => save_option_vec(kind, ident, section, &key_lit, crate_path),
+        ContainerKind::Tuple(arity) => {
+            let indices = (0..arity).map(syn::Index::from);
+            let items = quote! { vec![#(#ident.#indices),*] };
+            save_fixed(kind, items, section, &key_lit, crate_path)
+        }
+        ContainerKind::Array(_) => {
+            let items = quote! { ::std::vec::Vec::from(#ident) };
+            save_fixed(kind, items, section, &key_lit, crate_path)
+        }
+        ContainerKind::Map => quote! {
+            store.set_table(#section, #key_lit, #ident.into_iter().collect()).await?;
+        },
     };
 
     Ok(block)
 }
 
+/// Loads a `#[confer(with = ...)]` field through its module's `load` function.
+fn load_with(field: &Field, with: &syn::Path, section: &TokenStream, crate_path: &syn::Path) -> TokenStream {
+    let ident = &field.ident;
+    let key_lit = LitStr::new(&field.key, field.span);
+    let on_missing = match &field.default {
+        Some(default) => quote! { self.#ident = #default; },
+        None => quote! { return Err(#crate_path::ConferError::missing_key(#section, #key_lit)); },
+    };
+    quote! {
+        match store.get_value(#section, #key_lit).await {
+            ::core::option::Option::Some(value) => {
+                self.#ident = #with::load(value)?;
+            }
+            ::core::option::Option::None => { #on_missing }
+        }
+    }
+}
+
 /// Selects the appropriate async getter call for a field based on its kind.
-fn fetch_expression(kind: &FieldType, section: &LitStr, key: &LitStr) -> TokenStream {
-    let method = match (kind.container, &kind.scalar) {
-        (ContainerKind::Vec, ScalarKind::String) | (ContainerKind::OptionVec, ScalarKind::String) => "get_string_vec",
-        (ContainerKind::Vec, ScalarKind::Bool) | (ContainerKind::OptionVec, ScalarKind::Bool) => "get_boolean_vec",
-        (ContainerKind::Vec, ScalarKind::Integer(_)) | (ContainerKind::OptionVec, ScalarKind::Integer(_)) => "get_integer_vec",
-        (ContainerKind::Vec, ScalarKind::Float(_)) | (ContainerKind::OptionVec, ScalarKind::Float(_)) => "get_float_vec",
-        (ContainerKind::Vec, ScalarKind::Datetime) | (ContainerKind::OptionVec, ScalarKind::Datetime) => "get_datetime_vec",
-        (_, ScalarKind::String) => "get_string",
-        (_, ScalarKind::Bool) => "get_boolean",
-        (_, ScalarKind::Integer(_)) => "get_integer",
-        (_, ScalarKind::Float(_)) => "get_float",
-        (_, ScalarKind::Datetime) => "get_datetime",
+fn fetch_expression(kind: &FieldType, section: &TokenStream, key: &LitStr) -> TokenStream {
+    let array = !matches!(kind.container, ContainerKind::Plain | ContainerKind::Option);
+    let method = match (array, &kind.scalar) {
+        (_, ScalarKind::Value) => "get_table",
+        (_, ScalarKind::Bytes(BytesEncoding::Base64)) => "get_base64",
+        (_, ScalarKind::Bytes(BytesEncoding::Hex)) => "get_hex",
+        (true, ScalarKind::String | ScalarKind::Net(_)) => "get_string_vec",
+        (true, ScalarKind::Bool) => "get_boolean_vec",
+        (true, ScalarKind::Integer(_) | ScalarKind::NonZero(_)) => "get_integer_vec",
+        (true, ScalarKind::Float(_)) => "get_float_vec",
+        (true, ScalarKind::Datetime) => "get_datetime_vec",
+        (true, ScalarKind::Date) => "get_date_vec",
+        (true, ScalarKind::Time) => "get_time_vec",
+        (true, ScalarKind::ByteSize) => "get_byte_size_vec",
+        (false, ScalarKind::String | ScalarKind::Net(_)) => "get_string",
+        (false, ScalarKind::Bool) => "get_boolean",
+        (false, ScalarKind::Integer(_) | ScalarKind::NonZero(_)) => "get_integer",
+        (false, ScalarKind::Float(_)) => "get_float",
+        (false, ScalarKind::Datetime) => "get_datetime",
+        (false, ScalarKind::Date) => "get_date",
+        (false, ScalarKind::Time) => "get_time",
+        (false, ScalarKind::ByteSize) => "get_byte_size",
     };
 
     let ident = Ident::new(method, Span::call_site());
@@ -190,21 +676,62 @@ fn fetch_expression(kind: &FieldType, section: &LitStr, key: &LitStr) -> TokenSt
 /// Converts the raw value obtained from the store into the field's Rust type.
 fn convert_from_store(
     kind: &FieldType,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
     match kind.container {
         ContainerKind::Plain | ContainerKind::Option => scalar_from_store(&kind.scalar, section, key, crate_path),
         ContainerKind::Vec | ContainerKind::OptionVec => vec_from_store(&kind.scalar, section, key, crate_path),
+        ContainerKind::Tuple(arity) => {
+            let items = vec_from_store(&kind.scalar, section, key, crate_path);
+            let array = array_from_vec(items, arity, section, key, crate_path);
+            let bindings: Vec<_> = (0..arity).map(|index| format_ident!("item{}", index)).collect();
+            quote! {
+                {
+                    let [#(#bindings),*] = #array;
+                    (#(#bindings,)*)
+                }
+            }
+        }
+        ContainerKind::Array(len) => {
+            let items = vec_from_store(&kind.scalar, section, key, crate_path);
+            array_from_vec(items, len, section, key, crate_path)
+        }
+        ContainerKind::Map => quote! { value.into_iter().collect() },
+    }
+}
+
+/// Converts a loaded vector into `[T; len]`, rejecting arrays of the wrong length.
+fn array_from_vec(
+    items: TokenStream,
+    len: usize,
+    section: &TokenStream,
+    key: &LitStr,
+    crate_path: &syn::Path,
+) -> TokenStream {
+    let err = quote! { #crate_path::ConferError };
+    quote! {
+        match <[_; #len]>::try_from(#items) {
+            Ok(array) => array,
+            Err(items) => {
+                return Err(#err::value_parse_owned(#section, #key, format!("expected {} elements, found {}", #len, items.len())));
+            }
+        }
     }
 }
 
-/// Emits the assignment into the guard, taking optional containers into account.
+/// Emits the assignment into `self`, taking optional containers into account.
 fn assign_converted(kind: &FieldType, ident: &Ident) -> TokenStream {
     match kind.container {
-        ContainerKind::Plain | ContainerKind::Vec => quote! { guard.#ident = converted; },
-        ContainerKind::Option | ContainerKind::OptionVec => quote! { guard.#ident = ::core::option::Option::Some(converted); },
+        ContainerKind::Plain
+        | ContainerKind::Vec
+        | ContainerKind::Tuple(_)
+        | ContainerKind::Array(_)
+        | ContainerKind::Map => {
+            quote! { self.#ident = converted; }
+        }
+        ContainerKind::Option | ContainerKind::OptionVec => quote! { self.#ident = ::core::option::Option::Some(converted); },
     }
 }
 
@@ -213,16 +740,19 @@ fn missing_behavior(
     kind: &FieldType,
     ident: &Ident,
     default: Option<&TokenStream>,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
     match kind.container {
-        ContainerKind::Plain | ContainerKind::Vec => {
+        ContainerKind::Plain
+        | ContainerKind::Vec
+        | ContainerKind::Tuple(_)
+        | ContainerKind::Array(_)
+        | ContainerKind::Map => {
             if let Some(default) = default {
                 quote! {
-                    let mut guard = module.write().await;
-                    guard.#ident = #default;
+                    self.#ident = #default;
                 }
             } else {
                 quote! { return Err(#crate_path::ConferError::missing_key(#section, #key)); }
@@ -231,13 +761,11 @@ fn missing_behavior(
         ContainerKind::Option | ContainerKind::OptionVec => {
             if let Some(default) = default {
                 quote! {
-                    let mut guard = module.write().await;
-                    guard.#ident = #default;
+                    self.#ident = #default;
                 }
             } else {
                 quote! {
-                    let mut guard = module.write().await;
-                    guard.#ident = ::core::option::Option::None;
+                    self.#ident = ::core::option::Option::None;
                 }
             }
         }
@@ -248,7 +776,7 @@ fn missing_behavior(
 fn save_plain(
     kind: &FieldType,
     ident: &Ident,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
@@ -264,7 +792,7 @@ fn save_plain(
 fn save_vec(
     kind: &FieldType,
     ident: &Ident,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
@@ -276,11 +804,27 @@ fn save_vec(
     }
 }
 
+/// Persists tuple and fixed-size array fields, already collected into `items`, as TOML arrays.
+fn save_fixed(
+    kind: &FieldType,
+    items: TokenStream,
+    section: &TokenStream,
+    key: &LitStr,
+    crate_path: &syn::Path,
+) -> TokenStream {
+    let setter = setter_name(kind, true);
+    let setter_ident = Ident::new(setter, Span::call_site());
+    let value = vec_to_store(&kind.scalar, items, section, key, crate_path);
+    quote! {
+        store.#setter_ident(#section, #key, #value).await?;
+    }
+}
+
 /// Persists `Option<T>` fields, removing keys when the value is `None`.
 fn save_option(
     kind: &FieldType,
     ident: &Ident,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
@@ -303,7 +847,7 @@ fn save_option(
 fn save_option_vec(
     kind: &FieldType,
     ident: &Ident,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
@@ -325,44 +869,77 @@ fn save_option_vec(
 /// Resolves the setter method name for a given field.
 fn setter_name(kind: &FieldType, vec: bool) -> &'static str {
     match (vec, &kind.scalar) {
-        (false, ScalarKind::String) => "set_string",
+        (_, ScalarKind::Value) => "set_table",
+        (_, ScalarKind::Bytes(BytesEncoding::Base64)) => "set_base64",
+        (_, ScalarKind::Bytes(BytesEncoding::Hex)) => "set_hex",
+        (false, ScalarKind::String | ScalarKind::Net(_)) => "set_string",
         (false, ScalarKind::Bool) => "set_boolean",
-        (false, ScalarKind::Integer(_)) => "set_integer",
+        (false, ScalarKind::Integer(_) | ScalarKind::NonZero(_)) => "set_integer",
         (false, ScalarKind::Float(_)) => "set_float",
         (false, ScalarKind::Datetime) => "set_datetime",
-        (true, ScalarKind::String) => "set_string_vec",
+        (false, ScalarKind::Date) => "set_date",
+        (false, ScalarKind::Time) => "set_time",
+        (false, ScalarKind::ByteSize) => "set_byte_size",
+        (true, ScalarKind::String | ScalarKind::Net(_)) => "set_string_vec",
         (true, ScalarKind::Bool) => "set_boolean_vec",
-        (true, ScalarKind::Integer(_)) => "set_integer_vec",
+        (true, ScalarKind::Integer(_) | ScalarKind::NonZero(_)) => "set_integer_vec",
         (true, ScalarKind::Float(_)) => "set_float_vec",
         (true, ScalarKind::Datetime) => "set_datetime_vec",
+        (true, ScalarKind::Date) => "set_date_vec",
+        (true, ScalarKind::Time) => "set_time_vec",
+        (true, ScalarKind::ByteSize) => "set_byte_size_vec",
     }
 }
 
 /// Applies container-specific conversions for scalar fields.
 fn scalar_from_store(
     scalar: &ScalarKind,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
     match scalar {
-        ScalarKind::String | ScalarKind::Bool | ScalarKind::Datetime => quote! { value },
+        ScalarKind::String
+        | ScalarKind::Bool
+        | ScalarKind::Datetime
+        | ScalarKind::Date
+        | ScalarKind::Time
+        | ScalarKind::ByteSize
+        | ScalarKind::Bytes(_)
+        | ScalarKind::Value => quote! { value },
         ScalarKind::Integer(kind) => integer_from_store(kind, section, key, crate_path),
+        ScalarKind::NonZero(kind) => {
+            let value = integer_from_store(kind, section, key, crate_path);
+            non_zero_from_store(value, section, key, crate_path)
+        }
         ScalarKind::Float(kind) => float_from_store(kind, section, key, crate_path),
+        ScalarKind::Net(kind) => net_from_store(kind, section, key, crate_path),
     }
 }
 
 /// Applies container-specific conversions for vector fields.
 fn vec_from_store(
     scalar: &ScalarKind,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
     match scalar {
-        ScalarKind::String | ScalarKind::Bool | ScalarKind::Datetime => quote! { value },
+        ScalarKind::String
+        | ScalarKind::Bool
+        | ScalarKind::Datetime
+        | ScalarKind::Date
+        | ScalarKind::Time
+        | ScalarKind::ByteSize
+        | ScalarKind::Bytes(_)
+        | ScalarKind::Value => quote! { value },
         ScalarKind::Integer(kind) => integer_vec_from_store(kind, section, key, crate_path),
+        ScalarKind::NonZero(kind) => {
+            let value = integer_vec_from_store(kind, section, key, crate_path);
+            non_zero_vec_from_store(value, section, key, crate_path)
+        }
         ScalarKind::Float(kind) => float_vec_from_store(kind, section, key, crate_path),
+        ScalarKind::Net(kind) => net_vec_from_store(kind, section, key, crate_path),
     }
 }
 
@@ -370,14 +947,23 @@ fn vec_from_store(
 fn scalar_to_store(
     scalar: &ScalarKind,
     value: TokenStream,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
     match scalar {
-        ScalarKind::String | ScalarKind::Bool | ScalarKind::Datetime => value,
+        ScalarKind::String
+        | ScalarKind::Bool
+        | ScalarKind::Datetime
+        | ScalarKind::Date
+        | ScalarKind::Time
+        | ScalarKind::ByteSize
+        | ScalarKind::Bytes(_)
+        | ScalarKind::Value => value,
         ScalarKind::Integer(kind) => integer_to_store(kind, value, section, key, crate_path),
+        ScalarKind::NonZero(kind) => integer_to_store(kind, quote! { #value.get() }, section, key, crate_path),
         ScalarKind::Float(kind) => float_to_store(kind, value),
+        ScalarKind::Net(_) => quote! { #value.to_string() },
     }
 }
 
@@ -385,21 +971,33 @@ fn scalar_to_store(
 fn vec_to_store(
     scalar: &ScalarKind,
     value: TokenStream,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
     match scalar {
-        ScalarKind::String | ScalarKind::Bool | ScalarKind::Datetime => value,
+        ScalarKind::String
+        | ScalarKind::Bool
+        | ScalarKind::Datetime
+        | ScalarKind::Date
+        | ScalarKind::Time
+        | ScalarKind::ByteSize
+        | ScalarKind::Bytes(_)
+        | ScalarKind::Value => value,
         ScalarKind::Integer(kind) => integer_vec_to_store(kind, value, section, key, crate_path),
+        ScalarKind::NonZero(kind) => {
+            let value = quote! { #value.into_iter().map(|v| v.get()).collect::<Vec<_>>() };
+            integer_vec_to_store(kind, value, section, key, crate_path)
+        }
         ScalarKind::Float(kind) => float_vec_to_store(kind, value),
+        ScalarKind::Net(_) => quote! { #value.iter().map(|v| v.to_string()).collect::<Vec<_>>() },
     }
 }
 
 /// Validates and converts TOML integers into the appropriate Rust integer type.
 fn integer_from_store(
     kind: &IntegerKind,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
@@ -415,10 +1013,53 @@ fn integer_from_store(
     }
 }
 
+/// Wraps an already range-checked integer in `NonZero`, rejecting zero.
+fn non_zero_from_store(
+    value: TokenStream,
+    section: &TokenStream,
+    key: &LitStr,
+    crate_path: &syn::Path,
+) -> TokenStream {
+    let err = quote! { #crate_path::ConferError };
+    quote! {
+        match ::core::num::NonZero::new(#value) {
+            Some(v) => v,
+            None => {
+                return Err(#err::value_parse(#section, #key, "value must be non-zero"));
+            }
+        }
+    }
+}
+
+/// Wraps every element of a range-checked integer vector in `NonZero`, rejecting zeros.
+fn non_zero_vec_from_store(
+    value: TokenStream,
+    section: &TokenStream,
+    key: &LitStr,
+    crate_path: &syn::Path,
+) -> TokenStream {
+    let err = quote! { #crate_path::ConferError };
+    quote! {
+        {
+            let value = #value;
+            let mut out = Vec::with_capacity(value.len());
+            for (index, raw) in value.into_iter().enumerate() {
+                match ::core::num::NonZero::new(raw) {
+                    Some(v) => out.push(v),
+                    None => {
+                        return Err(#err::value_parse_owned(#section, #key, format!("value must be non-zero (at index {})", index)));
+                    }
+                }
+            }
+            out
+        }
+    }
+}
+
 /// Validates and converts TOML floats into the requested Rust float type.
 fn float_from_store(
     kind: &FloatKind,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
@@ -442,10 +1083,30 @@ fn float_from_store(
     }
 }
 
+/// Parses a TOML string into the requested network address type.
+fn net_from_store(
+    kind: &NetKind,
+    section: &TokenStream,
+    key: &LitStr,
+    crate_path: &syn::Path,
+) -> TokenStream {
+    let err = quote! { #crate_path::ConferError };
+    let ty = kind.type_tokens();
+    let description = kind.description();
+    quote! {
+        match <#ty as ::core::str::FromStr>::from_str(&value) {
+            Ok(v) => v,
+            Err(parse_err) => {
+                return Err(#err::value_parse_owned(#section, #key, format!("invalid {} `{}`: {}", #description, value, parse_err)));
+            }
+        }
+    }
+}
+
 /// Validates and converts TOML integer arrays into typed Rust vectors.
 fn integer_vec_from_store(
     kind: &IntegerKind,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
@@ -470,7 +1131,7 @@ fn integer_vec_from_store(
 /// Validates and converts TOML float arrays into typed Rust vectors.
 fn float_vec_from_store(
     kind: &FloatKind,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
@@ -497,11 +1158,37 @@ fn float_vec_from_store(
     }
 }
 
+/// Parses TOML string arrays into typed network address vectors.
+fn net_vec_from_store(
+    kind: &NetKind,
+    section: &TokenStream,
+    key: &LitStr,
+    crate_path: &syn::Path,
+) -> TokenStream {
+    let err = quote! { #crate_path::ConferError };
+    let ty = kind.type_tokens();
+    let description = kind.description();
+    quote! {
+        {
+            let mut out = Vec::with_capacity(value.len());
+            for (index, raw) in value.into_iter().enumerate() {
+                match <#ty as ::core::str::FromStr>::from_str(&raw) {
+                    Ok(v) => out.push(v),
+                    Err(parse_err) => {
+                        return Err(#err::value_parse_owned(#section, #key, format!("invalid {} `{}`: {} (at index {})", #description, raw, parse_err, index)));
+                    }
+                }
+            }
+            out
+        }
+    }
+}
+
 /// Widens Rust integers to TOML's signed 64-bit representation, checking ranges when needed.
 fn integer_to_store(
     kind: &IntegerKind,
     value: TokenStream,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
@@ -536,7 +1223,7 @@ fn float_to_store(kind: &FloatKind, value: TokenStream) -> TokenStream {
 fn integer_vec_to_store(
     kind: &IntegerKind,
     value: TokenStream,
-    section: &LitStr,
+    section: &TokenStream,
     key: &LitStr,
     crate_path: &syn::Path,
 ) -> TokenStream {
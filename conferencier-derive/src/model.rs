@@ -6,10 +6,80 @@ use syn::Ident;
 pub struct Module {
     pub ident: Ident,
     pub generics: syn::Generics,
-    pub section: String,
+    /// Expression yielding the section name: a string literal, a const path, or e.g. `env!(...)`.
+    pub section: TokenStream,
+    pub version: Option<SchemaVersion>,
+    pub shared_section: bool,
+    /// Function called with `&mut Self` at the end of `load`.
+    pub post_load: Option<TokenStream>,
+    /// Function called with `&mut Self` at the start of `save`.
+    pub pre_save: Option<TokenStream>,
     pub fields: Vec<Field>,
 }
 
+/// Reserved key under which versioned modules store their schema version.
+pub const VERSION_KEY: &str = "__version";
+
+/// Schema version declared via `#[confer(version = N, migrate = ...)]`.
+#[derive(Debug, Clone)]
+pub struct SchemaVersion {
+    pub number: u32,
+    pub migrate: Option<TokenStream>,
+}
+
+/// Key casing applied to every field via `#[confer(rename_all = "...")]`.
+#[derive(Debug, Clone, Copy)]
+pub enum RenameRule {
+    Snake,
+    Kebab,
+    Camel,
+    Pascal,
+    ScreamingSnake,
+}
+
+impl RenameRule {
+    /// Accepted spellings, matching serde's `rename_all` values.
+    pub const NAMES: &'static str =
+        "\"snake_case\", \"kebab-case\", \"camelCase\", \"PascalCase\", \"SCREAMING_SNAKE_CASE\"";
+
+    /// Parses the attribute value, returning `None` for unknown rules.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "snake_case" => Some(Self::Snake),
+            "kebab-case" => Some(Self::Kebab),
+            "camelCase" => Some(Self::Camel),
+            "PascalCase" => Some(Self::Pascal),
+            "SCREAMING_SNAKE_CASE" => Some(Self::ScreamingSnake),
+            _ => None,
+        }
+    }
+
+    /// Converts a `snake_case` field name into the rule's casing.
+    pub fn apply(&self, field: &str) -> String {
+        match self {
+            Self::Snake => field.to_string(),
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Camel | Self::Pascal => {
+                let mut out = String::with_capacity(field.len());
+                let mut upper = matches!(self, Self::Pascal);
+                for ch in field.chars() {
+                    if ch == '_' {
+                        // Leading underscores are dropped without resetting the first letter's case.
+                        upper |= !out.is_empty();
+                    } else if upper {
+                        out.push(ch.to_ascii_uppercase());
+                        upper = false;
+                    } else {
+                        out.push(ch);
+                    }
+                }
+                out
+            }
+        }
+    }
+}
+
 /// Description of a single field within a derived module.
 #[derive(Debug, Clone)]
 pub struct Field {
@@ -19,9 +89,51 @@ pub struct Field {
     pub default: Option<TokenStream>,
     pub init: Option<TokenStream>,
     pub ignore: bool,
+    pub secret: bool,
+    /// Loaded normally but never written or pruned by `save`.
+    pub skip_save: bool,
+    /// Saved normally but never read by `load`, keeping its initialized value.
+    pub skip_load: bool,
+    /// `Option` field whose key must still be present on load.
+    pub required: bool,
+    /// Module named by `#[confer(with = "...")]`, whose `load`/`store` functions replace the
+    /// built-in conversion.
+    pub with: Option<syn::Path>,
+    /// Message from `#[confer(deprecated = "...")]`, warned about when the key is present on load.
+    pub deprecated: Option<String>,
+    /// Type of a `#[confer(flatten)]` field, whose keys are inlined into the parent section.
+    pub flatten: Option<syn::Type>,
+    /// Field type as written, reported by the generated schema.
+    pub type_name: String,
+    /// Source text of the default, reported by the generated schema.
+    pub default_repr: Option<String>,
     pub span: Span,
 }
 
+impl Field {
+    /// Returns `true` for fields stored under their own key in the module's section.
+    pub fn is_keyed(&self) -> bool {
+        !self.ignore && self.flatten.is_none()
+    }
+
+    /// Returns `true` for keyed fields that `save` writes back.
+    pub fn is_saved(&self) -> bool {
+        self.is_keyed() && !self.skip_save
+    }
+
+    /// Returns `true` when loading fails if the field's key is absent.
+    pub fn is_required(&self) -> bool {
+        if self.skip_load {
+            return false;
+        }
+        match self.kind.as_ref().map(|kind| kind.container) {
+            Some(ContainerKind::Option | ContainerKind::OptionVec) => self.required,
+            Some(_) => self.default.is_none(),
+            None => self.with.is_some() && self.default.is_none(),
+        }
+    }
+}
+
 /// Fully classified field type, including container and scalar information.
 #[derive(Debug, Clone)]
 pub struct FieldType {
@@ -36,6 +148,12 @@ pub enum ContainerKind {
     Vec,
     Option,
     OptionVec,
+    /// Homogeneous tuple stored as a TOML array of exactly this many elements.
+    Tuple(usize),
+    /// Fixed-size array `[T; N]` stored as a TOML array of exactly `N` elements.
+    Array(usize),
+    /// `HashMap<String, T>` stored as a nested table under the field's key.
+    Map,
 }
 
 /// Primitive scalar type available for derived configuration fields.
@@ -44,8 +162,28 @@ pub enum ScalarKind {
     String,
     Bool,
     Integer(IntegerKind),
+    /// `std::num::NonZero*` wrapper around an integer width.
+    NonZero(IntegerKind),
     Float(FloatKind),
     Datetime,
+    /// `toml::value::Date`, a datetime holding only a local date.
+    Date,
+    /// `toml::value::Time`, a datetime holding only a local time.
+    Time,
+    Net(NetKind),
+    /// `conferencier::ByteSize`, stored as raw bytes or a string with a unit suffix.
+    ByteSize,
+    /// `Vec<u8>` stored as a single encoded string, selected by `#[confer(bytes = ...)]`.
+    Bytes(BytesEncoding),
+    /// Raw `toml::Value`, kept as-is; only valid as a map value.
+    Value,
+}
+
+/// Text encodings available for `#[confer(bytes = ...)]` fields.
+#[derive(Debug, Clone, Copy)]
+pub enum BytesEncoding {
+    Base64,
+    Hex,
 }
 
 /// Supported integer widths mapped from TOML values.
@@ -70,6 +208,13 @@ pub enum FloatKind {
     F64,
 }
 
+/// Supported network address types stored as TOML strings.
+#[derive(Debug, Clone, Copy)]
+pub enum NetKind {
+    IpAddr,
+    SocketAddr,
+}
+
 impl IntegerKind {
     /// Returns the Rust type tokens for the integer variant.
     pub fn type_tokens(&self) -> TokenStream {
@@ -97,3 +242,21 @@ impl FloatKind {
         }
     }
 }
+
+impl NetKind {
+    /// Returns the fully qualified Rust type tokens for the address variant.
+    pub fn type_tokens(&self) -> TokenStream {
+        match self {
+            Self::IpAddr => quote::quote!(::std::net::IpAddr),
+            Self::SocketAddr => quote::quote!(::std::net::SocketAddr),
+        }
+    }
+
+    /// Human-readable name used in parse error messages.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::IpAddr => "IP address",
+            Self::SocketAddr => "socket address",
+        }
+    }
+}
@@ -1,12 +1,17 @@
 use std::collections::HashMap;
 
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::meta::ParseNestedMeta;
 use syn::spanned::Spanned;
-use syn::{Attribute, DeriveInput, Expr, Field as SynField, Fields, Lit, LitStr, Result, Type};
+use syn::{
+    Attribute, DeriveInput, Expr, Field as SynField, Fields, Ident, Lit, LitInt, LitStr, Result, Type,
+};
 
-use crate::model::{ContainerKind, Field, FieldType, FloatKind, IntegerKind, Module, ScalarKind};
+use crate::model::{
+    BytesEncoding, ContainerKind, Field, FieldType, FloatKind, IntegerKind, Module, NetKind, RenameRule,
+    ScalarKind, SchemaVersion, VERSION_KEY,
+};
 
 /// Parses the derive input into the intermediate `Module` representation.
 pub fn parse_module(input: DeriveInput) -> Result<Module> {
@@ -18,7 +23,14 @@ pub fn parse_module(input: DeriveInput) -> Result<Module> {
         ..
     } = input;
 
-    let section = parse_section_name(&attrs, &ident)?;
+    if let Some(lifetime) = generics.lifetimes().next() {
+        return Err(syn::Error::new(
+            lifetime.span(),
+            "#[derive(ConferModule)] does not support lifetime parameters: ConferModule requires `'static` types, so use owned fields instead",
+        ));
+    }
+
+    let options = parse_module_options(&attrs, &ident)?;
 
     let data = match data {
         syn::Data::Struct(data) => data,
@@ -44,20 +56,92 @@ pub fn parse_module(input: DeriveInput) -> Result<Module> {
     let mut seen_keys: HashMap<String, Span> = HashMap::new();
 
     for field in fields {
-        result_fields.push(parse_field(&field, &mut seen_keys)?);
+        result_fields.push(parse_field(&field, options.rename_all, &mut seen_keys)?);
+    }
+
+    let type_params: Vec<Ident> = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .chain(generics.const_params().map(|param| param.ident.clone()))
+        .collect();
+    if let Some(ty) = result_fields
+        .iter()
+        .filter_map(|field| field.flatten.as_ref())
+        .find(|ty| mentions_ident(quote! { #ty }, &type_params))
+    {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "#[confer(flatten)] field types cannot depend on the struct's generic parameters",
+        ));
+    }
+
+    if options.version.is_some()
+        && let Some(field) = result_fields
+            .iter()
+            .find(|field| field.is_keyed() && field.key == VERSION_KEY)
+    {
+        return Err(syn::Error::new(
+            field.span,
+            format!("TOML key `{VERSION_KEY}` is reserved by #[confer(version = ...)]"),
+        ));
+    }
+
+    if options.bare_keys {
+        for field in result_fields.iter().filter(|field| field.is_keyed()) {
+            if !is_bare_key(&field.key) {
+                return Err(syn::Error::new(
+                    field.span,
+                    format!(
+                        "TOML key `{}` is not a valid bare key (only A-Za-z0-9_- allowed) under #[confer(bare_keys)]",
+                        field.key
+                    ),
+                ));
+            }
+        }
     }
 
     Ok(Module {
         ident,
         generics,
-        section,
+        section: options.section,
+        version: options.version,
+        shared_section: options.shared_section,
+        post_load: options.post_load.map(|expr| quote! { #expr }),
+        pre_save: options.pre_save.map(|expr| quote! { #expr }),
         fields: result_fields,
     })
 }
 
-/// Extracts the TOML section name from the `#[confer(...)]` attributes or generates a default.
-fn parse_section_name(attrs: &[Attribute], ident: &syn::Ident) -> Result<String> {
-    let mut section: Option<String> = None;
+/// Returns `true` when `tokens` contain any identifier in `names`.
+fn mentions_ident(tokens: TokenStream, names: &[Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => names.contains(&ident),
+        proc_macro2::TokenTree::Group(group) => mentions_ident(group.stream(), names),
+        _ => false,
+    })
+}
+
+/// Struct-level settings gathered from `#[confer(...)]` attributes.
+struct ModuleOptions {
+    section: TokenStream,
+    bare_keys: bool,
+    rename_all: Option<RenameRule>,
+    shared_section: bool,
+    post_load: Option<Expr>,
+    pre_save: Option<Expr>,
+    version: Option<SchemaVersion>,
+}
+
+/// Extracts struct-level `#[confer(...)]` options, generating a default section name if needed.
+fn parse_module_options(attrs: &[Attribute], ident: &syn::Ident) -> Result<ModuleOptions> {
+    let mut section: Option<Expr> = None;
+    let mut bare_keys = false;
+    let mut rename_all: Option<RenameRule> = None;
+    let mut shared_section = false;
+    let mut post_load: Option<Expr> = None;
+    let mut pre_save: Option<Expr> = None;
+    let mut version: Option<u32> = None;
+    let mut migrate: Option<Expr> = None;
 
     for attr in attrs {
         if !is_confer_attr(attr) {
@@ -69,8 +153,65 @@ fn parse_section_name(attrs: &[Attribute], ident: &syn::Ident) -> Result<String>
                 if section.is_some() {
                     return Err(meta.error("duplicate #[confer(section = ...)] attribute"));
                 }
+                let value: Expr = meta.value()?.parse()?;
+                if let Expr::Lit(syn::ExprLit { lit, .. }) = &value
+                    && !matches!(lit, Lit::Str(_))
+                {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "expected a string literal, a const path, or a macro such as env!(...)",
+                    ));
+                }
+                section = Some(value);
+                Ok(())
+            } else if meta.path.is_ident("bare_keys") {
+                if bare_keys {
+                    return Err(meta.error("duplicate #[confer(bare_keys)] attribute"));
+                }
+                bare_keys = true;
+                Ok(())
+            } else if meta.path.is_ident("rename_all") {
+                if rename_all.is_some() {
+                    return Err(meta.error("duplicate #[confer(rename_all = ...)] attribute"));
+                }
                 let value: LitStr = meta.value()?.parse()?;
-                section = Some(value.value());
+                rename_all = Some(RenameRule::from_name(&value.value()).ok_or_else(|| {
+                    syn::Error::new(
+                        value.span(),
+                        format!("unknown rename_all rule, expected one of {}", RenameRule::NAMES),
+                    )
+                })?);
+                Ok(())
+            } else if meta.path.is_ident("shared_section") {
+                if shared_section {
+                    return Err(meta.error("duplicate #[confer(shared_section)] attribute"));
+                }
+                shared_section = true;
+                Ok(())
+            } else if meta.path.is_ident("post_load") {
+                if post_load.is_some() {
+                    return Err(meta.error("duplicate #[confer(post_load = ...)] attribute"));
+                }
+                post_load = Some(parse_expr_value(&meta)?);
+                Ok(())
+            } else if meta.path.is_ident("pre_save") {
+                if pre_save.is_some() {
+                    return Err(meta.error("duplicate #[confer(pre_save = ...)] attribute"));
+                }
+                pre_save = Some(parse_expr_value(&meta)?);
+                Ok(())
+            } else if meta.path.is_ident("version") {
+                if version.is_some() {
+                    return Err(meta.error("duplicate #[confer(version = ...)] attribute"));
+                }
+                let value: LitInt = meta.value()?.parse()?;
+                version = Some(value.base10_parse()?);
+                Ok(())
+            } else if meta.path.is_ident("migrate") {
+                if migrate.is_some() {
+                    return Err(meta.error("duplicate #[confer(migrate = ...)] attribute"));
+                }
+                migrate = Some(parse_expr_value(&meta)?);
                 Ok(())
             } else {
                 Err(meta.error("unsupported attribute on struct for #[derive(ConferModule)]"))
@@ -78,15 +219,43 @@ fn parse_section_name(attrs: &[Attribute], ident: &syn::Ident) -> Result<String>
         })?;
     }
 
-    if let Some(section) = section {
-        Ok(section)
-    } else {
-        Ok(default_section_name(ident))
-    }
+    let version = match (version, migrate) {
+        (Some(number), migrate) => Some(SchemaVersion {
+            number,
+            migrate: migrate.map(|expr| quote! { #expr }),
+        }),
+        (None, Some(expr)) => {
+            return Err(syn::Error::new(
+                expr.span(),
+                "#[confer(migrate = ...)] requires #[confer(version = ...)]",
+            ));
+        }
+        (None, None) => None,
+    };
+
+    Ok(ModuleOptions {
+        section: match section {
+            Some(expr) => quote! { #expr },
+            None => {
+                let name = LitStr::new(&default_section_name(ident), ident.span());
+                quote! { #name }
+            }
+        },
+        bare_keys,
+        rename_all,
+        shared_section,
+        post_load,
+        pre_save,
+        version,
+    })
 }
 
 /// Parses an individual struct field, tracking duplicate keys and metadata.
-fn parse_field(field: &SynField, seen_keys: &mut HashMap<String, Span>) -> Result<Field> {
+fn parse_field(
+    field: &SynField,
+    rename_all: Option<RenameRule>,
+    seen_keys: &mut HashMap<String, Span>,
+) -> Result<Field> {
     let ident = field
         .ident
         .clone()
@@ -94,8 +263,17 @@ fn parse_field(field: &SynField, seen_keys: &mut HashMap<String, Span>) -> Resul
 
     let mut rename: Option<String> = None;
     let mut default_expr: Option<Expr> = None;
+    let mut default_from_trait = false;
     let mut init_expr: Option<Expr> = None;
     let mut ignore = false;
+    let mut secret = false;
+    let mut skip_save = false;
+    let mut skip_load = false;
+    let mut with: Option<syn::Path> = None;
+    let mut bytes: Option<BytesEncoding> = None;
+    let mut deprecated: Option<String> = None;
+    let mut required = false;
+    let mut flatten = false;
 
     for attr in &field.attrs {
         if !is_confer_attr(attr) {
@@ -111,17 +289,21 @@ fn parse_field(field: &SynField, seen_keys: &mut HashMap<String, Span>) -> Resul
                 rename = Some(value.value());
                 Ok(())
             } else if meta.path.is_ident("default") {
-                if default_expr.is_some() {
+                if default_expr.is_some() || default_from_trait {
                     return Err(meta.error("duplicate #[confer(default = ...)] attribute"));
                 }
-                let expr: Expr = meta.value()?.parse()?;
-                default_expr = Some(expr);
+                if meta.input.peek(syn::Token![=]) {
+                    let expr: Expr = meta.value()?.parse()?;
+                    default_expr = Some(expr);
+                } else {
+                    default_from_trait = true;
+                }
                 Ok(())
             } else if meta.path.is_ident("init") {
                 if init_expr.is_some() {
                     return Err(meta.error("duplicate #[confer(init = ...)] attribute"));
                 }
-                let expr: Expr = parse_init_expr(&meta)?;
+                let expr: Expr = parse_expr_value(&meta)?;
                 init_expr = Some(expr);
                 Ok(())
             } else if meta.path.is_ident("ignore") {
@@ -130,20 +312,125 @@ fn parse_field(field: &SynField, seen_keys: &mut HashMap<String, Span>) -> Resul
                 }
                 ignore = true;
                 Ok(())
+            } else if meta.path.is_ident("secret") {
+                if secret {
+                    return Err(meta.error("duplicate #[confer(secret)] attribute"));
+                }
+                secret = true;
+                Ok(())
+            } else if meta.path.is_ident("skip_save") {
+                if skip_save {
+                    return Err(meta.error("duplicate #[confer(skip_save)] attribute"));
+                }
+                skip_save = true;
+                Ok(())
+            } else if meta.path.is_ident("skip_load") {
+                if skip_load {
+                    return Err(meta.error("duplicate #[confer(skip_load)] attribute"));
+                }
+                skip_load = true;
+                Ok(())
+            } else if meta.path.is_ident("with") {
+                if with.is_some() {
+                    return Err(meta.error("duplicate #[confer(with = ...)] attribute"));
+                }
+                let value: LitStr = meta.value()?.parse()?;
+                with = Some(value.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("bytes") {
+                if bytes.is_some() {
+                    return Err(meta.error("duplicate #[confer(bytes = ...)] attribute"));
+                }
+                let value: LitStr = meta.value()?.parse()?;
+                bytes = Some(match value.value().as_str() {
+                    "base64" => BytesEncoding::Base64,
+                    "hex" => BytesEncoding::Hex,
+                    _ => {
+                        return Err(syn::Error::new(
+                            value.span(),
+                            "unsupported bytes encoding; expected \"base64\" or \"hex\"",
+                        ));
+                    }
+                });
+                Ok(())
+            } else if meta.path.is_ident("deprecated") {
+                if deprecated.is_some() {
+                    return Err(meta.error("duplicate #[confer(deprecated = ...)] attribute"));
+                }
+                let value: LitStr = meta.value()?.parse()?;
+                deprecated = Some(value.value());
+                Ok(())
+            } else if meta.path.is_ident("required") {
+                if required {
+                    return Err(meta.error("duplicate #[confer(required)] attribute"));
+                }
+                required = true;
+                Ok(())
+            } else if meta.path.is_ident("flatten") {
+                if flatten {
+                    return Err(meta.error("duplicate #[confer(flatten)] attribute"));
+                }
+                flatten = true;
+                Ok(())
             } else {
                 Err(meta.error("unsupported attribute for #[derive(ConferModule)]"))
             }
         })?;
     }
 
-    if default_expr.is_some() && init_expr.is_some() {
+    let has_default = default_expr.is_some() || default_from_trait;
+    if has_default && init_expr.is_some() {
         return Err(syn::Error::new(
             field.span(),
             "#[confer(default = ...)] and #[confer(init = ...)] cannot be combined",
         ));
     }
 
-    let key = rename.unwrap_or_else(|| ident.to_string());
+    if flatten {
+        if rename.is_some()
+            || has_default
+            || init_expr.is_some()
+            || ignore
+            || secret
+            || skip_save
+            || skip_load
+            || with.is_some()
+            || bytes.is_some()
+            || deprecated.is_some()
+            || required
+        {
+            return Err(syn::Error::new(
+                field.span(),
+                "#[confer(flatten)] cannot be combined with other field attributes",
+            ));
+        }
+        return Ok(Field {
+            key: ident.to_string(),
+            ident,
+            kind: None,
+            default: None,
+            init: None,
+            ignore: false,
+            secret: false,
+            skip_save: false,
+            skip_load: false,
+            required: false,
+            with: None,
+            deprecated: None,
+            flatten: Some(field.ty.clone()),
+            type_name: type_name(&field.ty),
+            default_repr: None,
+            span: field.span(),
+        });
+    }
+
+    let key = rename.unwrap_or_else(|| {
+        let name = ident.to_string();
+        match rename_all {
+            Some(rule) => rule.apply(&name),
+            None => name,
+        }
+    });
 
     if let Some(prev_span) = seen_keys.insert(key.clone(), field.span()) {
         return Err(syn::Error::new(
@@ -156,20 +443,94 @@ fn parse_field(field: &SynField, seen_keys: &mut HashMap<String, Span>) -> Resul
         .with_span(prev_span));
     }
 
-    let kind = if ignore {
+    if ignore && (skip_save || skip_load) {
+        let skip = if skip_save { "skip_save" } else { "skip_load" };
+        return Err(syn::Error::new(
+            field.span(),
+            format!("#[confer(ignore)] and #[confer({skip})] cannot be combined"),
+        ));
+    }
+    if skip_save && skip_load {
+        return Err(syn::Error::new(
+            field.span(),
+            "#[confer(skip_save)] and #[confer(skip_load)] cannot be combined; use #[confer(ignore)]",
+        ));
+    }
+    if skip_load && required {
+        return Err(syn::Error::new(
+            field.span(),
+            "#[confer(required)] and #[confer(skip_load)] cannot be combined",
+        ));
+    }
+
+    if ignore && with.is_some() {
+        return Err(syn::Error::new(
+            field.span(),
+            "#[confer(ignore)] and #[confer(with = ...)] cannot be combined",
+        ));
+    }
+
+    if ignore && deprecated.is_some() {
+        return Err(syn::Error::new(
+            field.span(),
+            "#[confer(ignore)] and #[confer(deprecated = ...)] cannot be combined",
+        ));
+    }
+
+    if bytes.is_some() && (ignore || with.is_some()) {
+        let other = if ignore { "ignore" } else { "with = ..." };
+        return Err(syn::Error::new(
+            field.span(),
+            format!("#[confer(bytes = ...)] and #[confer({other})] cannot be combined"),
+        ));
+    }
+
+    let kind = if ignore || with.is_some() {
         None
     } else {
         Some(classify_type(&field.ty)?)
     };
 
+    if required {
+        if has_default {
+            return Err(syn::Error::new(
+                field.span(),
+                "#[confer(required)] and #[confer(default = ...)] cannot be combined",
+            ));
+        }
+        if !matches!(
+            kind.as_ref().map(|kind| kind.container),
+            Some(ContainerKind::Option | ContainerKind::OptionVec)
+        ) {
+            return Err(syn::Error::new(
+                field.ty.span(),
+                "#[confer(required)] only applies to Option<T> fields",
+            ));
+        }
+    }
+
+    let default_repr = match &default_expr {
+        Some(expr) => Some(quote!(#expr).to_string()),
+        None if default_from_trait => Some("Default::default()".to_string()),
+        None => None,
+    };
+
     let default_tokens = if let (Some(expr), Some(kind)) = (&default_expr, &kind) {
         Some(transform_default(expr.clone(), kind)?)
     } else if let Some(expr) = &default_expr {
         Some(quote! { #expr })
+    } else if default_from_trait {
+        Some(quote! { ::core::default::Default::default() })
     } else {
         None
     };
 
+    // Defaults are checked against the plain `Vec<u8>` shape before switching to the encoded form.
+    let kind = match (kind, bytes) {
+        (Some(kind), Some(encoding)) => Some(bytes_field_type(kind, encoding, &field.ty)?),
+        (kind, _) => kind,
+    };
+
     let init_tokens = init_expr.map(|expr| quote! { #expr });
 
     Ok(Field {
@@ -179,10 +540,46 @@ fn parse_field(field: &SynField, seen_keys: &mut HashMap<String, Span>) -> Resul
         default: default_tokens,
         init: init_tokens,
         ignore,
+        secret,
+        skip_save,
+        skip_load,
+        required,
+        with,
+        deprecated,
+        flatten: None,
+        type_name: type_name(&field.ty),
+        default_repr,
         span: field.span(),
     })
 }
 
+/// Turns a `Vec<u8>` or `Option<Vec<u8>>` field into one stored as an encoded string.
+fn bytes_field_type(kind: FieldType, encoding: BytesEncoding, ty: &Type) -> Result<FieldType> {
+    let container = match (kind.container, &kind.scalar) {
+        (ContainerKind::Vec, ScalarKind::Integer(IntegerKind::U8)) => ContainerKind::Plain,
+        (ContainerKind::OptionVec, ScalarKind::Integer(IntegerKind::U8)) => ContainerKind::Option,
+        _ => {
+            return Err(syn::Error::new(
+                ty.span(),
+                "#[confer(bytes = ...)] only applies to Vec<u8> and Option<Vec<u8>> fields",
+            ));
+        }
+    };
+    Ok(FieldType {
+        container,
+        scalar: ScalarKind::Bytes(encoding),
+    })
+}
+
+/// Renders a field type as compact source text, e.g. `Option<Vec<String>>`.
+fn type_name(ty: &Type) -> String {
+    quote!(#ty)
+        .to_string()
+        .replace(' ', "")
+        .replace(',', ", ")
+        .replace(';', "; ")
+}
+
 /// Derives the default section name from the type identifier.
 fn default_section_name(ident: &syn::Ident) -> String {
     let name = ident.to_string();
@@ -198,18 +595,26 @@ fn default_section_name(ident: &syn::Ident) -> String {
     }
 }
 
+/// Returns `true` when `key` can be written as a bare TOML key without quoting.
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+}
+
 /// Returns `true` when the attribute is `#[confer(...)]`.
 fn is_confer_attr(attr: &Attribute) -> bool {
     attr.path().is_ident("confer")
 }
 
-/// Parses the `init` attribute, allowing either raw expressions or string literals.
-fn parse_init_expr(meta: &ParseNestedMeta) -> Result<Expr> {
+/// Parses an expression-valued attribute such as `init`, allowing raw expressions or string literals.
+fn parse_expr_value(meta: &ParseNestedMeta) -> Result<Expr> {
     let expr: Expr = meta.value()?.parse()?;
-    if let Expr::Lit(expr_lit) = &expr {
-        if let Lit::Str(lit) = &expr_lit.lit {
-            return syn::parse_str::<Expr>(&lit.value());
-        }
+    if let Expr::Lit(expr_lit) = &expr
+        && let Lit::Str(lit) = &expr_lit.lit
+    {
+        return syn::parse_str::<Expr>(&lit.value());
     }
     Ok(expr)
 }
@@ -218,6 +623,22 @@ fn parse_init_expr(meta: &ParseNestedMeta) -> Result<Expr> {
 fn classify_type(ty: &Type) -> Result<FieldType> {
     let (container, inner) = classify_container(ty)?;
     let scalar = classify_scalar(inner)?;
+    match (container, &scalar) {
+        (ContainerKind::Map, ScalarKind::Value) => {}
+        (ContainerKind::Map, _) => {
+            return Err(syn::Error::new(
+                inner.span(),
+                "map fields must use HashMap<String, toml::Value>",
+            ));
+        }
+        (_, ScalarKind::Value) => {
+            return Err(syn::Error::new(
+                inner.span(),
+                "toml::Value is only supported as the value type of HashMap<String, toml::Value>",
+            ));
+        }
+        _ => {}
+    }
     Ok(FieldType { container, scalar })
 }
 
@@ -239,28 +660,86 @@ fn classify_container(ty: &Type) -> Result<(ContainerKind, &Type)> {
         return Ok((ContainerKind::Vec, inner));
     }
 
+    if let Some((key, value)) = match_map_type(ty) {
+        if type_ident(key)? != "String" {
+            return Err(syn::Error::new(key.span(), "map keys must be String"));
+        }
+        return Ok((ContainerKind::Map, value));
+    }
+
+    if let Type::Tuple(tuple) = ty {
+        return classify_tuple(tuple);
+    }
+
+    if let Type::Array(array) = ty {
+        return classify_array(array);
+    }
+
     Ok((ContainerKind::Plain, ty))
 }
 
+/// Accepts `[T; N]` arrays whose length is an integer literal.
+fn classify_array(array: &syn::TypeArray) -> Result<(ContainerKind, &Type)> {
+    let len = match &array.len {
+        Expr::Lit(syn::ExprLit { lit: Lit::Int(len), .. }) => len.base10_parse::<usize>()?,
+        other => {
+            return Err(syn::Error::new(
+                other.span(),
+                "array length must be an integer literal",
+            ));
+        }
+    };
+    Ok((ContainerKind::Array(len), &array.elem))
+}
+
+/// Accepts non-empty tuples whose elements all share one type.
+fn classify_tuple(tuple: &syn::TypeTuple) -> Result<(ContainerKind, &Type)> {
+    let mut elems = tuple.elems.iter();
+    let first = elems
+        .next()
+        .ok_or_else(|| syn::Error::new(tuple.span(), "unit type is not a supported field type"))?;
+    let expected = quote!(#first).to_string();
+    if let Some(other) = elems.find(|elem| quote!(#elem).to_string() != expected) {
+        return Err(syn::Error::new(
+            other.span(),
+            "tuple fields must use the same type for every element",
+        ));
+    }
+    Ok((ContainerKind::Tuple(tuple.elems.len()), first))
+}
+
 /// Resolves the scalar kind supported by the derive implementation.
 fn classify_scalar(ty: &Type) -> Result<ScalarKind> {
+    if let Some(inner) = match_outer_type(ty, "NonZero") {
+        let ident = type_ident(inner)?;
+        return integer_kind(&ident).map(ScalarKind::NonZero).ok_or_else(|| {
+            syn::Error::new(inner.span(), "NonZero can only wrap integer types")
+        });
+    }
+
     let ident = type_ident(ty)?;
+    if let Some(kind) = integer_kind(&ident) {
+        return Ok(ScalarKind::Integer(kind));
+    }
+    if let Some(kind) = ident
+        .strip_prefix("NonZero")
+        .and_then(|width| integer_kind(&width.to_ascii_lowercase()))
+    {
+        return Ok(ScalarKind::NonZero(kind));
+    }
+
     match ident.as_str() {
         "String" => Ok(ScalarKind::String),
         "bool" => Ok(ScalarKind::Bool),
-        "i8" => Ok(ScalarKind::Integer(IntegerKind::I8)),
-        "i16" => Ok(ScalarKind::Integer(IntegerKind::I16)),
-        "i32" => Ok(ScalarKind::Integer(IntegerKind::I32)),
-        "i64" => Ok(ScalarKind::Integer(IntegerKind::I64)),
-        "isize" => Ok(ScalarKind::Integer(IntegerKind::Isize)),
-        "u8" => Ok(ScalarKind::Integer(IntegerKind::U8)),
-        "u16" => Ok(ScalarKind::Integer(IntegerKind::U16)),
-        "u32" => Ok(ScalarKind::Integer(IntegerKind::U32)),
-        "u64" => Ok(ScalarKind::Integer(IntegerKind::U64)),
-        "usize" => Ok(ScalarKind::Integer(IntegerKind::Usize)),
         "f32" => Ok(ScalarKind::Float(FloatKind::F32)),
         "f64" => Ok(ScalarKind::Float(FloatKind::F64)),
         "Datetime" => Ok(ScalarKind::Datetime),
+        "Date" => Ok(ScalarKind::Date),
+        "Time" => Ok(ScalarKind::Time),
+        "ByteSize" => Ok(ScalarKind::ByteSize),
+        "Value" => Ok(ScalarKind::Value),
+        "IpAddr" => Ok(ScalarKind::Net(NetKind::IpAddr)),
+        "SocketAddr" => Ok(ScalarKind::Net(NetKind::SocketAddr)),
         other => Err(syn::Error::new(
             ty.span(),
             format!("unsupported field type `{}`", other),
@@ -268,6 +747,23 @@ fn classify_scalar(ty: &Type) -> Result<ScalarKind> {
     }
 }
 
+/// Maps a primitive integer type name to its width.
+fn integer_kind(name: &str) -> Option<IntegerKind> {
+    match name {
+        "i8" => Some(IntegerKind::I8),
+        "i16" => Some(IntegerKind::I16),
+        "i32" => Some(IntegerKind::I32),
+        "i64" => Some(IntegerKind::I64),
+        "isize" => Some(IntegerKind::Isize),
+        "u8" => Some(IntegerKind::U8),
+        "u16" => Some(IntegerKind::U16),
+        "u32" => Some(IntegerKind::U32),
+        "u64" => Some(IntegerKind::U64),
+        "usize" => Some(IntegerKind::Usize),
+        _ => None,
+    }
+}
+
 /// Extracts the terminal identifier from a type path.
 fn type_ident(ty: &Type) -> Result<String> {
     match ty {
@@ -296,19 +792,37 @@ fn match_outer_type<'a>(ty: &'a Type, expected: &str) -> Option<&'a Type> {
     };
 
     let last = path.path.segments.last()?;
-    if last.ident == expected {
-        if let syn::PathArguments::AngleBracketed(generic) = &last.arguments {
-            if generic.args.len() == 1 {
-                if let syn::GenericArgument::Type(inner) = generic.args.first().unwrap() {
-                    return Some(inner);
-                }
-            }
-        }
+    if last.ident == expected
+        && let syn::PathArguments::AngleBracketed(generic) = &last.arguments
+        && generic.args.len() == 1
+        && let syn::GenericArgument::Type(inner) = generic.args.first().unwrap()
+    {
+        return Some(inner);
     }
 
     None
 }
 
+/// Returns the key and value types when `ty` is a `HashMap<K, V>`.
+fn match_map_type(ty: &Type) -> Option<(&Type, &Type)> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let last = path.path.segments.last()?;
+    if last.ident != "HashMap" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(generic) = &last.arguments else {
+        return None;
+    };
+    match (generic.args.first(), generic.args.get(1), generic.args.len()) {
+        (Some(syn::GenericArgument::Type(key)), Some(syn::GenericArgument::Type(value)), 2) => {
+            Some((key, value))
+        }
+        _ => None,
+    }
+}
+
 /// Converts a literal default expression into tokens matching the field type.
 fn transform_default(expr: Expr, field_type: &FieldType) -> Result<TokenStream> {
     match field_type.container {
@@ -316,6 +830,12 @@ fn transform_default(expr: Expr, field_type: &FieldType) -> Result<TokenStream>
         ContainerKind::Vec => transform_vec_default(expr, &field_type.scalar, false),
         ContainerKind::Option => transform_option_default(expr, &field_type.scalar),
         ContainerKind::OptionVec => transform_vec_default(expr, &field_type.scalar, true),
+        ContainerKind::Tuple(arity) => transform_tuple_default(expr, &field_type.scalar, arity),
+        ContainerKind::Array(len) => transform_array_default(expr, &field_type.scalar, len),
+        ContainerKind::Map => Err(syn::Error::new(
+            expr.span(),
+            "map fields only support a bare #[confer(default)]",
+        )),
     }
 }
 
@@ -356,10 +876,90 @@ fn transform_vec_default(
     }
 }
 
+/// Validates fixed-size array defaults, requiring exactly `len` elements.
+fn transform_array_default(expr: Expr, scalar: &ScalarKind, len: usize) -> Result<TokenStream> {
+    match expr {
+        Expr::Array(array) => {
+            if array.elems.len() != len {
+                return Err(syn::Error::new(
+                    array.span(),
+                    format!("expected an array of {} elements, found {}", len, array.elems.len()),
+                ));
+            }
+            let elements: Vec<_> = array
+                .elems
+                .into_iter()
+                .map(|element| literal_tokens(element, scalar))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(quote! { [#(#elements),*] })
+        }
+        Expr::Repeat(repeat) => {
+            let found = match &*repeat.len {
+                Expr::Lit(syn::ExprLit { lit: Lit::Int(lit), .. }) => lit.base10_parse::<usize>()?,
+                other => {
+                    return Err(syn::Error::new(
+                        other.span(),
+                        "array default length must be an integer literal",
+                    ));
+                }
+            };
+            if found != len {
+                return Err(syn::Error::new(
+                    repeat.span(),
+                    format!("expected an array of {} elements, found {}", len, found),
+                ));
+            }
+            let element = literal_tokens(*repeat.expr, scalar)?;
+            let elements = std::iter::repeat_n(element, len);
+            Ok(quote! { [#(#elements),*] })
+        }
+        _ => Err(syn::Error::new(
+            expr.span(),
+            "defaults for [T; N] must use [ ... ] or [value; N] syntax",
+        )),
+    }
+}
+
+/// Validates tuple defaults element by element, requiring the declared arity.
+fn transform_tuple_default(expr: Expr, scalar: &ScalarKind, arity: usize) -> Result<TokenStream> {
+    match expr {
+        Expr::Tuple(tuple) => {
+            if tuple.elems.len() != arity {
+                return Err(syn::Error::new(
+                    tuple.span(),
+                    format!("expected a tuple of {} elements, found {}", arity, tuple.elems.len()),
+                ));
+            }
+            let elements: Vec<_> = tuple
+                .elems
+                .into_iter()
+                .map(|element| literal_tokens(element, scalar))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(quote! { (#(#elements,)*) })
+        }
+        _ => Err(syn::Error::new(
+            expr.span(),
+            "defaults for tuples must use ( ... ) syntax",
+        )),
+    }
+}
+
 /// Ensures the provided literal matches the scalar kind expected by the field.
 fn validate_literal(expr: &Expr, scalar: &ScalarKind) -> Result<()> {
     match scalar {
-        ScalarKind::String | ScalarKind::Datetime => match expr {
+        ScalarKind::Datetime => match expr {
+            Expr::Lit(syn::ExprLit { lit: Lit::Str(lit), .. }) => lit
+                .value()
+                .parse::<toml::value::Datetime>()
+                .map(|_| ())
+                .map_err(|err| {
+                    syn::Error::new(lit.span(), format!("invalid datetime `{}`: {err}", lit.value()))
+                }),
+            _ => Err(syn::Error::new(expr.span(), "expected string literal")),
+        },
+        ScalarKind::Date => date_literal(expr).map(|_| ()),
+        ScalarKind::Time => time_literal(expr).map(|_| ()),
+        ScalarKind::String | ScalarKind::Net(_) => match expr {
             Expr::Lit(expr_lit) => match &expr_lit.lit {
                 Lit::Str(_) => Ok(()),
                 _ => Err(syn::Error::new(
@@ -383,6 +983,16 @@ fn validate_literal(expr: &Expr, scalar: &ScalarKind) -> Result<()> {
             },
             _ => Err(syn::Error::new(expr.span(), "expected integer literal")),
         },
+        ScalarKind::NonZero(_) => match expr {
+            Expr::Lit(syn::ExprLit { lit: Lit::Int(lit), .. }) => {
+                if lit.base10_parse::<u128>().is_ok_and(|value| value == 0) {
+                    Err(syn::Error::new(lit.span(), "default for a NonZero field must not be zero"))
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Err(syn::Error::new(expr.span(), "expected integer literal")),
+        },
         ScalarKind::Float(_) => match expr {
             Expr::Lit(expr_lit) => match expr_lit.lit {
                 Lit::Float(_) | Lit::Int(_) => Ok(()),
@@ -390,6 +1000,9 @@ fn validate_literal(expr: &Expr, scalar: &ScalarKind) -> Result<()> {
             },
             _ => Err(syn::Error::new(expr.span(), "expected float literal")),
         },
+        ScalarKind::ByteSize => byte_size_tokens(expr).map(|_| ()),
+        ScalarKind::Value => Err(syn::Error::new(expr.span(), "toml::Value fields take no literal defaults")),
+        ScalarKind::Bytes(_) => unreachable!("bytes defaults are checked as Vec<u8>"),
     }
 }
 
@@ -400,16 +1013,171 @@ fn literal_tokens(expr: Expr, scalar: &ScalarKind) -> Result<TokenStream> {
         ScalarKind::String => quote! { (#expr).to_string() },
         ScalarKind::Bool => quote! { #expr },
         ScalarKind::Integer(_) => quote! { #expr },
+        ScalarKind::NonZero(kind) => {
+            let ty = kind.type_tokens();
+            quote! {
+                const {
+                    match ::core::num::NonZero::<#ty>::new(#expr) {
+                        ::core::option::Option::Some(value) => value,
+                        ::core::option::Option::None => ::core::panic!("default for a NonZero field must not be zero"),
+                    }
+                }
+            }
+        }
         ScalarKind::Float(kind) => {
             let ty = kind.type_tokens();
             quote! { (#expr) as #ty }
         }
         ScalarKind::Datetime => {
-            quote! { <toml::value::Datetime as std::str::FromStr>::from_str(#expr).expect("invalid datetime literal") }
+            let (toml::value::Datetime { date, time, offset }, _) = datetime_literal(&expr)?;
+            let date = option_tokens(date.map(date_tokens));
+            let time = option_tokens(time.map(time_tokens));
+            let offset = option_tokens(offset.map(|offset| match offset {
+                toml::value::Offset::Z => quote! { toml::value::Offset::Z },
+                toml::value::Offset::Custom { minutes } => quote! { toml::value::Offset::Custom { minutes: #minutes } },
+            }));
+            quote! { toml::value::Datetime { date: #date, time: #time, offset: #offset } }
         }
+        ScalarKind::Date => date_tokens(date_literal(&expr)?),
+        ScalarKind::Time => time_tokens(time_literal(&expr)?),
+        ScalarKind::Net(kind) => net_literal_tokens(&expr, *kind)?,
+        ScalarKind::ByteSize => byte_size_tokens(&expr)?,
+        ScalarKind::Value | ScalarKind::Bytes(_) => unreachable!("rejected by validate_literal"),
     })
 }
 
+/// Emits `date` as a struct literal, so datetime defaults never parse at runtime.
+fn date_tokens(date: toml::value::Date) -> TokenStream {
+    let toml::value::Date { year, month, day } = date;
+    quote! { toml::value::Date { year: #year, month: #month, day: #day } }
+}
+
+/// Emits `time` as a struct literal.
+fn time_tokens(time: toml::value::Time) -> TokenStream {
+    let toml::value::Time { hour, minute, second, nanosecond } = time;
+    quote! { toml::value::Time { hour: #hour, minute: #minute, second: #second, nanosecond: #nanosecond } }
+}
+
+/// Wraps optional tokens in `Some(..)` or emits `None`.
+fn option_tokens(tokens: Option<TokenStream>) -> TokenStream {
+    match tokens {
+        Some(tokens) => quote! { ::core::option::Option::Some(#tokens) },
+        None => quote! { ::core::option::Option::None },
+    }
+}
+
+/// Parses a date-only default such as `"2025-01-01"`.
+fn date_literal(expr: &Expr) -> Result<toml::value::Date> {
+    match datetime_literal(expr)? {
+        (toml::value::Datetime { date: Some(date), time: None, offset: None }, _) => Ok(date),
+        (_, lit) => Err(syn::Error::new(lit.span(), "expected a date without a time, like \"2025-01-01\"")),
+    }
+}
+
+/// Parses a time-only default such as `"08:30:00"`.
+fn time_literal(expr: &Expr) -> Result<toml::value::Time> {
+    match datetime_literal(expr)? {
+        (toml::value::Datetime { date: None, time: Some(time), offset: None }, _) => Ok(time),
+        (_, lit) => Err(syn::Error::new(lit.span(), "expected a time without a date, like \"08:30:00\"")),
+    }
+}
+
+/// Parses a string literal as a TOML datetime, returning the literal for error spans.
+fn datetime_literal(expr: &Expr) -> Result<(toml::value::Datetime, &LitStr)> {
+    let Expr::Lit(syn::ExprLit { lit: Lit::Str(lit), .. }) = expr else {
+        return Err(syn::Error::new(expr.span(), "expected string literal"));
+    };
+    let datetime = lit.value().parse::<toml::value::Datetime>().map_err(|err| {
+        syn::Error::new(lit.span(), format!("invalid datetime `{}`: {err}", lit.value()))
+    })?;
+    Ok((datetime, lit))
+}
+
+/// Emits a byte-size default. Strings such as `"10MB"` are parsed by `conferencier::ByteSize`'s
+/// own parser in a `const` item, so an invalid unit fails compilation at the literal.
+fn byte_size_tokens(expr: &Expr) -> Result<TokenStream> {
+    let lit = match expr {
+        Expr::Lit(syn::ExprLit { lit: Lit::Int(lit), .. }) => {
+            let bytes = proc_macro2::Literal::u64_suffixed(lit.base10_parse()?);
+            return Ok(quote! { ::core::convert::From::from(#bytes) });
+        }
+        Expr::Lit(syn::ExprLit { lit: Lit::Str(lit), .. }) => lit,
+        _ => return Err(syn::Error::new(expr.span(), "expected integer or string literal")),
+    };
+    let crate_path = crate::crate_path::conferencier_path()?;
+    let crate_path: TokenStream = quote! { #crate_path }
+        .into_iter()
+        .map(|mut token| {
+            token.set_span(lit.span());
+            token
+        })
+        .collect();
+    let message = LitStr::new(&format!("invalid byte size `{}`", lit.value()), lit.span());
+    Ok(quote_spanned! {lit.span()=>
+        {
+            const BYTES: u64 = match #crate_path::__private::byte_size(#lit) {
+                ::core::option::Option::Some(bytes) => bytes,
+                ::core::option::Option::None => ::core::panic!(#message),
+            };
+            ::core::convert::From::from(BYTES)
+        }
+    })
+}
+
+/// Parses a network address default during expansion and emits an equivalent constructor.
+fn net_literal_tokens(expr: &Expr, kind: NetKind) -> Result<TokenStream> {
+    let raw = match expr {
+        Expr::Lit(syn::ExprLit { lit: Lit::Str(lit), .. }) => lit.value(),
+        _ => return Err(syn::Error::new(expr.span(), "expected string literal")),
+    };
+    let invalid = || syn::Error::new(expr.span(), format!("invalid {} `{}`", kind.description(), raw));
+    Ok(match kind {
+        NetKind::IpAddr => ip_tokens(raw.parse().map_err(|_| invalid())?),
+        NetKind::SocketAddr => match raw.parse::<std::net::SocketAddr>().map_err(|_| invalid())? {
+            std::net::SocketAddr::V4(addr) => {
+                let ip = ipv4_tokens(*addr.ip());
+                let port = addr.port();
+                quote! { ::std::net::SocketAddr::V4(::std::net::SocketAddrV4::new(#ip, #port)) }
+            }
+            std::net::SocketAddr::V6(addr) => {
+                let ip = ipv6_tokens(*addr.ip());
+                let port = addr.port();
+                let flowinfo = addr.flowinfo();
+                let scope_id = addr.scope_id();
+                quote! {
+                    ::std::net::SocketAddr::V6(::std::net::SocketAddrV6::new(#ip, #port, #flowinfo, #scope_id))
+                }
+            }
+        },
+    })
+}
+
+/// Emits tokens constructing the given [`std::net::IpAddr`].
+fn ip_tokens(ip: std::net::IpAddr) -> TokenStream {
+    match ip {
+        std::net::IpAddr::V4(v4) => {
+            let inner = ipv4_tokens(v4);
+            quote! { ::std::net::IpAddr::V4(#inner) }
+        }
+        std::net::IpAddr::V6(v6) => {
+            let inner = ipv6_tokens(v6);
+            quote! { ::std::net::IpAddr::V6(#inner) }
+        }
+    }
+}
+
+/// Emits tokens constructing the given [`std::net::Ipv4Addr`].
+fn ipv4_tokens(ip: std::net::Ipv4Addr) -> TokenStream {
+    let octets = ip.octets();
+    quote! { ::std::net::Ipv4Addr::new(#(#octets),*) }
+}
+
+/// Emits tokens constructing the given [`std::net::Ipv6Addr`].
+fn ipv6_tokens(ip: std::net::Ipv6Addr) -> TokenStream {
+    let segments = ip.segments();
+    quote! { ::std::net::Ipv6Addr::new(#(#segments),*) }
+}
+
 /// Helper trait for enriching errors with additional span information.
 trait ErrorWithSpan {
     /// Combines `self` with an extra error pointing at `span` for better diagnostics.
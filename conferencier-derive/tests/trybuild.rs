@@ -5,4 +5,21 @@ fn trybuild_suite() {
     t.compile_fail("tests/trybuild/fail_duplicate_keys.rs");
     t.compile_fail("tests/trybuild/fail_unsupported_type.rs");
     t.compile_fail("tests/trybuild/fail_conflicting_attrs.rs");
+    t.compile_fail("tests/trybuild/fail_invalid_net_default.rs");
+    t.compile_fail("tests/trybuild/fail_bare_keys.rs");
+    t.compile_fail("tests/trybuild/fail_lifetime_param.rs");
+    t.compile_fail("tests/trybuild/fail_invalid_datetime_default.rs");
+    t.compile_fail("tests/trybuild/fail_rename_all_rule.rs");
+    t.compile_fail("tests/trybuild/fail_flatten_overlap.rs");
+    t.compile_fail("tests/trybuild/fail_flatten_bounds.rs");
+    t.compile_fail("tests/trybuild/fail_generic_flatten.rs");
+    t.compile_fail("tests/trybuild/fail_tuple_shape.rs");
+    t.compile_fail("tests/trybuild/fail_array_shape.rs");
+    t.compile_fail("tests/trybuild/fail_non_zero_default.rs");
+    t.compile_fail("tests/trybuild/fail_required_non_option.rs");
+    t.compile_fail("tests/trybuild/fail_map_shape.rs");
+    t.compile_fail("tests/trybuild/fail_map_value.rs");
+    t.compile_fail("tests/trybuild/fail_byte_size_default.rs");
+    t.compile_fail("tests/trybuild/fail_bytes_shape.rs");
+    t.compile_fail("tests/trybuild/fail_section_expr.rs");
 }
@@ -0,0 +1,23 @@
+const LEN: usize = 3;
+
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Alerts")]
+struct NamedLength {
+    levels: [u8; LEN],
+}
+
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Alerts")]
+struct ShortDefault {
+    #[confer(default = [0.5, 0.75])]
+    thresholds: [f64; 4],
+}
+
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Alerts")]
+struct LongRepeat {
+    #[confer(default = [1; 5])]
+    levels: [u8; 3],
+}
+
+fn main() {}
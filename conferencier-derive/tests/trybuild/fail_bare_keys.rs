@@ -0,0 +1,9 @@
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Strict", bare_keys)]
+struct StrictKeys {
+    plain_key: i32,
+    #[confer(rename = "has space")]
+    spaced: i32,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use conferencier::ByteSize;
+
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Upload")]
+struct UnknownUnit {
+    #[confer(default = "10XB")]
+    max_body: ByteSize,
+}
+
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Upload")]
+struct WrongLiteral {
+    #[confer(default = true)]
+    max_body: ByteSize,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Tls")]
+struct WrongElement {
+    #[confer(bytes = "base64")]
+    key: Vec<u16>,
+}
+
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Tls")]
+struct UnknownEncoding {
+    #[confer(bytes = "base32")]
+    key: Vec<u8>,
+}
+
+fn main() {}
@@ -4,4 +4,34 @@ struct ConflictingAttrs {
     value: i32,
 }
 
+#[derive(conferencier_derive::ConferModule)]
+struct IgnoredSkipSave {
+    #[confer(ignore, skip_save)]
+    value: i32,
+}
+
+#[derive(conferencier_derive::ConferModule)]
+struct SkipBoth {
+    #[confer(skip_save, skip_load)]
+    value: i32,
+}
+
+#[derive(conferencier_derive::ConferModule)]
+struct RequiredSkipLoad {
+    #[confer(required, skip_load)]
+    value: Option<i32>,
+}
+
+#[derive(conferencier_derive::ConferModule)]
+struct IgnoredWith {
+    #[confer(ignore, with = "codec")]
+    value: i32,
+}
+
+#[derive(conferencier_derive::ConferModule)]
+struct IgnoredDeprecated {
+    #[confer(ignore, deprecated = "use other")]
+    value: i32,
+}
+
 fn main() {}
@@ -0,0 +1,11 @@
+struct Inner {
+    port: u16,
+}
+
+#[derive(conferencier_derive::ConferModule)]
+struct Outer {
+    #[confer(flatten)]
+    inner: Inner,
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+#[derive(conferencier_derive::ConferModule, Clone, PartialEq)]
+struct Inner {
+    #[confer(default = 1)]
+    port: u16,
+}
+
+#[derive(conferencier_derive::ConferModule)]
+struct Outer {
+    #[confer(default = 2)]
+    port: u16,
+    #[confer(flatten)]
+    inner: Inner,
+}
+
+#[derive(conferencier_derive::ConferModule, Clone, PartialEq)]
+struct Other {
+    #[confer(default = 3)]
+    port: u16,
+}
+
+#[derive(conferencier_derive::ConferModule)]
+struct Siblings {
+    #[confer(flatten)]
+    inner: Inner,
+    #[confer(flatten)]
+    other: Other,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+#[derive(conferencier_derive::ConferModule, Clone, PartialEq)]
+struct Inner {
+    #[confer(default = 1)]
+    port: u16,
+}
+
+#[derive(conferencier_derive::ConferModule)]
+struct Outer<T: Send + Sync + 'static> {
+    #[confer(flatten)]
+    inner: Wrapper<T>,
+}
+
+#[derive(Clone, PartialEq)]
+struct Wrapper<T>(Inner, std::marker::PhantomData<T>);
+
+fn main() {}
@@ -0,0 +1,21 @@
+use toml::value::{Date, Datetime, Time};
+
+#[derive(conferencier_derive::ConferModule)]
+struct BadSchedule {
+    #[confer(default = "not-a-date")]
+    start: Option<Datetime>,
+}
+
+#[derive(conferencier_derive::ConferModule)]
+struct DateWithTime {
+    #[confer(default = "2025-01-01T08:00:00")]
+    release: Date,
+}
+
+#[derive(conferencier_derive::ConferModule)]
+struct TimeWithDate {
+    #[confer(default = "2025-01-01")]
+    cutoff: Time,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use std::net::SocketAddr;
+
+#[derive(conferencier_derive::ConferModule)]
+struct BadBind {
+    #[confer(default = "localhost:80")]
+    bind: SocketAddr,
+}
+
+fn main() {}
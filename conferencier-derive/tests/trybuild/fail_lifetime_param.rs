@@ -0,0 +1,8 @@
+#[derive(conferencier_derive::ConferModule)]
+struct Borrowed<'a> {
+    #[confer(ignore)]
+    name: &'a str,
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use std::collections::HashMap;
+
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Limits")]
+struct TypedValues {
+    limits: HashMap<String, i64>,
+}
+
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Limits")]
+struct NumericKeys {
+    limits: HashMap<u32, toml::Value>,
+}
+
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Limits")]
+struct LiteralDefault {
+    #[confer(default = "none")]
+    limits: HashMap<String, toml::Value>,
+}
+
+fn main() {}
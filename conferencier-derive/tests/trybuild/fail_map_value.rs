@@ -0,0 +1,8 @@
+use std::collections::HashMap;
+
+#[derive(conferencier_derive::ConferModule)]
+struct BadMap {
+    map: HashMap<String, String>,
+}
+
+fn main() {}
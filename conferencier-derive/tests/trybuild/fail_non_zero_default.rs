@@ -0,0 +1,10 @@
+use std::num::NonZeroU32;
+
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Pool")]
+struct PoolConfig {
+    #[confer(default = 0)]
+    max_connections: NonZeroU32,
+}
+
+fn main() {}
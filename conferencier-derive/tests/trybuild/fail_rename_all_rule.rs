@@ -0,0 +1,7 @@
+#[derive(conferencier_derive::ConferModule)]
+#[confer(rename_all = "kebab")]
+struct BadCasing {
+    read_timeout: u32,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Deploy")]
+struct NotOptional {
+    #[confer(required)]
+    region: String,
+}
+
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Deploy")]
+struct WithDefault {
+    #[confer(required, default = "eu-west")]
+    region: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = 42)]
+struct NumericSection {
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Theme")]
+struct MixedTuple {
+    pair: (u8, String),
+}
+
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Theme")]
+struct ShortDefault {
+    #[confer(default = (1, 2))]
+    accent: (u8, u8, u8),
+}
+
+fn main() {}
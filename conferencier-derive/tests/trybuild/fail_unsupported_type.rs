@@ -1,8 +1,6 @@
-use std::collections::HashMap;
-
 #[derive(conferencier_derive::ConferModule)]
 struct BadType {
-    map: HashMap<String, String>,
+    started: std::time::Instant,
 }
 
 fn main() {}
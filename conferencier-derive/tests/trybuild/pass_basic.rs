@@ -13,9 +13,22 @@ struct AuthConfig {
     ratio: f32,
     #[confer(default = "2024-02-03T00:00:00Z")]
     started: toml::value::Datetime,
+    #[confer(default = "07:30:00")]
+    wake: Option<toml::value::Datetime>,
     optional: Option<bool>,
     #[confer(default = [1, 2, 3])]
     optional_numbers: Option<Vec<i32>>,
 }
 
-fn main() {}
+#[derive(conferencier_derive::ConferModule)]
+#[confer(section = "Tagged")]
+struct Tagged<T: Default + Send + Sync + 'static> {
+    #[confer(ignore)]
+    marker: std::marker::PhantomData<T>,
+    #[confer(default = 8)]
+    level: u8,
+}
+
+fn main() {
+    let _: &'static [&'static str] = <Tagged<u8> as conferencier::confer_module::ConferModule>::owned_keys();
+}
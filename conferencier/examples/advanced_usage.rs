@@ -86,7 +86,8 @@ async fn main() -> Result<()> {
 
     let snapshot_path = example_snapshot_path();
     if let Some(parent) = snapshot_path.parent() {
-        fs::create_dir_all(parent).map_err(conferencier::ConferError::from)?;
+        fs::create_dir_all(parent)
+            .map_err(|err| conferencier::ConferError::io_error(Some(parent.to_path_buf()), err))?;
     }
     store.save_file(&snapshot_path).await?;
     println!(
@@ -0,0 +1,92 @@
+//! Text encodings for binary values stored as TOML strings.
+
+/// Standard base64 alphabet (RFC 4648, with `=` padding).
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes `bytes` as padded standard base64.
+pub(crate) fn encode_base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let buffer = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (index, byte)| acc | u32::from(*byte) << (16 - 8 * index));
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (buffer >> (18 - 6 * index)) & 0x3f;
+                out.push(char::from(BASE64_ALPHABET[sextet as usize]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Decodes padded standard base64, ignoring surrounding whitespace.
+pub(crate) fn decode_base64(text: &str) -> Result<Vec<u8>, String> {
+    let text = text.trim().as_bytes();
+    if !text.len().is_multiple_of(4) {
+        return Err("base64 length must be a multiple of 4".into());
+    }
+    let mut out = Vec::with_capacity(text.len() / 4 * 3);
+    let chunks = text.len() / 4;
+    for (chunk_index, chunk) in text.chunks(4).enumerate() {
+        let padding = chunk.iter().rev().take_while(|byte| **byte == b'=').count();
+        if padding > 2 || (padding > 0 && chunk_index + 1 != chunks) {
+            return Err("misplaced base64 padding".into());
+        }
+        let mut buffer = 0u32;
+        for (index, byte) in chunk[..4 - padding].iter().enumerate() {
+            let sextet = BASE64_ALPHABET
+                .iter()
+                .position(|candidate| candidate == byte)
+                .ok_or_else(|| format!("invalid base64 character {:?}", char::from(*byte)))?;
+            buffer |= (sextet as u32) << (18 - 6 * index);
+        }
+        out.extend_from_slice(&buffer.to_be_bytes()[1..4 - padding]);
+    }
+    Ok(out)
+}
+
+/// Encodes `bytes` as lowercase hexadecimal.
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Decodes hexadecimal in either case, ignoring surrounding whitespace.
+pub(crate) fn decode_hex(text: &str) -> Result<Vec<u8>, String> {
+    let text = text.trim();
+    if !text.len().is_multiple_of(2) {
+        return Err("hex string must have an even number of digits".into());
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|at| {
+            text.get(at..at + 2)
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| format!("invalid hex digits at offset {at}"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_and_hex_round_trip() {
+        for (raw, encoded) in [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"), ("foobar", "Zm9vYmFy")] {
+            assert_eq!(encode_base64(raw.as_bytes()), encoded);
+            assert_eq!(decode_base64(encoded).as_deref(), Ok(raw.as_bytes()));
+        }
+        assert!(decode_base64("Zm9").is_err());
+        assert!(decode_base64("Zg==Zm9v").is_err());
+        assert!(decode_base64("Zm9*").is_err());
+
+        assert_eq!(encode_hex(&[0x00, 0xab, 0xff]), "00abff");
+        assert_eq!(decode_hex("00ABff"), Ok(vec![0x00, 0xab, 0xff]));
+        assert!(decode_hex("abc").is_err());
+        assert!(decode_hex("zz").is_err());
+    }
+}
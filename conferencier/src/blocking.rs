@@ -0,0 +1,278 @@
+//! Synchronous façade over [`Confer`] for code that does not run inside a Tokio runtime.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use tokio::runtime::{Builder, Runtime};
+use toml::value::{Date, Datetime, Time};
+use toml::{Table, Value};
+
+use crate::byte_size::ByteSize;
+use crate::confer_value::{FromConferValue, IntoConferValue};
+use crate::diff::ConfigChange;
+use crate::error::{ConferError, Result};
+use crate::save_options::SaveOptions;
+use crate::store::{Confer, SharedConfer};
+
+/// Blocking wrapper around a [`SharedConfer`].
+///
+/// Every call drives the corresponding async method to completion on a private
+/// current-thread runtime. The wrapper must not be used from within an async context:
+/// like [`Runtime::block_on`], such calls panic. The wrapped store can still be shared
+/// with async code through [`BlockingConfer::inner`].
+#[derive(Debug)]
+pub struct BlockingConfer {
+    inner: SharedConfer,
+    runtime: Runtime,
+}
+
+/// Generates blocking delegates for async [`Confer`] methods with identical signatures.
+macro_rules! blocking_delegates {
+    ($( $(#[$doc:meta])* fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty; )*) => {
+        $(
+            $(#[$doc])*
+            pub fn $name(&self $(, $arg: $ty)*) -> $ret {
+                self.runtime.block_on(self.inner.$name($($arg),*))
+            }
+        )*
+    };
+}
+
+impl BlockingConfer {
+    /// Wraps an existing shared store.
+    pub fn new(inner: SharedConfer) -> Result<Self> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| ConferError::io_error(None, err))?;
+        Ok(Self { inner, runtime })
+    }
+
+    /// Creates a wrapper around an empty store.
+    pub fn empty() -> Result<Self> {
+        Self::new(Confer::new())
+    }
+
+    /// Builds a blocking store from a TOML string.
+    pub fn from_string(source: &str) -> Result<Self> {
+        Self::new(Confer::from_string(source)?)
+    }
+
+    /// Reads a TOML file from disk and wraps the resulting store.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(Confer::from_file(path)?)
+    }
+
+    /// Reads TOML from `reader` until EOF and wraps the resulting store.
+    pub fn from_reader(reader: impl std::io::Read) -> Result<Self> {
+        Self::new(Confer::from_std_reader(reader)?)
+    }
+
+    /// Returns the wrapped store for sharing with async code.
+    pub fn inner(&self) -> &SharedConfer {
+        &self.inner
+    }
+
+    /// Blocking form of [`Confer::get`].
+    pub fn get<T: FromConferValue>(&self, section: &str, key: &str) -> Result<T> {
+        self.runtime.block_on(self.inner.get(section, key))
+    }
+
+    /// Blocking form of [`Confer::set`].
+    pub fn set<T: IntoConferValue>(&self, section: &str, key: &str, value: T) -> Result<()> {
+        self.runtime.block_on(self.inner.set(section, key, value))
+    }
+
+    /// Blocking counterpart of [`Confer::to_writer`] for [`std::io::Write`] sinks.
+    pub fn to_writer(&self, mut writer: impl std::io::Write) -> Result<()> {
+        let serialized = self.save_str()?;
+        let io_error = |err| ConferError::io_error(None, err);
+        writer.write_all(serialized.as_bytes()).map_err(io_error)?;
+        writer.flush().map_err(io_error)
+    }
+
+    /// Blocking form of [`Confer::set_many`].
+    pub fn set_many(
+        &self,
+        section: &str,
+        entries: impl IntoIterator<Item = (String, Value)>,
+    ) -> Result<()> {
+        self.runtime.block_on(self.inner.set_many(section, entries))
+    }
+
+    /// Blocking form of [`Confer::import_sections`].
+    pub fn import_sections(
+        &self,
+        sections: impl IntoIterator<Item = (String, Table)>,
+        overwrite: bool,
+    ) -> Result<()> {
+        self.runtime.block_on(self.inner.import_sections(sections, overwrite))
+    }
+
+    blocking_delegates! {
+        /// Blocking form of [`Confer::load_str`].
+        fn load_str(&self, source: &str) -> Result<()>;
+        /// Blocking form of [`Confer::patch`].
+        fn patch(&self, fragment: &str) -> Result<()>;
+        /// Blocking form of [`Confer::diff`].
+        fn diff(&self, other: &Confer) -> Vec<ConfigChange>;
+        /// Blocking form of [`Confer::load_file`].
+        fn load_file(&self, path: &Path) -> Result<()>;
+        /// Blocking form of [`Confer::diff_against_file`].
+        fn diff_against_file(&self, path: &Path) -> Result<Vec<ConfigChange>>;
+        /// Blocking form of [`Confer::reload_if_changed`].
+        fn reload_if_changed(&self, path: &Path) -> Result<bool>;
+        /// Blocking form of [`Confer::save_str`].
+        fn save_str(&self) -> Result<String>;
+        /// Blocking form of [`Confer::save_str_with`].
+        fn save_str_with(&self, options: &SaveOptions) -> Result<String>;
+        /// Blocking form of [`Confer::save_file_with`].
+        fn save_file_with(&self, path: &Path, options: &SaveOptions) -> Result<()>;
+        /// Blocking form of [`Confer::save_file`].
+        fn save_file(&self, path: &Path) -> Result<()>;
+        /// Blocking form of [`Confer::load_file_locked`].
+        #[cfg(feature = "file-lock")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "file-lock")))]
+        fn load_file_locked(&self, path: &Path) -> Result<()>;
+        /// Blocking form of [`Confer::save_file_locked`].
+        #[cfg(feature = "file-lock")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "file-lock")))]
+        fn save_file_locked(&self, path: &Path) -> Result<()>;
+        /// Blocking form of [`Confer::get_value`].
+        fn get_value(&self, section: &str, key: &str) -> Option<Value>;
+        /// Blocking form of [`Confer::replace_section`].
+        fn replace_section(&self, section: &str, table: Table) -> Result<()>;
+        /// Blocking form of [`Confer::validate`].
+        fn validate(&self) -> Result<()>;
+        /// Blocking form of [`Confer::flatten`].
+        fn flatten(&self) -> Vec<(String, Value)>;
+        /// Blocking form of [`Confer::section_snapshot`].
+        fn section_snapshot(&self, section: &str) -> Option<Arc<Table>>;
+        /// Blocking form of [`Confer::get_arc_value`].
+        fn get_arc_value(&self, section: &str, key: &str) -> Result<Arc<Value>>;
+        /// Blocking form of [`Confer::prune_section_to`].
+        fn prune_section_to(&self, section: &str, known_keys: &[&str]) -> Result<()>;
+        /// Blocking form of [`Confer::contains_key`].
+        fn contains_key(&self, section: &str, key: &str) -> bool;
+        /// Blocking form of [`Confer::value_type`].
+        fn value_type(&self, section: &str, key: &str) -> Option<&'static str>;
+        /// Blocking form of [`Confer::get_section_table`].
+        fn get_section_table(&self, section: &str) -> Option<Table>;
+        /// Blocking form of [`Confer::set_value`].
+        fn set_value(&self, section: &str, key: &str, value: Value) -> Result<()>;
+        /// Blocking form of [`Confer::section_exists`].
+        fn section_exists(&self, section: &str) -> bool;
+        /// Blocking form of [`Confer::add_section`].
+        fn add_section(&self, section: &str) -> Result<()>;
+        /// Blocking form of [`Confer::remove_key`].
+        fn remove_key(&self, section: &str, key: &str) -> Result<()>;
+        /// Blocking form of [`Confer::take_key`].
+        fn take_key(&self, section: &str, key: &str) -> Result<Option<Value>>;
+        /// Blocking form of [`Confer::remove_section`].
+        fn remove_section(&self, section: &str) -> Result<bool>;
+        /// Blocking form of [`Confer::sections`].
+        fn sections(&self) -> Vec<(String, Table)>;
+        /// Blocking form of [`Confer::list_sections`].
+        fn list_sections(&self) -> Vec<String>;
+        /// Blocking form of [`Confer::list_keys`].
+        fn list_keys(&self, section: &str) -> Result<Vec<String>>;
+        /// Blocking form of [`Confer::list_sections_sorted`].
+        fn list_sections_sorted(&self) -> Vec<String>;
+        /// Blocking form of [`Confer::list_keys_sorted`].
+        fn list_keys_sorted(&self, section: &str) -> Result<Vec<String>>;
+        /// Blocking form of [`Confer::section_count`].
+        fn section_count(&self) -> usize;
+        /// Blocking form of [`Confer::is_empty`].
+        fn is_empty(&self) -> bool;
+        /// Blocking form of [`Confer::key_count`].
+        fn key_count(&self, section: &str) -> Result<usize>;
+        /// Blocking form of [`Confer::get_string`].
+        fn get_string(&self, section: &str, key: &str) -> Result<String>;
+        /// Blocking form of [`Confer::get_integer`].
+        fn get_integer(&self, section: &str, key: &str) -> Result<i64>;
+        /// Blocking form of [`Confer::get_integer_coerce`].
+        fn get_integer_coerce(&self, section: &str, key: &str) -> Result<i64>;
+        /// Blocking form of [`Confer::get_float`].
+        fn get_float(&self, section: &str, key: &str) -> Result<f64>;
+        /// Blocking form of [`Confer::get_boolean`].
+        fn get_boolean(&self, section: &str, key: &str) -> Result<bool>;
+        /// Blocking form of [`Confer::get_boolean_lenient`].
+        fn get_boolean_lenient(&self, section: &str, key: &str) -> Result<bool>;
+        /// Blocking form of [`Confer::get_datetime`].
+        fn get_datetime(&self, section: &str, key: &str) -> Result<Datetime>;
+        /// Blocking form of [`Confer::get_datetime_offset`].
+        fn get_datetime_offset(&self, section: &str, key: &str) -> Result<Datetime>;
+        /// Blocking form of [`Confer::get_date`].
+        fn get_date(&self, section: &str, key: &str) -> Result<Date>;
+        /// Blocking form of [`Confer::get_time`].
+        fn get_time(&self, section: &str, key: &str) -> Result<Time>;
+        /// Blocking form of [`Confer::get_date_vec`].
+        fn get_date_vec(&self, section: &str, key: &str) -> Result<Vec<Date>>;
+        /// Blocking form of [`Confer::get_time_vec`].
+        fn get_time_vec(&self, section: &str, key: &str) -> Result<Vec<Time>>;
+        /// Blocking form of [`Confer::get_string_vec`].
+        fn get_string_vec(&self, section: &str, key: &str) -> Result<Vec<String>>;
+        /// Blocking form of [`Confer::get_integer_vec`].
+        fn get_integer_vec(&self, section: &str, key: &str) -> Result<Vec<i64>>;
+        /// Blocking form of [`Confer::get_value_at_index`].
+        fn get_value_at_index(&self, section: &str, key: &str, index: usize) -> Result<Option<Value>>;
+        /// Blocking form of [`Confer::array_len`].
+        fn array_len(&self, section: &str, key: &str) -> Result<usize>;
+        /// Blocking form of [`Confer::get_integer_vec_coerce`].
+        fn get_integer_vec_coerce(&self, section: &str, key: &str) -> Result<Vec<i64>>;
+        /// Blocking form of [`Confer::get_float_vec`].
+        fn get_float_vec(&self, section: &str, key: &str) -> Result<Vec<f64>>;
+        /// Blocking form of [`Confer::get_boolean_vec`].
+        fn get_boolean_vec(&self, section: &str, key: &str) -> Result<Vec<bool>>;
+        /// Blocking form of [`Confer::get_datetime_vec`].
+        fn get_datetime_vec(&self, section: &str, key: &str) -> Result<Vec<Datetime>>;
+        /// Blocking form of [`Confer::get_base64`].
+        fn get_base64(&self, section: &str, key: &str) -> Result<Vec<u8>>;
+        /// Blocking form of [`Confer::get_hex`].
+        fn get_hex(&self, section: &str, key: &str) -> Result<Vec<u8>>;
+        /// Blocking form of [`Confer::get_byte_size`].
+        fn get_byte_size(&self, section: &str, key: &str) -> Result<ByteSize>;
+        /// Blocking form of [`Confer::get_byte_size_vec`].
+        fn get_byte_size_vec(&self, section: &str, key: &str) -> Result<Vec<ByteSize>>;
+        /// Blocking form of [`Confer::get_table`].
+        fn get_table(&self, section: &str, key: &str) -> Result<Table>;
+        /// Blocking form of [`Confer::set_string`].
+        fn set_string(&self, section: &str, key: &str, value: String) -> Result<()>;
+        /// Blocking form of [`Confer::set_integer`].
+        fn set_integer(&self, section: &str, key: &str, value: i64) -> Result<()>;
+        /// Blocking form of [`Confer::set_float`].
+        fn set_float(&self, section: &str, key: &str, value: f64) -> Result<()>;
+        /// Blocking form of [`Confer::set_boolean`].
+        fn set_boolean(&self, section: &str, key: &str, value: bool) -> Result<()>;
+        /// Blocking form of [`Confer::set_datetime`].
+        fn set_datetime(&self, section: &str, key: &str, value: Datetime) -> Result<()>;
+        /// Blocking form of [`Confer::set_string_vec`].
+        fn set_string_vec(&self, section: &str, key: &str, value: Vec<String>) -> Result<()>;
+        /// Blocking form of [`Confer::set_integer_vec`].
+        fn set_integer_vec(&self, section: &str, key: &str, value: Vec<i64>) -> Result<()>;
+        /// Blocking form of [`Confer::set_float_vec`].
+        fn set_float_vec(&self, section: &str, key: &str, value: Vec<f64>) -> Result<()>;
+        /// Blocking form of [`Confer::set_boolean_vec`].
+        fn set_boolean_vec(&self, section: &str, key: &str, value: Vec<bool>) -> Result<()>;
+        /// Blocking form of [`Confer::set_datetime_vec`].
+        fn set_datetime_vec(&self, section: &str, key: &str, value: Vec<Datetime>) -> Result<()>;
+        /// Blocking form of [`Confer::set_date`].
+        fn set_date(&self, section: &str, key: &str, value: Date) -> Result<()>;
+        /// Blocking form of [`Confer::set_time`].
+        fn set_time(&self, section: &str, key: &str, value: Time) -> Result<()>;
+        /// Blocking form of [`Confer::set_date_vec`].
+        fn set_date_vec(&self, section: &str, key: &str, value: Vec<Date>) -> Result<()>;
+        /// Blocking form of [`Confer::set_time_vec`].
+        fn set_time_vec(&self, section: &str, key: &str, value: Vec<Time>) -> Result<()>;
+        /// Blocking form of [`Confer::set_base64`].
+        fn set_base64(&self, section: &str, key: &str, value: Vec<u8>) -> Result<()>;
+        /// Blocking form of [`Confer::set_hex`].
+        fn set_hex(&self, section: &str, key: &str, value: Vec<u8>) -> Result<()>;
+        /// Blocking form of [`Confer::set_byte_size`].
+        fn set_byte_size(&self, section: &str, key: &str, value: ByteSize) -> Result<()>;
+        /// Blocking form of [`Confer::set_byte_size_vec`].
+        fn set_byte_size_vec(&self, section: &str, key: &str, value: Vec<ByteSize>) -> Result<()>;
+        /// Blocking form of [`Confer::set_table`].
+        fn set_table(&self, section: &str, key: &str, value: Table) -> Result<()>;
+    }
+}
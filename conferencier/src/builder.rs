@@ -0,0 +1,182 @@
+//! Configurable construction of [`Confer`] stores.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::error::{ConferError, Result};
+use crate::store::{Confer, FileStamp, SharedConfer};
+
+/// Nesting limit applied unless [`ConferBuilder::max_depth`] overrides it.
+pub(crate) const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// Behavioural options fixed when a store is constructed.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ConferOptions {
+    /// Resolve section and key names ignoring ASCII case when no exact match exists.
+    pub case_insensitive: bool,
+    /// Keep the parsed source document and replay changes onto it when serializing.
+    pub preserve_format: bool,
+    /// Deepest table/array nesting accepted on load or insert; sections are depth 1.
+    pub max_depth: usize,
+    /// Reject writes to sections that do not exist yet instead of creating them.
+    pub require_existing_sections: bool,
+    /// Let typed scalar accessors unwrap one-element arrays and array accessors wrap scalars.
+    pub coerce_singleton_arrays: bool,
+    /// Have derived `from_confer` claim module keys and reject overlapping modules.
+    pub detect_section_conflicts: bool,
+}
+
+impl Default for ConferOptions {
+    fn default() -> Self {
+        Self {
+            case_insensitive: false,
+            preserve_format: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+            require_existing_sections: false,
+            coerce_singleton_arrays: false,
+            detect_section_conflicts: false,
+        }
+    }
+}
+
+/// Builder for [`Confer`] stores with non-default behaviour.
+///
+/// ```
+/// # fn main() -> conferencier::Result<()> {
+/// let store = conferencier::Confer::builder()
+///     .case_insensitive(true)
+///     .build_from_str("[App]\nName = \"demo\"\n")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ConferBuilder {
+    options: ConferOptions,
+}
+
+impl ConferBuilder {
+    /// Creates a builder with all options disabled, matching [`Confer::new`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes section and key lookups fall back to an ASCII case-insensitive match.
+    ///
+    /// Exact matches always win; writes to an existing key reuse its stored spelling.
+    pub fn case_insensitive(mut self, enabled: bool) -> Self {
+        self.options.case_insensitive = enabled;
+        self
+    }
+
+    /// Preserves comments, whitespace, and ordering of loaded TOML when saving.
+    ///
+    /// Only the entries that changed since the last load are rewritten by [`Confer::save_str`]
+    /// and [`Confer::save_file`].
+    pub fn preserve_format(mut self, enabled: bool) -> Self {
+        self.options.preserve_format = enabled;
+        self
+    }
+
+    /// Limits how deeply tables and arrays may nest, counting sections as depth 1.
+    ///
+    /// Deeper input fails with [`ConferError::ValueParse`] on load and on insert instead of
+    /// risking a stack overflow in recursive traversals. Defaults to 128.
+    pub fn max_depth(mut self, limit: usize) -> Self {
+        self.options.max_depth = limit;
+        self
+    }
+
+    /// Makes writes that would create a section fail with [`ConferError::MissingKey`] (key
+    /// `<section>`), catching typos such as `[Servr]`.
+    ///
+    /// This covers the `set_*` and `get_or_insert_*` helpers, [`Confer::set_many`],
+    /// [`Confer::patch`], [`Confer::replace_section`], [`Confer::import_sections`] and the
+    /// target of [`Confer::copy_section`] and [`Confer::move_section`].
+    ///
+    /// Sections must then be declared with [`Confer::add_section`] or by loading TOML first.
+    pub fn require_existing_sections(mut self, enabled: bool) -> Self {
+        self.options.require_existing_sections = enabled;
+        self
+    }
+
+    /// Lets typed scalar getters such as [`Confer::get_string`] read a one-element array like
+    /// `hosts = ["a"]`, and typed array getters such as [`Confer::get_string_vec`] read a bare
+    /// scalar as a one-element vector.
+    ///
+    /// Arrays of any other length still fail with [`ConferError::TypeMismatch`] in scalar
+    /// getters. [`Confer::get_value`] and [`Confer::get`] always return the stored shape.
+    pub fn coerce_singleton_arrays(mut self, enabled: bool) -> Self {
+        self.options.coerce_singleton_arrays = enabled;
+        self
+    }
+
+    /// Makes derived `from_confer` register the keys of each module type with
+    /// [`Confer::register_module_section`], failing with [`ConferError::SectionConflict`] when
+    /// two types claim the same key of a section.
+    ///
+    /// Without this option, derived modules register nothing and may overlap freely.
+    pub fn detect_section_conflicts(mut self, enabled: bool) -> Self {
+        self.options.detect_section_conflicts = enabled;
+        self
+    }
+
+    /// Builds an empty store.
+    pub fn build(self) -> SharedConfer {
+        Arc::new(Confer::with_options(self.options))
+    }
+
+    /// Builds a store from a TOML string.
+    pub fn build_from_str(self, source: &str) -> Result<SharedConfer> {
+        let store = Confer::with_options(self.options);
+        store.replace_from_source(source)?;
+        Ok(Arc::new(store))
+    }
+
+    /// Builds a store from TOML held in a byte buffer, parsing it in place.
+    ///
+    /// Invalid UTF-8 fails with a [`ConferError::Io`] of kind [`InvalidData`](std::io::ErrorKind::InvalidData).
+    pub fn build_from_slice(self, bytes: &[u8]) -> Result<SharedConfer> {
+        self.build_from_str(utf8_source(bytes, None)?)
+    }
+
+    /// Synchronously reads a TOML file from disk and builds a store from it.
+    pub fn build_from_file(self, path: impl AsRef<Path>) -> Result<SharedConfer> {
+        let path_buf = path.as_ref().to_path_buf();
+        let stamp = FileStamp::read_sync(&path_buf)?;
+        let contents = std::fs::read_to_string(&path_buf)
+            .map_err(|err| ConferError::io_error(Some(path_buf.clone()), err))?;
+        self.build_from_file_contents(&contents, stamp)
+    }
+
+    /// Builds a store from the `contents` read from a file and remembers its `stamp` for
+    /// [`Confer::reload_if_changed`].
+    pub(crate) fn build_from_file_contents(self, contents: &str, stamp: FileStamp) -> Result<SharedConfer> {
+        let store = self.build_from_str(contents)?;
+        store.remember_file_stamp(stamp);
+        Ok(store)
+    }
+
+    /// Memory-maps a TOML file and builds a store by parsing the mapping directly.
+    ///
+    /// The file contents never get copied into a heap buffer, so loading a large file costs
+    /// little more than the parsed table. The file must not be modified while it is parsed.
+    #[cfg(feature = "mmap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+    pub fn build_from_file_mmap(self, path: impl AsRef<Path>) -> Result<SharedConfer> {
+        let path = path.as_ref();
+        let io_error = |err| ConferError::io_error(Some(path.to_path_buf()), err);
+        let file = std::fs::File::open(path).map_err(io_error)?;
+        // SAFETY: the mapping is read-only and dropped before returning; concurrent truncation
+        // by another process is the documented caller responsibility.
+        let map = unsafe { memmap2::Mmap::map(&file) }.map_err(io_error)?;
+        self.build_from_str(utf8_source(&map, Some(path))?)
+    }
+}
+
+/// Borrows `bytes` as TOML source text, reporting invalid UTF-8 like `read_to_string` does.
+pub(crate) fn utf8_source<'a>(bytes: &'a [u8], path: Option<&Path>) -> Result<&'a str> {
+    std::str::from_utf8(bytes).map_err(|err| {
+        let err = std::io::Error::new(std::io::ErrorKind::InvalidData, err);
+        ConferError::io_error(path.map(Path::to_path_buf), err)
+    })
+}
@@ -0,0 +1,171 @@
+//! Human-readable byte counts such as `"10MB"` or `"4GiB"`.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Suffixes accepted by [`ByteSize`], largest first so formatting picks the widest exact unit.
+const UNITS: &[(&str, u64)] = &[
+    ("TiB", 1 << 40),
+    ("TB", 1_000_000_000_000),
+    ("GiB", 1 << 30),
+    ("GB", 1_000_000_000),
+    ("MiB", 1 << 20),
+    ("MB", 1_000_000),
+    ("KiB", 1 << 10),
+    ("KB", 1_000),
+    ("B", 1),
+];
+
+/// Number of bytes, stored in TOML as a bare integer or a string with a unit suffix.
+///
+/// Decimal (`KB`, `MB`, `GB`, `TB`) and binary (`KiB`, `MiB`, `GiB`, `TiB`) units are
+/// accepted case-insensitively, with optional whitespace and fractions such as `"1.5GB"`.
+/// Formatting uses the largest unit that represents the count exactly.
+///
+/// ```
+/// use conferencier::ByteSize;
+///
+/// let size: ByteSize = "10MB".parse().unwrap();
+/// assert_eq!(size.as_u64(), 10_000_000);
+/// assert_eq!(ByteSize(4 << 30).to_string(), "4GiB");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteSize(pub u64);
+
+impl ByteSize {
+    /// Returns the number of bytes.
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(bytes: u64) -> Self {
+        Self(bytes)
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (unit, factor) = UNITS
+            .iter()
+            .find(|(_, factor)| self.0 >= *factor && self.0.is_multiple_of(*factor))
+            .unwrap_or(&("B", 1));
+        write!(f, "{}{unit}", self.0 / factor)
+    }
+}
+
+/// Error returned when a string is not a valid [`ByteSize`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseByteSizeError(String);
+
+impl fmt::Display for ParseByteSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ParseByteSizeError {}
+
+impl FromStr for ByteSize {
+    type Err = ParseByteSizeError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        match parse_bytes(raw) {
+            Ok(bytes) => Ok(Self(bytes)),
+            Err(ParseFailure::Invalid) => Err(ParseByteSizeError(format!("invalid byte size `{raw}`"))),
+            Err(ParseFailure::Overflow) => Err(ParseByteSizeError(format!("byte size `{raw}` overflows u64"))),
+        }
+    }
+}
+
+/// Why [`parse_bytes`] rejected its input.
+pub(crate) enum ParseFailure {
+    Invalid,
+    Overflow,
+}
+
+/// Parses a byte count such as `"10MB"` or `"1.5 GiB"`, rounding fractions to the nearest byte.
+///
+/// This is `const` so `#[confer(default = "...")]` byte-size defaults are checked against the
+/// same unit table at compile time.
+pub(crate) const fn parse_bytes(raw: &str) -> Result<u64, ParseFailure> {
+    let text = raw.trim_ascii().as_bytes();
+    let mut split = 0;
+    while split < text.len() && !text[split].is_ascii_alphabetic() {
+        split += 1;
+    }
+    let (number, unit) = text.split_at(split);
+    let number = number.trim_ascii_end();
+
+    let factor = if unit.is_empty() {
+        1
+    } else {
+        let mut index = 0;
+        loop {
+            if index == UNITS.len() {
+                return Err(ParseFailure::Invalid);
+            }
+            if UNITS[index].0.as_bytes().eq_ignore_ascii_case(unit) {
+                break UNITS[index].1 as u128;
+            }
+            index += 1;
+        }
+    };
+
+    let (mut mantissa, mut scale, mut digits, mut seen_point) = (0_u128, 1_u128, 0, false);
+    let mut index = 0;
+    while index < number.len() {
+        match number[index] {
+            b'.' if !seen_point => seen_point = true,
+            digit @ b'0'..=b'9' => {
+                let (Some(shifted), Some(next_scale)) = (mantissa.checked_mul(10), scale.checked_mul(10)) else {
+                    return Err(ParseFailure::Overflow);
+                };
+                mantissa = shifted + (digit - b'0') as u128;
+                if seen_point {
+                    scale = next_scale;
+                }
+                digits += 1;
+            }
+            _ => return Err(ParseFailure::Invalid),
+        }
+        index += 1;
+    }
+    if digits == 0 {
+        return Err(ParseFailure::Invalid);
+    }
+    let Some(scaled) = mantissa.checked_mul(factor) else {
+        return Err(ParseFailure::Overflow);
+    };
+    let bytes = (scaled + scale / 2) / scale;
+    if bytes > u64::MAX as u128 {
+        return Err(ParseFailure::Overflow);
+    }
+    Ok(bytes as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_units_and_formats_exactly() {
+        let parse = |raw: &str| raw.parse::<ByteSize>().map(ByteSize::as_u64);
+        assert_eq!(parse("512"), Ok(512));
+        assert_eq!(parse("10MB"), Ok(10_000_000));
+        assert_eq!(parse("4 gib"), Ok(4 << 30));
+        assert_eq!(parse("1.5KB"), Ok(1_500));
+        assert!(parse("10XB").is_err());
+        assert!(parse("-1MB").is_err());
+        assert!(parse("20000000TiB").is_err());
+        assert_eq!(parse(" 0.5 KiB "), Ok(512));
+        assert!(parse("1..5KB").is_err());
+        assert!(parse("MB").is_err());
+
+        assert_eq!(ByteSize(0).to_string(), "0B");
+        assert_eq!(ByteSize(1_500).to_string(), "1500B");
+        assert_eq!(ByteSize(10_000_000).to_string(), "10MB");
+        assert_eq!(ByteSize(3 << 20).to_string(), "3MiB");
+    }
+}
@@ -0,0 +1,61 @@
+//! Change notifications published by [`Confer`](crate::Confer) mutations.
+
+use std::sync::OnceLock;
+
+use tokio::sync::broadcast;
+
+/// Number of events buffered per subscriber before the oldest are dropped.
+const CHANNEL_CAPACITY: usize = 256;
+
+/// Kind of mutation described by a [`ChangeEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// A value was inserted or replaced.
+    Set,
+    /// A key was removed from its section.
+    Removed,
+    /// A whole section was removed.
+    SectionRemoved,
+}
+
+/// Notification sent to [`Confer::subscribe`](crate::Confer::subscribe) receivers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeEvent {
+    /// Section that was modified.
+    pub section: String,
+    /// Affected key, or `None` for [`ChangeKind::SectionRemoved`].
+    pub key: Option<String>,
+    /// What happened to the key or section.
+    pub kind: ChangeKind,
+}
+
+/// Lazily created broadcast sender; mutations cost one atomic load until someone subscribes.
+#[derive(Debug, Default)]
+pub(crate) struct ChangeNotifier {
+    sender: OnceLock<broadcast::Sender<ChangeEvent>>,
+}
+
+impl ChangeNotifier {
+    /// Returns a new receiver, creating the channel on first use.
+    pub fn subscribe(&self) -> broadcast::Receiver<ChangeEvent> {
+        self.sender
+            .get_or_init(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+
+    /// Publishes an event when at least one receiver is alive.
+    ///
+    /// The mutation is also traced, whether or not anyone subscribed.
+    pub fn notify(&self, section: &str, key: Option<&str>, kind: ChangeKind) {
+        crate::trace::mutation(section, key, kind);
+        if let Some(sender) = self.sender.get()
+            && sender.receiver_count() > 0
+        {
+            let _ = sender.send(ChangeEvent {
+                section: section.to_owned(),
+                key: key.map(str::to_owned),
+                kind,
+            });
+        }
+    }
+}
@@ -9,13 +9,121 @@ use crate::store::SharedConfer;
 /// Shared, asynchronous handle to a module derived with [`ConferModule`].
 pub type SharedConferModule<T> = Arc<RwLock<T>>;
 
+/// Compile-time description of one key of a module, as returned by [`ConferModule::schema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FieldSchema {
+    /// Section holding the key.
+    pub section: &'static str,
+    /// TOML key, after `rename`/`rename_all`.
+    pub key: &'static str,
+    /// Rust type of the field as written, e.g. `Option<Vec<String>>`.
+    pub type_name: &'static str,
+    /// `true` when loading fails with [`ConferError::MissingKey`](crate::ConferError::MissingKey)
+    /// if the key is absent.
+    pub required: bool,
+    /// Source text of the `#[confer(default = ...)]` expression, if any.
+    pub default_repr: Option<&'static str>,
+    /// Shape of the TOML value stored under the key.
+    pub value_type: SchemaType,
+}
+
+/// TOML value shape of a key described by [`FieldSchema`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SchemaType {
+    String,
+    Integer,
+    Float,
+    Boolean,
+    Datetime,
+    /// [`ByteSize`](crate::ByteSize): raw bytes as an integer or a string with a unit suffix.
+    ByteSize,
+    /// Value converted by a `#[confer(with = ...)]` module; its shape is not known statically.
+    Custom,
+    /// Nested table with arbitrary values, as used by `HashMap<String, toml::Value>` fields.
+    Table,
+    /// Array of `items`; `len` is fixed for tuples and `[T; N]` fields.
+    Array {
+        items: &'static SchemaType,
+        len: Option<usize>,
+    },
+}
+
 /// Trait implemented by structs annotated with `#[derive(ConferModule)]`.
 #[async_trait]
 pub trait ConferModule: Send + Sync + Sized + 'static {
+    /// TOML section the module reads and writes, as resolved from `#[confer(section = ...)]`.
+    ///
+    /// Manual implementations that do not override this report an empty name.
+    const SECTION: &'static str = "";
+
+    /// Keys the module owns in [`ConferModule::SECTION`]: its fields' keys, those of flattened
+    /// fields, and the schema-version key of versioned modules. `save` keeps these keys and prunes
+    /// every other key of the section; `load_strict` rejects any key missing from the list.
+    ///
+    /// Manual implementations that do not override this own no keys.
+    fn owned_keys() -> &'static [&'static str] {
+        &[]
+    }
     /// Instantiates the module from the provided [`SharedConfer`], performing an initial load.
     async fn from_confer(store: SharedConfer) -> Result<SharedConferModule<Self>>;
     /// Refreshes the module state from the shared store.
     async fn load(module: &SharedConferModule<Self>, store: SharedConfer) -> Result<()>;
+    /// Loads the module like [`ConferModule::load`], but first fails on the first key in its
+    /// section that no field maps to, catching typos such as `prot` for `port`. A rejected load
+    /// leaves the module untouched. Versioned modules run their schema migration first, so keys
+    /// that `migrate` renames or drops are not reported.
+    ///
+    /// Such keys are reported as [`ConferError::UnknownKey`](crate::ConferError::UnknownKey).
+    /// Manual implementations that do not override this run a plain [`ConferModule::load`].
+    async fn load_strict(module: &SharedConferModule<Self>, store: SharedConfer) -> Result<()> {
+        Self::load(module, store).await
+    }
+    /// Reloads the module from the store and returns the names of fields whose value changed.
+    ///
+    /// Ignored fields are left untouched and never reported. Manual implementations that do not
+    /// override this run [`ConferModule::load`] and report no fields.
+    async fn reload(module: &SharedConferModule<Self>, store: SharedConfer) -> Result<Vec<&'static str>> {
+        Self::load(module, store).await?;
+        Ok(Vec::new())
+    }
     /// Persists the module state back to the shared store.
     async fn save(module: &SharedConferModule<Self>, store: SharedConfer) -> Result<()>;
+    /// Persists only the keys whose values differ from the last load or save of this module.
+    ///
+    /// Owned keys that became absent (for example an `Option` set to `None`) are removed; keys
+    /// the module does not own are never touched. Manual implementations that do not override
+    /// this run a full [`ConferModule::save`].
+    async fn save_changes(module: &SharedConferModule<Self>, store: SharedConfer) -> Result<()> {
+        Self::save(module, store).await
+    }
+    /// Persists the module only if its section is unchanged since the last load or save.
+    ///
+    /// Returns `Ok(false)` without writing when another writer modified the section in the
+    /// meantime, allowing callers to reload and retry instead of overwriting those changes. Derived
+    /// modules compare and write under a single store write lock, so any writer, including plain
+    /// setters, is either seen by the check or ordered after the save.
+    ///
+    /// Manual implementations that do not override this save unconditionally and return `true`.
+    async fn save_if_unchanged(module: &SharedConferModule<Self>, store: SharedConfer) -> Result<bool> {
+        Self::save(module, store).await?;
+        Ok(true)
+    }
+    /// Describes every persisted key, including those of flattened fields, without a store.
+    ///
+    /// Derived modules list their fields in declaration order; manual implementations report
+    /// nothing unless they override this.
+    fn schema() -> Vec<FieldSchema> {
+        Vec::new()
+    }
+    /// Renders [`ConferModule::schema`] as a JSON Schema (draft 2020-12) object describing the
+    /// module's section, for editor autocomplete and validation.
+    ///
+    /// Keys that must be present on load are listed under `required`.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    fn json_schema() -> serde_json::Value {
+        crate::json::schema_document(&Self::schema())
+    }
 }
@@ -0,0 +1,199 @@
+//! Traits backing the generic [`Confer::get`](crate::Confer::get) and
+//! [`Confer::set`](crate::Confer::set) accessors.
+
+use toml::Value;
+use toml::value::{Date, Datetime, Time};
+
+use crate::byte_size::ByteSize;
+use crate::error::{ConferError, Result};
+use crate::value_conversion;
+
+/// Types that can be extracted from a stored TOML [`Value`].
+///
+/// `section` and `key` are only used to give errors their context.
+pub trait FromConferValue: Sized {
+    /// Converts `value`, reporting mismatches against `section.key`.
+    fn from_confer_value(section: &str, key: &str, value: Value) -> Result<Self>;
+}
+
+/// Types that can be written into the store as a TOML [`Value`].
+pub trait IntoConferValue {
+    /// Converts `self`, reporting unrepresentable values against `section.key`.
+    fn into_confer_value(self, section: &str, key: &str) -> Result<Value>;
+}
+
+/// Implements both traits for a type with a dedicated `value_conversion` function.
+macro_rules! direct_conversion {
+    ($ty:ty, $from:path, $variant:ident) => {
+        impl FromConferValue for $ty {
+            fn from_confer_value(section: &str, key: &str, value: Value) -> Result<Self> {
+                $from(section, key, value)
+            }
+        }
+
+        impl IntoConferValue for $ty {
+            fn into_confer_value(self, _section: &str, _key: &str) -> Result<Value> {
+                Ok(Value::$variant(self))
+            }
+        }
+    };
+}
+
+direct_conversion!(i64, value_conversion::integer, Integer);
+direct_conversion!(f64, value_conversion::float, Float);
+direct_conversion!(bool, value_conversion::boolean, Boolean);
+direct_conversion!(Datetime, value_conversion::datetime, Datetime);
+
+impl FromConferValue for String {
+    fn from_confer_value(section: &str, key: &str, value: Value) -> Result<Self> {
+        value_conversion::string(section, key, value)
+    }
+}
+
+impl IntoConferValue for String {
+    fn into_confer_value(self, section: &str, key: &str) -> Result<Value> {
+        value_conversion::validate_string(section, key, &self)?;
+        Ok(Value::String(self))
+    }
+}
+
+impl IntoConferValue for &str {
+    fn into_confer_value(self, section: &str, key: &str) -> Result<Value> {
+        self.to_owned().into_confer_value(section, key)
+    }
+}
+
+/// Implements both traits for integer types narrower or wider than TOML's `i64`.
+macro_rules! integer_conversion {
+    ($($ty:ty),*) => {
+        $(
+            impl FromConferValue for $ty {
+                fn from_confer_value(section: &str, key: &str, value: Value) -> Result<Self> {
+                    let raw = value_conversion::integer(section, key, value)?;
+                    <$ty>::try_from(raw).map_err(|_| {
+                        ConferError::value_parse_owned(
+                            section,
+                            key,
+                            format!("value out of range for {}", stringify!($ty)),
+                        )
+                    })
+                }
+            }
+
+            impl IntoConferValue for $ty {
+                fn into_confer_value(self, section: &str, key: &str) -> Result<Value> {
+                    i64::try_from(self).map(Value::Integer).map_err(|_| {
+                        ConferError::value_parse_owned(
+                            section,
+                            key,
+                            format!("value `{self}` out of range for TOML integer"),
+                        )
+                    })
+                }
+            }
+        )*
+    };
+}
+
+integer_conversion!(i8, i16, i32, isize, u8, u16, u32, u64, usize);
+
+impl FromConferValue for f32 {
+    fn from_confer_value(section: &str, key: &str, value: Value) -> Result<Self> {
+        let raw = value_conversion::float(section, key, value)?;
+        if !raw.is_finite() {
+            return Err(ConferError::value_parse(section, key, "non-finite float"));
+        }
+        if raw < f32::MIN as f64 || raw > f32::MAX as f64 {
+            return Err(ConferError::value_parse(section, key, "value out of range for f32"));
+        }
+        Ok(raw as f32)
+    }
+}
+
+impl IntoConferValue for f32 {
+    fn into_confer_value(self, _section: &str, _key: &str) -> Result<Value> {
+        Ok(Value::Float(f64::from(self)))
+    }
+}
+
+impl FromConferValue for Date {
+    fn from_confer_value(section: &str, key: &str, value: Value) -> Result<Self> {
+        value_conversion::date(section, key, value)
+    }
+}
+
+impl IntoConferValue for Date {
+    fn into_confer_value(self, _section: &str, _key: &str) -> Result<Value> {
+        Ok(Value::Datetime(self.into()))
+    }
+}
+
+impl FromConferValue for Time {
+    fn from_confer_value(section: &str, key: &str, value: Value) -> Result<Self> {
+        value_conversion::time(section, key, value)
+    }
+}
+
+impl IntoConferValue for Time {
+    fn into_confer_value(self, _section: &str, _key: &str) -> Result<Value> {
+        Ok(Value::Datetime(self.into()))
+    }
+}
+
+impl FromConferValue for ByteSize {
+    fn from_confer_value(section: &str, key: &str, value: Value) -> Result<Self> {
+        value_conversion::byte_size(section, key, value)
+    }
+}
+
+impl IntoConferValue for ByteSize {
+    fn into_confer_value(self, _section: &str, _key: &str) -> Result<Value> {
+        Ok(Value::String(self.to_string()))
+    }
+}
+
+impl<T: FromConferValue> FromConferValue for Vec<T> {
+    fn from_confer_value(section: &str, key: &str, value: Value) -> Result<Self> {
+        value_conversion::vec_of(section, key, value, T::from_confer_value)
+    }
+}
+
+impl<T: IntoConferValue> IntoConferValue for Vec<T> {
+    fn into_confer_value(self, section: &str, key: &str) -> Result<Value> {
+        self.into_iter()
+            .enumerate()
+            .map(|(index, item)| {
+                item.into_confer_value(section, key)
+                    .map_err(|err| value_conversion::annotate_with_index(err, index))
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Value::Array)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vec_conversion_reports_element_index() {
+        let value = Value::Array(vec![Value::Integer(1), Value::Integer(300)]);
+        let err = Vec::<u8>::from_confer_value("S", "k", value).unwrap_err();
+        assert!(err.to_string().contains("out of range for u8 (at index 1)"), "{err}");
+    }
+
+    #[test]
+    fn vec_conversion_matches_typed_element_errors() {
+        let value = Value::Array(vec![Value::String("a".into()), Value::Integer(1)]);
+        let generic = Vec::<String>::from_confer_value("S", "k", value.clone()).unwrap_err();
+        let typed = value_conversion::string_vec("S", "k", value).unwrap_err();
+        assert_eq!(generic.to_string(), typed.to_string());
+    }
+
+    #[test]
+    fn u64_beyond_i64_is_rejected() {
+        let err = u64::MAX.into_confer_value("S", "k").unwrap_err();
+        assert!(matches!(err, ConferError::ValueParse { .. }));
+        assert_eq!(7u16.into_confer_value("S", "k").unwrap(), Value::Integer(7));
+    }
+}
@@ -0,0 +1,147 @@
+//! Key-level differences between two configuration tables.
+
+use toml::{Table, Value};
+
+/// Key placeholder used when a whole section entry is not a table.
+const SECTION_KEY: &str = "<section>";
+
+/// One key that differs between two configurations, as reported by [`Confer::diff`] and
+/// [`Confer::diff_against_file`].
+///
+/// [`Confer::diff`]: crate::Confer::diff
+/// [`Confer::diff_against_file`]: crate::Confer::diff_against_file
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ConfigChange {
+    /// The key exists only on the new side.
+    Added {
+        section: String,
+        key: String,
+        value: Value,
+    },
+    /// The key exists only on the old side.
+    Removed {
+        section: String,
+        key: String,
+        value: Value,
+    },
+    /// The key exists on both sides with different values.
+    Modified {
+        section: String,
+        key: String,
+        old: Value,
+        new: Value,
+    },
+}
+
+impl ConfigChange {
+    /// Section containing the changed key.
+    pub fn section(&self) -> &str {
+        match self {
+            Self::Added { section, .. }
+            | Self::Removed { section, .. }
+            | Self::Modified { section, .. } => section,
+        }
+    }
+
+    /// Changed key, or `<section>` when the section itself is not a table.
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Added { key, .. } | Self::Removed { key, .. } | Self::Modified { key, .. } => key,
+        }
+    }
+}
+
+/// Lists the changes turning `old` into `new`, ordered by section and then key.
+pub(crate) fn diff_tables(old: &Table, new: &Table) -> Vec<ConfigChange> {
+    let mut sections: Vec<&String> = old.keys().chain(new.keys()).collect();
+    sections.sort();
+    sections.dedup();
+
+    let mut changes = Vec::new();
+    for section in sections {
+        match (old.get(section), new.get(section)) {
+            (Some(Value::Table(old)), Some(Value::Table(new))) => {
+                diff_section(section, old, new, &mut changes)
+            }
+            (Some(Value::Table(old)), None) => {
+                diff_section(section, old, &Table::new(), &mut changes)
+            }
+            (None, Some(Value::Table(new))) => {
+                diff_section(section, &Table::new(), new, &mut changes)
+            }
+            (old, new) => push_change(section, SECTION_KEY, old, new, &mut changes),
+        }
+    }
+    changes
+}
+
+/// Appends the key-level changes between two versions of `section`.
+fn diff_section(section: &str, old: &Table, new: &Table, changes: &mut Vec<ConfigChange>) {
+    let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        push_change(section, key, old.get(key), new.get(key), changes);
+    }
+}
+
+/// Records the change from `old` to `new` at `section.key`, if any.
+fn push_change(
+    section: &str,
+    key: &str,
+    old: Option<&Value>,
+    new: Option<&Value>,
+    changes: &mut Vec<ConfigChange>,
+) {
+    let (section, key) = (section.to_owned(), key.to_owned());
+    let change = match (old, new) {
+        (None, Some(value)) => ConfigChange::Added {
+            section,
+            key,
+            value: value.clone(),
+        },
+        (Some(value), None) => ConfigChange::Removed {
+            section,
+            key,
+            value: value.clone(),
+        },
+        (Some(old), Some(new)) if old != new => ConfigChange::Modified {
+            section,
+            key,
+            old: old.clone(),
+            new: new.clone(),
+        },
+        _ => return,
+    };
+    changes.push(change);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_reports_added_removed_and_modified_keys() {
+        let old: Table =
+            toml::from_str("[App]\nport = 1\nname = \"a\"\n[Gone]\nx = 1\n").expect("old");
+        let new: Table =
+            toml::from_str("[App]\nport = 2\nname = \"a\"\nmode = \"fast\"\n").expect("new");
+
+        let changes = diff_tables(&old, &new);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|change| (change.section(), change.key()))
+            .collect();
+        assert_eq!(summary, [("App", "mode"), ("App", "port"), ("Gone", "x")]);
+        assert!(
+            matches!(&changes[0], ConfigChange::Added { value, .. } if value.as_str() == Some("fast"))
+        );
+        assert!(matches!(
+            &changes[1],
+            ConfigChange::Modified { old, new, .. } if old.as_integer() == Some(1) && new.as_integer() == Some(2)
+        ));
+        assert!(matches!(&changes[2], ConfigChange::Removed { .. }));
+        assert!(diff_tables(&new, &new).is_empty());
+    }
+}
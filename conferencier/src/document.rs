@@ -0,0 +1,168 @@
+//! Format-preserving serialization that replays store changes onto the original TOML document.
+
+use serde::ser::Error as _;
+use toml::{Table, Value};
+use toml_edit::{DocumentMut, Item};
+
+use crate::error::{ConferError, Result};
+
+/// Parses `source` into an editable document, returning `None` if it cannot be represented.
+pub fn parse(source: &str) -> Option<DocumentMut> {
+    crate::store::strip_bom(source).parse().ok()
+}
+
+/// Renders `table` by applying its differences to `document`, keeping untouched comments,
+/// whitespace, and ordering intact.
+pub fn render(document: &DocumentMut, table: &Table) -> Result<String> {
+    let original: Table = toml::from_str(&document.to_string())?;
+    let mut document = document.clone();
+    sync_table(document.as_table_mut(), &original, table)?;
+    Ok(document.to_string())
+}
+
+/// Rewrites single-line arrays in `source` holding more than `threshold` elements so that each
+/// element sits on its own indented line, followed by a trailing comma.
+///
+/// Arrays inside inline tables stay on one line, since TOML 1.0 forbids newlines there.
+pub fn expand_arrays(source: &str, threshold: usize) -> Result<String> {
+    let mut document: DocumentMut = source
+        .parse()
+        .map_err(|err| ConferError::Serialize(toml::ser::Error::custom(err)))?;
+    expand_table(document.as_table_mut(), threshold);
+    Ok(document.to_string())
+}
+
+/// Expands the long arrays stored directly or in sub-tables of `table`.
+fn expand_table(table: &mut toml_edit::Table, threshold: usize) {
+    for (_, item) in table.iter_mut() {
+        match item {
+            Item::Table(inner) => expand_table(inner, threshold),
+            Item::ArrayOfTables(tables) => tables.iter_mut().for_each(|inner| expand_table(inner, threshold)),
+            Item::Value(toml_edit::Value::Array(array)) => expand_array(array, threshold, 1),
+            _ => {}
+        }
+    }
+}
+
+/// Places each element of `array` on its own line, indented by `depth` levels.
+fn expand_array(array: &mut toml_edit::Array, threshold: usize, depth: usize) {
+    let expand = array.len() > threshold && !array.to_string().contains('\n');
+    let inner_depth = if expand { depth + 1 } else { depth };
+    for item in array.iter_mut() {
+        if let toml_edit::Value::Array(inner) = item {
+            expand_array(inner, threshold, inner_depth);
+        }
+    }
+    if !expand {
+        return;
+    }
+    let indent = "    ".repeat(depth);
+    for item in array.iter_mut() {
+        let decor = item.decor_mut();
+        decor.set_prefix(format!("\n{indent}"));
+        decor.set_suffix("");
+    }
+    array.set_trailing_comma(true);
+    array.set_trailing(format!("\n{}", "    ".repeat(depth - 1)));
+}
+
+/// Brings `target` in line with `current`, leaving entries equal to `original` untouched.
+fn sync_table(target: &mut toml_edit::Table, original: &Table, current: &Table) -> Result<()> {
+    let stale: Vec<String> = target
+        .iter()
+        .map(|(key, _)| key.to_owned())
+        .filter(|key| !current.contains_key(key))
+        .collect();
+    for key in stale {
+        target.remove(&key);
+    }
+
+    for (key, value) in current {
+        if original.get(key) == Some(value) && target.contains_key(key) {
+            continue;
+        }
+        match (value, target.get_mut(key)) {
+            (Value::Table(inner), Some(Item::Table(existing))) => {
+                let empty = Table::new();
+                let original_inner = original
+                    .get(key)
+                    .and_then(Value::as_table)
+                    .unwrap_or(&empty);
+                sync_table(existing, original_inner, inner)?;
+            }
+            (Value::Table(_), _) => {
+                target.insert(key, to_item(value)?);
+            }
+            (_, Some(Item::Value(existing))) => {
+                let decor = existing.decor().clone();
+                let mut replacement = to_edit_value(value)?;
+                *replacement.decor_mut() = decor;
+                *existing = replacement;
+            }
+            (_, _) => {
+                target.insert(key, to_item(value)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Converts a store value into a document item, using standard tables for sub-tables.
+fn to_item(value: &Value) -> Result<Item> {
+    match value {
+        Value::Table(table) => {
+            let document: DocumentMut = toml::to_string(table)?
+                .parse()
+                .map_err(|err| ConferError::Serialize(toml::ser::Error::custom(err)))?;
+            Ok(Item::Table(document.as_table().clone()))
+        }
+        other => Ok(Item::Value(to_edit_value(other)?)),
+    }
+}
+
+/// Converts a store value into an inline document value.
+fn to_edit_value(value: &Value) -> Result<toml_edit::Value> {
+    value
+        .to_string()
+        .parse()
+        .map_err(|err| ConferError::Serialize(toml::ser::Error::custom(err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_arrays_splits_long_arrays_only() -> Result<()> {
+        let source = "[App]\nshort = [1, 2]\nhosts = [\"a\", \"b\", \"c\"]\nnested = [[1, 2, 3], [4], [5]]\ninline = { list = [1, 2, 3] }\n";
+        let expanded = expand_arrays(source, 2)?;
+        assert_eq!(
+            expanded,
+            "[App]\nshort = [1, 2]\nhosts = [\n    \"a\",\n    \"b\",\n    \"c\",\n]\n\
+             nested = [\n    [\n        1,\n        2,\n        3,\n    ],\n    [4],\n    [5],\n]\ninline = { list = [1, 2, 3] }\n"
+        );
+        assert_eq!(toml::from_str::<Table>(&expanded)?, toml::from_str::<Table>(source)?);
+        assert_eq!(expand_arrays(&expanded, 2)?, expanded);
+        Ok(())
+    }
+
+    #[test]
+    fn render_keeps_comments_and_order() -> Result<()> {
+        let source = "# top comment\n[Srv]\nport = 80 # listen port\nhost = \"a\"\n\n[Old]\nx = 1\n";
+        let document = parse(source).expect("document");
+        let mut table: Table = toml::from_str(source)?;
+        {
+            let srv = table["Srv"].as_table_mut().expect("section");
+            srv.insert("port".into(), Value::Integer(8080));
+            srv.insert("extra".into(), Value::Boolean(true));
+        }
+        table.remove("Old");
+
+        let rendered = render(&document, &table)?;
+        assert_eq!(
+            rendered,
+            "# top comment\n[Srv]\nport = 8080 # listen port\nhost = \"a\"\nextra = true\n"
+        );
+        Ok(())
+    }
+}
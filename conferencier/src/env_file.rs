@@ -0,0 +1,105 @@
+//! Rendering of store sections into dotenv-style `KEY=value` files.
+
+use toml::{Table, Value};
+
+/// Renders the scalar and array entries of `table` as `PREFIX_KEY=value` lines.
+///
+/// Nested tables, and arrays containing tables, are skipped. Array elements are joined with
+/// `separator`, and values that would not survive shell-style parsing unquoted are quoted.
+pub fn render(table: &Table, prefix: &str, separator: &str) -> String {
+    let mut out = String::new();
+    for (key, value) in table {
+        let Some(raw) = render_value(value, separator) else {
+            continue;
+        };
+        out.push_str(&variable_name(prefix, key));
+        out.push('=');
+        out.push_str(&quote_if_needed(&raw));
+        out.push('\n');
+    }
+    out
+}
+
+/// Builds an upper-case variable name, replacing characters not allowed in identifiers.
+fn variable_name(prefix: &str, key: &str) -> String {
+    let joined = if prefix.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{prefix}_{key}")
+    };
+    joined
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch.to_ascii_uppercase() } else { '_' })
+        .collect()
+}
+
+/// Formats a value as plain text, returning `None` for values that have no dotenv form.
+fn render_value(value: &Value, separator: &str) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Integer(v) => Some(v.to_string()),
+        Value::Float(v) => Some(v.to_string()),
+        Value::Boolean(v) => Some(v.to_string()),
+        Value::Datetime(dt) => Some(dt.to_string()),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| render_value(item, separator))
+            .collect::<Option<Vec<_>>>()
+            .map(|parts| parts.join(separator)),
+        Value::Table(_) => None,
+    }
+}
+
+/// Wraps `raw` in double quotes when it contains whitespace or characters with dotenv meaning.
+fn quote_if_needed(raw: &str) -> String {
+    let needs_quotes = raw.is_empty()
+        || raw
+            .chars()
+            .any(|ch| ch.is_whitespace() || matches!(ch, '"' | '\'' | '#' | '\\' | '$' | '`'));
+    if !needs_quotes {
+        return raw.to_owned();
+    }
+
+    let mut quoted = String::with_capacity(raw.len() + 2);
+    quoted.push('"');
+    for ch in raw.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '$' => quoted.push_str("\\$"),
+            '`' => quoted.push_str("\\`"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            other => quoted.push(other),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_scalars_arrays_and_skips_tables() {
+        let table: Table = toml::from_str(
+            r#"
+port = 8080
+greeting = "hello world"
+hosts = ["a", "b"]
+enabled = true
+[nested]
+skip = 1
+"#,
+        )
+        .expect("valid TOML");
+
+        let rendered = render(&table, "app", ";");
+        assert!(rendered.contains("APP_PORT=8080\n"));
+        assert!(rendered.contains("APP_GREETING=\"hello world\"\n"));
+        assert!(rendered.contains("APP_HOSTS=a;b\n"));
+        assert!(rendered.contains("APP_ENABLED=true\n"));
+        assert!(!rendered.contains("NESTED"));
+    }
+}
@@ -1,3 +1,4 @@
+use std::ops::Range;
 use std::path::PathBuf;
 
 use thiserror::Error;
@@ -5,7 +6,10 @@ use thiserror::Error;
 pub type Result<T> = std::result::Result<T, ConferError>;
 
 /// Errors that can be raised while interacting with a [`Confer`](crate::store::Confer) store.
+///
+/// New variants may be added in minor releases, so matches need a wildcard arm.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ConferError {
     #[error("I/O error (path: {path:?}): {source}")]
     Io {
@@ -13,18 +17,27 @@ pub enum ConferError {
         #[source]
         source: std::io::Error,
     },
-    #[error("failed to parse TOML: {0}")]
-    Parse(#[from] toml::de::Error),
+    #[error("{}failed to parse TOML: {}", location_prefix(.location.as_deref()), .source.message())]
+    Parse {
+        location: Option<Box<ParseLocation>>,
+        #[source]
+        source: toml::de::Error,
+    },
     #[error("failed to serialize TOML: {0}")]
     Serialize(#[from] toml::ser::Error),
+    #[cfg(feature = "json")]
+    #[error("failed to process JSON: {0}")]
+    Json(#[from] serde_json::Error),
     #[error("missing key {section}.{key}")]
     MissingKey { section: String, key: String },
-    #[error("expected {expected} at {section}.{key} but found {found}")]
+    #[error("expected {expected} at {section}.{key} but found {found}{}", index_suffix(*.index))]
     TypeMismatch {
         section: String,
         key: String,
         expected: &'static str,
         found: &'static str,
+        /// Position of the offending element when the mismatch occurred inside an array.
+        index: Option<usize>,
     },
     #[error("invalid value at {section}.{key}: {message}")]
     ValueParse {
@@ -32,11 +45,135 @@ pub enum ConferError {
         key: String,
         message: String,
     },
+    #[error("unknown key {section}.{key}")]
+    UnknownKey { section: String, key: String },
+    #[error("section {section} already exists")]
+    SectionExists { section: String },
+    #[error("key {section}.{key} is claimed by both {owner} and {claimant}")]
+    SectionConflict {
+        section: String,
+        key: String,
+        owner: String,
+        claimant: String,
+    },
+}
+
+/// Position of a TOML parse failure within the original source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLocation {
+    /// One-based line number of the error.
+    pub line: usize,
+    /// One-based column (in characters) of the error within its line.
+    pub column: usize,
+    /// Byte range in the source reported by the parser.
+    pub span: Range<usize>,
+    /// Full text of the offending line, without its line terminator.
+    pub snippet: String,
+}
+
+impl ParseLocation {
+    /// Resolves the line, column, and snippet for a byte span within `source`.
+    fn from_span(source: &str, span: Range<usize>) -> Self {
+        let start = floor_char_boundary(source, span.start.min(source.len()));
+        let line_start = source[..start].rfind('\n').map_or(0, |pos| pos + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |pos| start + pos);
+        Self {
+            line: source[..start].matches('\n').count() + 1,
+            column: source[line_start..start].chars().count() + 1,
+            span,
+            snippet: source[line_start..line_end].trim_end_matches('\r').to_owned(),
+        }
+    }
+}
+
+/// Renders the ` (at index N)` suffix used by [`ConferError::TypeMismatch`].
+fn index_suffix(index: Option<usize>) -> String {
+    index.map(|index| format!(" (at index {index})")).unwrap_or_default()
+}
+
+/// Renders the `line:column: ` prefix used by [`ConferError::Parse`].
+fn location_prefix(location: Option<&ParseLocation>) -> String {
+    location
+        .map(|location| format!("{}:{}: ", location.line, location.column))
+        .unwrap_or_default()
+}
+
+/// Moves `index` back to the nearest UTF-8 character boundary in `source`.
+fn floor_char_boundary(source: &str, mut index: usize) -> usize {
+    while !source.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
 }
 
 impl ConferError {
+    /// Creates a [`ConferError::Parse`] with location details resolved against `input`.
+    pub(crate) fn parse_error(input: &str, source: toml::de::Error) -> Self {
+        let location = source
+            .span()
+            .map(|span| Box::new(ParseLocation::from_span(input, span)));
+        Self::Parse { location, source }
+    }
+
+    /// Returns where in the source a [`ConferError::Parse`] occurred, when known.
+    pub fn parse_location(&self) -> Option<&ParseLocation> {
+        match self {
+            Self::Parse { location, .. } => location.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`ConferError::MissingKey`].
+    pub fn is_missing_key(&self) -> bool {
+        matches!(self, Self::MissingKey { .. })
+    }
+
+    /// Returns `true` for [`ConferError::TypeMismatch`].
+    pub fn is_type_mismatch(&self) -> bool {
+        matches!(self, Self::TypeMismatch { .. })
+    }
+
+    /// Returns `true` for [`ConferError::ValueParse`].
+    pub fn is_value_parse(&self) -> bool {
+        matches!(self, Self::ValueParse { .. })
+    }
+
+    /// Returns `true` for [`ConferError::Io`].
+    pub fn is_io(&self) -> bool {
+        matches!(self, Self::Io { .. })
+    }
+
+    /// Returns the section an error refers to, for variants that name one.
+    pub fn section(&self) -> Option<&str> {
+        match self {
+            Self::MissingKey { section, .. }
+            | Self::TypeMismatch { section, .. }
+            | Self::ValueParse { section, .. }
+            | Self::UnknownKey { section, .. }
+            | Self::SectionExists { section }
+            | Self::SectionConflict { section, .. } => Some(section),
+            _ => None,
+        }
+    }
+
+    /// Returns the key an error refers to, for variants that name one.
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            Self::MissingKey { key, .. }
+            | Self::TypeMismatch { key, .. }
+            | Self::ValueParse { key, .. }
+            | Self::UnknownKey { key, .. }
+            | Self::SectionConflict { key, .. } => Some(key),
+            _ => None,
+        }
+    }
+
     /// Creates a [`ConferError::Io`] with the provided optional path context.
-    pub(crate) fn io_error(path: Option<PathBuf>, source: std::io::Error) -> Self {
+    ///
+    /// Prefer this over the `From<std::io::Error>` conversion whenever the path is known.
+    pub fn io_error(path: Option<PathBuf>, source: std::io::Error) -> Self {
         Self::Io { path, source }
     }
 
@@ -60,6 +197,37 @@ impl ConferError {
             key: key.into(),
             expected,
             found,
+            index: None,
+        }
+    }
+
+    /// Convenience constructor for [`ConferError::UnknownKey`].
+    pub fn unknown_key(section: impl Into<String>, key: impl Into<String>) -> Self {
+        Self::UnknownKey {
+            section: section.into(),
+            key: key.into(),
+        }
+    }
+
+    /// Convenience constructor for [`ConferError::SectionExists`].
+    pub fn section_exists(section: impl Into<String>) -> Self {
+        Self::SectionExists {
+            section: section.into(),
+        }
+    }
+
+    /// Convenience constructor for [`ConferError::SectionConflict`].
+    pub fn section_conflict(
+        section: impl Into<String>,
+        key: impl Into<String>,
+        owner: impl Into<String>,
+        claimant: impl Into<String>,
+    ) -> Self {
+        Self::SectionConflict {
+            section: section.into(),
+            key: key.into(),
+            owner: owner.into(),
+            claimant: claimant.into(),
         }
     }
 
@@ -86,6 +254,16 @@ impl ConferError {
     }
 }
 
+impl From<toml::de::Error> for ConferError {
+    /// Converts a TOML parse error without source text, so no location is attached.
+    fn from(source: toml::de::Error) -> Self {
+        Self::Parse {
+            location: None,
+            source,
+        }
+    }
+}
+
 impl From<std::io::Error> for ConferError {
     /// Converts a plain [`std::io::Error`] into [`ConferError::Io`] without path context.
     fn from(source: std::io::Error) -> Self {
@@ -0,0 +1,63 @@
+//! Advisory OS-level locks serializing processes that share one configuration file.
+
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+
+use crate::error::{ConferError, Result};
+
+/// Held advisory lock on the sidecar `<file>.lock` of a configuration file.
+///
+/// The configuration file itself is replaced by an atomic rename on every save, so locking it
+/// directly would leave waiters holding a lock on the old inode. The lock is released on drop.
+#[derive(Debug)]
+pub(crate) struct FileLock {
+    file: File,
+}
+
+impl FileLock {
+    /// Waits for an exclusive lock guarding `path`.
+    pub(crate) async fn exclusive(path: &Path) -> Result<Self> {
+        Self::acquire(path, true).await
+    }
+
+    /// Waits for a shared lock guarding `path`.
+    pub(crate) async fn shared(path: &Path) -> Result<Self> {
+        Self::acquire(path, false).await
+    }
+
+    /// Opens the sidecar lock file and blocks on a worker thread until the lock is granted.
+    async fn acquire(path: &Path, exclusive: bool) -> Result<Self> {
+        let lock_path = lock_path(path);
+        let task_path = lock_path.clone();
+        let acquired = tokio::task::spawn_blocking(move || {
+            let file = OpenOptions::new().create(true).truncate(false).write(true).open(&task_path)?;
+            if exclusive {
+                file.lock()?;
+            } else {
+                file.lock_shared()?;
+            }
+            Ok(file)
+        })
+        .await
+        .unwrap_or_else(|err| Err(std::io::Error::other(err)));
+        acquired
+            .map(|file| Self { file })
+            .map_err(|err| ConferError::io_error(Some(lock_path), err))
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/// Returns the sidecar lock path for `path`, e.g. `app.toml.lock`.
+fn lock_path(path: &Path) -> PathBuf {
+    let mut file_name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_else(|| "conferencier".into());
+    file_name.push(".lock");
+    path.with_file_name(file_name)
+}
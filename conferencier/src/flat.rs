@@ -0,0 +1,226 @@
+//! Dotted-path encoding used by [`Confer::flatten`] and [`Confer::unflatten`].
+//!
+//! A path joins table keys with `.`, starting with the section name. Keys made only of ASCII
+//! letters, digits, `_`, and `-` are written bare; any other key is written as a TOML basic
+//! string (`"a.b"`, with `\"` and `\\` escapes). Array elements append `[index]`, so
+//! `[[servers]] port = 443` flattens to `servers[0].port`.
+//!
+//! [`Confer::flatten`]: crate::Confer::flatten
+//! [`Confer::unflatten`]: crate::Confer::unflatten
+
+use std::fmt::Write as _;
+
+use toml::{Table, Value};
+
+use crate::error::{ConferError, Result};
+
+/// One step of a parsed path.
+#[derive(Debug, PartialEq)]
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+/// Lists every scalar in `table` with its encoded path, in table order.
+pub(crate) fn flatten_table(table: &Table) -> Vec<(String, Value)> {
+    let mut pairs = Vec::new();
+    for (key, value) in table {
+        flatten_value(encode_key(key), value, &mut pairs);
+    }
+    pairs
+}
+
+/// Appends the scalars below `value`, whose own path is `path`.
+fn flatten_value(path: String, value: &Value, pairs: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Table(table) => {
+            for (key, value) in table {
+                flatten_value(format!("{path}.{}", encode_key(key)), value, pairs);
+            }
+        }
+        Value::Array(items) => {
+            for (index, value) in items.iter().enumerate() {
+                flatten_value(format!("{path}[{index}]"), value, pairs);
+            }
+        }
+        leaf => pairs.push((path, leaf.clone())),
+    }
+}
+
+/// Rebuilds nested tables from `(path, value)` pairs.
+///
+/// Array indices must appear in ascending order without gaps, as [`flatten_table`] emits
+/// them. Malformed, duplicate, or conflicting paths fail with [`ConferError::ValueParse`].
+pub(crate) fn unflatten_pairs(pairs: impl IntoIterator<Item = (String, Value)>) -> Result<Table> {
+    let mut root = Table::new();
+    for (path, value) in pairs {
+        let steps = parse_path(&path)
+            .map_err(|message| ConferError::value_parse_owned(path.as_str(), "<path>", message))?;
+        let Some((Step::Key(section), rest)) = steps.split_first() else {
+            unreachable!("parse_path always starts with a key");
+        };
+        insert_at(&mut root, section, rest, value).map_err(|message| {
+            ConferError::value_parse_owned(section.as_str(), encode_steps(rest), message)
+        })?;
+    }
+    Ok(root)
+}
+
+/// Stores `value` at `key` followed by `rest` inside `table`.
+fn insert_at(table: &mut Table, key: &str, rest: &[Step], value: Value) -> std::result::Result<(), String> {
+    let Some(next) = rest.first() else {
+        if table.contains_key(key) {
+            return Err("path is assigned more than once".into());
+        }
+        table.insert(key.to_owned(), value);
+        return Ok(());
+    };
+    let child = table.entry(key).or_insert_with(|| empty_container(next));
+    insert_into(child, rest, value)
+}
+
+/// Stores `value` at `steps` inside the container `slot`.
+fn insert_into(slot: &mut Value, steps: &[Step], value: Value) -> std::result::Result<(), String> {
+    match (slot, &steps[0]) {
+        (Value::Table(table), Step::Key(key)) => insert_at(table, key, &steps[1..], value),
+        (Value::Array(items), Step::Index(index)) => {
+            let rest = &steps[1..];
+            if *index > items.len() {
+                return Err(format!("array index {index} skips elements"));
+            }
+            match rest.first() {
+                None if *index < items.len() => Err("path is assigned more than once".into()),
+                None => {
+                    items.push(value);
+                    Ok(())
+                }
+                Some(next) => {
+                    if *index == items.len() {
+                        items.push(empty_container(next));
+                    }
+                    insert_into(&mut items[*index], rest, value)
+                }
+            }
+        }
+        _ => Err("path mixes a table, an array, and a scalar at the same position".into()),
+    }
+}
+
+/// Creates the empty table or array that `step` descends into.
+fn empty_container(step: &Step) -> Value {
+    match step {
+        Step::Key(_) => Value::Table(Table::new()),
+        Step::Index(_) => Value::Array(Vec::new()),
+    }
+}
+
+/// Writes `key` bare when possible, otherwise as a quoted basic string.
+pub(crate) fn encode_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+    if bare {
+        return key.to_owned();
+    }
+    let mut quoted = String::with_capacity(key.len() + 2);
+    quoted.push('"');
+    for ch in key.chars() {
+        if matches!(ch, '"' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(ch);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Re-encodes the steps after a section, for error messages.
+fn encode_steps(steps: &[Step]) -> String {
+    let mut out = String::new();
+    for step in steps {
+        match step {
+            Step::Key(key) if out.is_empty() => out.push_str(&encode_key(key)),
+            Step::Key(key) => {
+                let _ = write!(out, ".{}", encode_key(key));
+            }
+            Step::Index(index) => {
+                let _ = write!(out, "[{index}]");
+            }
+        }
+    }
+    out
+}
+
+/// Splits an encoded path into keys and indices.
+fn parse_path(path: &str) -> std::result::Result<Vec<Step>, String> {
+    let mut chars = path.chars().peekable();
+    let mut steps = vec![Step::Key(parse_key(&mut chars)?)];
+    while let Some(ch) = chars.next() {
+        match ch {
+            '.' => steps.push(Step::Key(parse_key(&mut chars)?)),
+            '[' => {
+                let digits: String = chars.by_ref().take_while(|ch| *ch != ']').collect();
+                let index = digits
+                    .parse()
+                    .map_err(|_| format!("invalid array index `[{digits}]`"))?;
+                steps.push(Step::Index(index));
+            }
+            other => return Err(format!("unexpected `{other}` in path")),
+        }
+    }
+    Ok(steps)
+}
+
+/// Reads one bare or quoted key.
+fn parse_key(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> std::result::Result<String, String> {
+    let mut key = String::new();
+    if chars.next_if_eq(&'"').is_some() {
+        loop {
+            match chars.next() {
+                Some('"') => return Ok(key),
+                Some('\\') => key.push(chars.next().ok_or("unterminated quoted key")?),
+                Some(ch) => key.push(ch),
+                None => return Err("unterminated quoted key".into()),
+            }
+        }
+    }
+    while let Some(ch) = chars.next_if(|ch| !matches!(ch, '.' | '[')) {
+        key.push(ch);
+    }
+    if key.is_empty() {
+        return Err("empty key in path".into());
+    }
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flatten_and_unflatten_round_trip() -> Result<()> {
+        let table: Table = toml::from_str(
+            "[server]\nports = [80, 443]\n\"a.b\" = 1\n[server.tls]\non = true\n[[jobs]]\nname = \"x\"\n",
+        )
+        .expect("table");
+        let pairs = flatten_table(&table);
+        let paths: Vec<_> = pairs.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["jobs[0].name", "server.\"a.b\"", "server.ports[0]", "server.ports[1]", "server.tls.on"]
+        );
+        assert_eq!(unflatten_pairs(pairs)?, table);
+        Ok(())
+    }
+
+    #[test]
+    fn unflatten_rejects_bad_paths() {
+        let pair = |path: &str| (path.to_owned(), Value::Integer(1));
+        assert!(unflatten_pairs([pair("a..b")]).is_err());
+        assert!(unflatten_pairs([pair("a.b"), pair("a.b")]).is_err());
+        assert!(unflatten_pairs([pair("a.b"), pair("a.b.c")]).is_err());
+        assert!(unflatten_pairs([pair("a.list[1]")]).is_err());
+        assert!(unflatten_pairs([pair("a.\"open")]).is_err());
+    }
+}
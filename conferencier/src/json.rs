@@ -0,0 +1,173 @@
+//! Conversions between JSON documents and the TOML table used by the store.
+
+use serde::de::Error as _;
+use serde_json::{Map, Number, Value as JsonValue, json};
+use toml::{Table, Value};
+
+use crate::confer_module::{FieldSchema, SchemaType};
+use crate::error::{ConferError, Result};
+
+/// Parses a JSON document whose root is an object into a TOML table.
+pub fn parse_table(source: &str) -> Result<Table> {
+    match serde_json::from_str(source)? {
+        JsonValue::Object(map) => object_to_table(map),
+        _ => Err(json_error("JSON root must be an object")),
+    }
+}
+
+/// Serializes a TOML table as pretty-printed JSON, rendering datetimes as RFC 3339 strings.
+pub fn to_string(table: &Table) -> Result<String> {
+    let object = JsonValue::Object(table_to_object(table)?);
+    serde_json::to_string_pretty(&object).map_err(ConferError::from)
+}
+
+/// Builds a JSON Schema object for the section described by `fields`.
+pub fn schema_document(fields: &[FieldSchema]) -> JsonValue {
+    let properties: Map<String, JsonValue> = fields
+        .iter()
+        .map(|field| (field.key.to_owned(), type_schema(&field.value_type)))
+        .collect();
+    let required: Vec<JsonValue> = fields
+        .iter()
+        .filter(|field| field.required)
+        .map(|field| JsonValue::from(field.key))
+        .collect();
+
+    let mut document = Map::new();
+    document.insert("$schema".into(), "https://json-schema.org/draft/2020-12/schema".into());
+    if let Some(field) = fields.first() {
+        document.insert("title".into(), field.section.into());
+    }
+    document.insert("type".into(), "object".into());
+    document.insert("properties".into(), JsonValue::Object(properties));
+    document.insert("required".into(), JsonValue::Array(required));
+    JsonValue::Object(document)
+}
+
+/// Maps a key's TOML shape onto a JSON Schema type.
+fn type_schema(value_type: &SchemaType) -> JsonValue {
+    match value_type {
+        SchemaType::String => json!({ "type": "string" }),
+        SchemaType::Integer => json!({ "type": "integer" }),
+        SchemaType::Float => json!({ "type": "number" }),
+        SchemaType::Boolean => json!({ "type": "boolean" }),
+        SchemaType::Datetime => json!({ "type": "string", "format": "date-time" }),
+        SchemaType::ByteSize => json!({ "type": ["integer", "string"] }),
+        SchemaType::Table => json!({ "type": "object" }),
+        SchemaType::Custom => json!({}),
+        SchemaType::Array { items, len } => {
+            let mut schema = json!({ "type": "array", "items": type_schema(items) });
+            if let Some(len) = len {
+                schema["minItems"] = (*len).into();
+                schema["maxItems"] = (*len).into();
+            }
+            schema
+        }
+    }
+}
+
+/// Converts a JSON object into a TOML table, rejecting values TOML cannot represent.
+fn object_to_table(map: Map<String, JsonValue>) -> Result<Table> {
+    map.into_iter()
+        .map(|(key, value)| Ok((key, json_to_toml(value)?)))
+        .collect()
+}
+
+/// Converts a single JSON value into its TOML counterpart.
+fn json_to_toml(value: JsonValue) -> Result<Value> {
+    match value {
+        JsonValue::Null => Err(json_error("null values cannot be represented in TOML")),
+        JsonValue::Bool(v) => Ok(Value::Boolean(v)),
+        JsonValue::Number(number) => number_to_toml(&number),
+        JsonValue::String(s) => Ok(Value::String(s)),
+        JsonValue::Array(items) => items
+            .into_iter()
+            .map(json_to_toml)
+            .collect::<Result<Vec<_>>>()
+            .map(Value::Array),
+        JsonValue::Object(map) => object_to_table(map).map(Value::Table),
+    }
+}
+
+/// Maps JSON integers to TOML integers and other numbers to floats.
+///
+/// Integers outside the `i64` range are rejected rather than rounded to a float.
+fn number_to_toml(number: &Number) -> Result<Value> {
+    if let Some(v) = number.as_i64() {
+        Ok(Value::Integer(v))
+    } else if number.is_f64()
+        && let Some(v) = number.as_f64()
+    {
+        Ok(Value::Float(v))
+    } else {
+        Err(json_error(format!("integer {number} overflows a TOML integer (i64)")))
+    }
+}
+
+/// Converts a TOML table into a JSON object.
+fn table_to_object(table: &Table) -> Result<Map<String, JsonValue>> {
+    table
+        .iter()
+        .map(|(key, value)| Ok((key.clone(), toml_to_json(value)?)))
+        .collect()
+}
+
+/// Converts a single TOML value into its JSON counterpart.
+fn toml_to_json(value: &Value) -> Result<JsonValue> {
+    Ok(match value {
+        Value::String(s) => JsonValue::String(s.clone()),
+        Value::Integer(v) => JsonValue::from(*v),
+        Value::Float(v) => Number::from_f64(*v)
+            .map(JsonValue::Number)
+            .ok_or_else(|| json_error(format!("non-finite float {v} cannot be represented in JSON")))?,
+        Value::Boolean(v) => JsonValue::Bool(*v),
+        Value::Datetime(dt) => JsonValue::String(dt.to_string()),
+        Value::Array(items) => {
+            JsonValue::Array(items.iter().map(toml_to_json).collect::<Result<_>>()?)
+        }
+        Value::Table(table) => JsonValue::Object(table_to_object(table)?),
+    })
+}
+
+/// Builds a [`ConferError::Json`] carrying a custom message.
+fn json_error(message: impl std::fmt::Display) -> ConferError {
+    ConferError::Json(serde_json::Error::custom(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_objects_become_tables() -> Result<()> {
+        let table = parse_table(r#"{"App": {"port": 8080, "ratio": 0.5, "tags": ["a"]}}"#)?;
+        let app = table["App"].as_table().expect("section table");
+        assert_eq!(app["port"], Value::Integer(8080));
+        assert_eq!(app["ratio"], Value::Float(0.5));
+        assert_eq!(app["tags"], Value::Array(vec![Value::String("a".into())]));
+        Ok(())
+    }
+
+    #[test]
+    fn null_and_non_object_roots_are_rejected() {
+        assert!(matches!(parse_table(r#"{"App": {"x": null}}"#), Err(ConferError::Json(_))));
+        assert!(matches!(parse_table("[1, 2]"), Err(ConferError::Json(_))));
+    }
+
+    #[test]
+    fn integers_beyond_i64_are_rejected() {
+        let err = parse_table(r#"{"App": {"id": 9223372036854775808}}"#).unwrap_err();
+        assert!(err.to_string().contains("overflows a TOML integer"), "{err}");
+        let table = parse_table(r#"{"App": {"id": 9223372036854775807, "big": 1e300}}"#).unwrap();
+        assert_eq!(table["App"]["id"], Value::Integer(i64::MAX));
+        assert_eq!(table["App"]["big"], Value::Float(1e300));
+    }
+
+    #[test]
+    fn datetimes_serialize_as_strings() -> Result<()> {
+        let table: Table = toml::from_str("[Build]\ntime = 2024-01-01T00:00:00Z\n")?;
+        let json = to_string(&table)?;
+        assert!(json.contains(r#""time": "2024-01-01T00:00:00Z""#));
+        Ok(())
+    }
+}
@@ -1,16 +1,45 @@
 //! Async, TOML-backed configuration hub with an ergonomic derive macro.
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+#[cfg(feature = "blocking")]
+mod blocking;
+mod binary;
+mod builder;
+mod byte_size;
+mod change;
 pub mod confer_module;
+mod confer_value;
+mod diff;
+mod document;
+mod env_file;
 pub mod error;
+mod flat;
+#[cfg(feature = "file-lock")]
+mod file_lock;
+#[cfg(feature = "json")]
+mod json;
+mod save_options;
 mod store;
+mod trace;
+mod validate;
 mod value_conversion;
 mod section_guard;
 
-pub use crate::error::{ConferError, Result};
+pub use crate::error::{ConferError, ParseLocation, Result};
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub use crate::blocking::BlockingConfer;
+pub use crate::builder::ConferBuilder;
+pub use crate::byte_size::{ByteSize, ParseByteSizeError};
+pub use crate::change::{ChangeEvent, ChangeKind};
+pub use crate::confer_value::{FromConferValue, IntoConferValue};
+pub use crate::diff::ConfigChange;
+pub use crate::save_options::SaveOptions;
+pub use crate::section_guard::SectionGuard;
 pub use crate::store::{Confer, SharedConfer};
 /// Shared [`tokio::sync::RwLock`] wrapper used by derived modules.
 pub use crate::confer_module::SharedConferModule;
+pub use crate::confer_module::{FieldSchema, SchemaType};
 
 #[cfg(feature = "with-derive")]
 pub use conferencier_derive::ConferModule;
@@ -20,11 +49,324 @@ pub mod __private {
 	pub use async_trait::async_trait;
 	pub use std::sync::Arc;
 	pub use tokio::sync::RwLock;
+	pub use toml;
 
-	use crate::confer_module::SharedConferModule;
+	pub use crate::trace::ModuleTrace;
+
+	use crate::confer_module::{FieldSchema, SchemaType, SharedConferModule};
+	use crate::store::Confer;
+
+	/// Field-level load/save shared by `ConferModule` impls and `#[confer(flatten)]` parents.
+	#[async_trait]
+	pub trait ConferFields: Sized + Send + Sync {
+		/// Keys owned by the type, including those of flattened fields.
+		const KEY_SET: KeySet;
+		/// Keys written by `save_fields`: [`ConferFields::KEY_SET`] minus `#[confer(skip_save)]` keys.
+		const SAVED_KEYS: KeySet;
+		/// Compile-time check that no key is claimed twice across flatten boundaries.
+		const KEYS_DISJOINT: ();
+
+		/// Builds the value with every field at its default or `init` expression.
+		fn confer_defaults() -> Self;
+		/// Reads every persisted field from `section`.
+		async fn load_fields(&mut self, store: &Confer, section: &str) -> crate::Result<()>;
+		/// Swaps in the fields `load_fields` reads from `loaded`, leaving ignored and
+		/// `#[confer(skip_load)]` fields as they are.
+		fn take_loaded(&mut self, loaded: &mut Self);
+		/// Writes every persisted field into `section`.
+		async fn save_fields(&self, store: &Confer, section: &str) -> crate::Result<()>;
+		/// Writes every field `load_fields` can change into `section`, including
+		/// `#[confer(skip_save)]` ones, so `reload` can compare TOML values.
+		async fn render_fields(&self, store: &Confer, section: &str) -> crate::Result<()>;
+		/// Describes every persisted field as stored in `section`.
+		fn field_schema(section: &'static str) -> Vec<FieldSchema>;
+	}
+
+	/// Builds a [`FieldSchema`] from derive-generated metadata.
+	pub const fn field_schema(
+		section: &'static str,
+		key: &'static str,
+		type_name: &'static str,
+		required: bool,
+		default_repr: Option<&'static str>,
+		value_type: SchemaType,
+	) -> FieldSchema {
+		FieldSchema { section, key, type_name, required, default_repr, value_type }
+	}
+
+	/// Compile-time bound check for `#[confer(flatten)]` field types.
+	pub fn assert_flatten_field<T: ConferFields>() {}
+
+	/// Renders `fields` into a scratch store and returns its `section` table, as `reload` compares it.
+	pub async fn render_fields<T: ConferFields>(fields: &T, section: &str) -> crate::Result<toml::Table> {
+		let scratch = Confer::default();
+		fields.render_fields(&scratch, section).await?;
+		Ok(scratch.get_section_table(section).await.unwrap_or_default())
+	}
+
+	/// Returns `true` when any key of `keys` renders differently in `before` and `after`.
+	pub fn keys_changed(before: &toml::Table, after: &toml::Table, keys: &KeySet) -> bool {
+		keys.keys.iter().any(|key| before.get(*key) != after.get(*key))
+			|| keys.nested.iter().any(|nested| keys_changed(before, after, nested))
+	}
+
+	/// Tree of TOML keys owned by a module: its own keys plus those of flattened fields.
+	#[derive(Debug)]
+	pub struct KeySet {
+		pub keys: &'static [&'static str],
+		pub nested: &'static [&'static KeySet],
+	}
+
+	impl KeySet {
+		/// Returns `true` when `key` is owned anywhere in the tree.
+		pub const fn contains(&self, key: &str) -> bool {
+			let mut index = 0;
+			while index < self.keys.len() {
+				if str_eq(self.keys[index], key) {
+					return true;
+				}
+				index += 1;
+			}
+			let mut index = 0;
+			while index < self.nested.len() {
+				if self.nested[index].contains(key) {
+					return true;
+				}
+				index += 1;
+			}
+			false
+		}
+
+		/// Returns the first key shared between the own keys and a nested set, or between two
+		/// nested sets. Nested sets check their own children.
+		pub const fn first_shared_key(&self) -> Option<&'static str> {
+			let own = KeySet { keys: self.keys, nested: &[] };
+			let mut first = 0;
+			while first < self.nested.len() {
+				if let Some(key) = own.shared_key(self.nested[first]) {
+					return Some(key);
+				}
+				let mut second = first + 1;
+				while second < self.nested.len() {
+					if let Some(key) = self.nested[first].shared_key(self.nested[second]) {
+						return Some(key);
+					}
+					second += 1;
+				}
+				first += 1;
+			}
+			None
+		}
+
+		/// Returns the first key of `self` that is also owned by `other`.
+		const fn shared_key(&self, other: &KeySet) -> Option<&'static str> {
+			let mut index = 0;
+			while index < self.keys.len() {
+				if other.contains(self.keys[index]) {
+					return Some(self.keys[index]);
+				}
+				index += 1;
+			}
+			let mut index = 0;
+			while index < self.nested.len() {
+				if let Some(key) = self.nested[index].shared_key(other) {
+					return Some(key);
+				}
+				index += 1;
+			}
+			None
+		}
+
+		/// Counts every key in the tree.
+		pub const fn len(&self) -> usize {
+			let mut len = self.keys.len();
+			let mut index = 0;
+			while index < self.nested.len() {
+				len += self.nested[index].len();
+				index += 1;
+			}
+			len
+		}
+
+		/// Returns `true` when the tree owns no key.
+		pub const fn is_empty(&self) -> bool {
+			self.len() == 0
+		}
+
+		/// Copies every key of the tree into `out` starting at `at`, returning the next free index.
+		const fn copy_into(&self, out: &mut [&'static str], mut at: usize) -> usize {
+			let mut index = 0;
+			while index < self.keys.len() {
+				out[at] = self.keys[index];
+				at += 1;
+				index += 1;
+			}
+			let mut index = 0;
+			while index < self.nested.len() {
+				at = self.nested[index].copy_into(out, at);
+				index += 1;
+			}
+			at
+		}
+	}
+
+	/// Panic message assembled at compile time, for `const` checks naming a key not known to the
+	/// derive macro. Text beyond 256 bytes is cut at a character boundary.
+	pub struct ConstMessage {
+		bytes: [u8; 256],
+		len: usize,
+	}
+
+	impl ConstMessage {
+		/// Concatenates `parts`.
+		pub const fn new(parts: &[&str]) -> Self {
+			let mut message = ConstMessage { bytes: [0; 256], len: 0 };
+			let mut part = 0;
+			while part < parts.len() {
+				let bytes = parts[part].as_bytes();
+				let mut index = 0;
+				while index < bytes.len() {
+					let width = match bytes[index] {
+						0x00..=0x7F => 1,
+						0xF0.. => 4,
+						0xE0.. => 3,
+						_ => 2,
+					};
+					if message.len + width > message.bytes.len() {
+						return message;
+					}
+					let end = index + width;
+					while index < end {
+						message.bytes[message.len] = bytes[index];
+						message.len += 1;
+						index += 1;
+					}
+				}
+				part += 1;
+			}
+			message
+		}
+
+		/// Returns the assembled text.
+		pub const fn as_str(&self) -> &str {
+			match std::str::from_utf8(self.bytes.split_at(self.len).0) {
+				Ok(text) => text,
+				Err(_) => "",
+			}
+		}
+	}
+
+	/// Resolves a string byte-size default at compile time, or `None` when it is invalid.
+	pub const fn byte_size(raw: &str) -> Option<u64> {
+		match crate::byte_size::parse_bytes(raw) {
+			Ok(bytes) => Some(bytes),
+			Err(_) => None,
+		}
+	}
+
+	/// `const` string equality.
+	const fn str_eq(left: &str, right: &str) -> bool {
+		let (left, right) = (left.as_bytes(), right.as_bytes());
+		if left.len() != right.len() {
+			return false;
+		}
+		let mut index = 0;
+		while index < left.len() {
+			if left[index] != right[index] {
+				return false;
+			}
+			index += 1;
+		}
+		true
+	}
+
+	/// Flattens `set` followed by `extra` into the array backing a derived `owned_keys` constant.
+	///
+	/// `N` must equal `set.len()` plus the length of `extra`; evaluation fails otherwise.
+	pub const fn owned_keys<const N: usize>(set: &KeySet, extra: Option<&'static str>) -> [&'static str; N] {
+		let mut keys = [""; N];
+		let at = set.copy_into(&mut keys, 0);
+		if let Some(key) = extra {
+			keys[at] = key;
+		}
+		keys
+	}
 
 	/// Wraps `value` in the shared module type used by the derive implementation.
 	pub fn new_shared_module<T>(value: T) -> SharedConferModule<T> {
 		Arc::new(RwLock::new(value))
 	}
+
+	/// Records the current hash of `section` as the state `module` was loaded from.
+	pub async fn record_load_snapshot<T: Send + Sync + 'static>(
+		store: &Confer,
+		module: &SharedConferModule<T>,
+		section: &str,
+	) {
+		store.record_load_snapshot(module.clone(), section).await;
+	}
+
+	/// Records `section` of `snapshot`, the detached copy `module` was just loaded from.
+	pub async fn record_snapshot_of<T: Send + Sync + 'static>(
+		store: &Confer,
+		module: &SharedConferModule<T>,
+		section: &str,
+		snapshot: &Confer,
+	) {
+		store.record_snapshot_of(module.clone(), section, snapshot).await;
+	}
+
+	/// Writes `rendered` into `section` only if it is unchanged since `module` last loaded or
+	/// saved it, checking and writing under one store write lock.
+	pub async fn save_if_unchanged<T: Send + Sync + 'static>(
+		store: &Confer,
+		module: &SharedConferModule<T>,
+		section: &str,
+		rendered: toml::Table,
+		owned: impl Fn(&str) -> bool,
+		known: Option<&[&str]>,
+	) -> crate::Result<bool> {
+		store.commit_if_unchanged(module.clone(), section, rendered, owned, known).await
+	}
+
+	/// Writes only the keys of `rendered` that changed since `module` last loaded or saved `section`.
+	pub async fn save_changes<T: Send + Sync + 'static>(
+		store: &Confer,
+		module: &SharedConferModule<T>,
+		section: &str,
+		rendered: toml::Table,
+		owned: impl Fn(&str) -> bool,
+	) -> crate::Result<()> {
+		let baseline = store.load_snapshot_table(module.clone(), section).unwrap_or_default();
+		store.apply_section_delta(section, rendered, &baseline, owned).await
+	}
+
+	/// Brings `section` up to schema `version`, invoking `migrate` for older stored versions.
+	pub async fn apply_schema_version<F>(
+		store: &Confer,
+		section: &str,
+		version_key: &str,
+		version: u32,
+		migrate: Option<F>,
+	) -> crate::Result<()>
+	where
+		F: FnOnce(&mut toml::Table, u32) -> crate::Result<()>,
+	{
+		store.apply_schema_version(section, version_key, version, migrate).await
+	}
+
+	/// Fails on the first key of `section` that `known_keys` does not list, as `load_strict` requires.
+	pub async fn reject_unknown_keys(store: &Confer, section: &str, known_keys: &[&str]) -> crate::Result<()> {
+		store.reject_unknown_keys(section, known_keys).await
+	}
+
+	/// Reports that the deprecated key `section.key` is present in the loaded configuration.
+	pub fn warn_deprecated(section: &str, key: &str, message: &str) {
+		crate::trace::deprecated_key(section, key, message);
+	}
+
+	/// Claims `keys` of `section` for module `type_name` when the store detects section conflicts.
+	pub fn claim_module_section(store: &Confer, type_name: &str, section: &str, keys: &[&str]) -> crate::Result<()> {
+		store.claim_module_section(type_name, section, keys)
+	}
 }
@@ -0,0 +1,181 @@
+//! Output tweaks applied by [`Confer::save_str_with`](crate::Confer::save_str_with) and
+//! [`Confer::save_file_with`](crate::Confer::save_file_with).
+
+use std::io::ErrorKind;
+use std::time::Duration;
+
+use toml::{Table, Value};
+
+use crate::error::Result;
+
+/// Formatting and persistence options for [`Confer::save_str_with`](crate::Confer::save_str_with)
+/// and [`Confer::save_file_with`](crate::Confer::save_file_with).
+///
+/// ```
+/// let options = conferencier::SaveOptions::default().float_precision(6);
+/// assert_eq!(options.float_precision, Some(6));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SaveOptions {
+    /// Rounds every float to this many significant digits (`0` acts as `1`); `None` writes
+    /// floats unchanged.
+    pub float_precision: Option<usize>,
+    /// How often a file write or rename failing with a transient error (interrupted, busy,
+    /// would block, timed out) is retried. Other errors fail immediately. Defaults to `0`.
+    pub retries: u32,
+    /// Delay before the first retry, doubled after each further attempt. Defaults to 50 ms.
+    pub backoff: Duration,
+    /// Writes arrays holding more than [`SaveOptions::multiline_threshold`] elements with one
+    /// indented element per line. Arrays inside inline tables stay on one line. Defaults to
+    /// `false`.
+    pub multiline_arrays: bool,
+    /// Element count above which [`SaveOptions::multiline_arrays`] splits an array. Defaults
+    /// to `4`.
+    pub multiline_threshold: usize,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self {
+            float_precision: None,
+            retries: 0,
+            backoff: Duration::from_millis(50),
+            multiline_arrays: false,
+            multiline_threshold: 4,
+        }
+    }
+}
+
+impl SaveOptions {
+    /// Sets [`SaveOptions::float_precision`].
+    pub fn float_precision(mut self, digits: usize) -> Self {
+        self.float_precision = Some(digits);
+        self
+    }
+
+    /// Sets [`SaveOptions::retries`].
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets [`SaveOptions::backoff`].
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Sets [`SaveOptions::multiline_arrays`].
+    pub fn multiline_arrays(mut self, enabled: bool) -> Self {
+        self.multiline_arrays = enabled;
+        self
+    }
+
+    /// Sets [`SaveOptions::multiline_threshold`].
+    pub fn multiline_threshold(mut self, elements: usize) -> Self {
+        self.multiline_threshold = elements;
+        self
+    }
+
+    /// Runs `op`, retrying transient failures up to [`SaveOptions::retries`] times with
+    /// exponential backoff. Returns the last error once retries are exhausted.
+    pub(crate) async fn retry<T, F, Fut>(&self, mut op: F) -> std::io::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = std::io::Result<T>>,
+    {
+        let mut delay = self.backoff;
+        let mut attempt = 0;
+        loop {
+            match op().await {
+                Err(err) if attempt < self.retries && is_transient(&err) => {
+                    attempt += 1;
+                    tokio::time::sleep(delay).await;
+                    delay = delay.saturating_mul(2);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Rewrites the values in `table` according to these options.
+    pub(crate) fn apply(&self, table: &mut Table) {
+        if let Some(digits) = self.float_precision {
+            table.iter_mut().for_each(|(_, value)| round_floats(value, digits));
+        }
+    }
+
+    /// Reformats the serialized `output` according to these options.
+    pub(crate) fn format(&self, output: String) -> Result<String> {
+        if self.multiline_arrays {
+            crate::document::expand_arrays(&output, self.multiline_threshold)
+        } else {
+            Ok(output)
+        }
+    }
+}
+
+/// Returns `true` for I/O errors that may succeed when the operation is repeated.
+fn is_transient(err: &std::io::Error) -> bool {
+    matches!(
+        err.kind(),
+        ErrorKind::Interrupted | ErrorKind::ResourceBusy | ErrorKind::WouldBlock | ErrorKind::TimedOut
+    )
+}
+
+/// Rounds the finite floats in `value`, including nested ones, to `digits` significant digits.
+fn round_floats(value: &mut Value, digits: usize) {
+    match value {
+        Value::Float(float) if float.is_finite() => {
+            let formatted = format!("{:.*e}", digits.saturating_sub(1), *float);
+            *float = formatted.parse().unwrap_or(*float);
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| round_floats(item, digits)),
+        Value::Table(table) => table.iter_mut().for_each(|(_, item)| round_floats(item, digits)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn retry_repeats_transient_errors_only() {
+        let options = SaveOptions::default().retries(2).backoff(Duration::ZERO);
+        let mut calls = 0;
+        let result = options
+            .retry(|| {
+                calls += 1;
+                let outcome = if calls < 3 {
+                    Err(std::io::Error::from(ErrorKind::Interrupted))
+                } else {
+                    Ok(calls)
+                };
+                async move { outcome }
+            })
+            .await;
+        assert_eq!(result.expect("third attempt succeeds"), 3);
+
+        let mut calls = 0;
+        let result: std::io::Result<()> = options
+            .retry(|| {
+                calls += 1;
+                async { Err(std::io::Error::from(ErrorKind::ResourceBusy)) }
+            })
+            .await;
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResourceBusy);
+        assert_eq!(calls, 3);
+
+        let mut calls = 0;
+        let result: std::io::Result<()> = options
+            .retry(|| {
+                calls += 1;
+                async { Err(std::io::Error::from(ErrorKind::PermissionDenied)) }
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+}
@@ -2,13 +2,30 @@
 
 use std::collections::BTreeSet;
 
-#[allow(dead_code)]
+use crate::error::Result;
+use crate::store::Confer;
+
 /// Tracks the set of keys owned by a module within a TOML section.
+///
+/// Derived modules use it to drop stale keys on `save`; manual [`ConferModule`]
+/// implementations can do the same:
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> conferencier::Result<()> {
+/// let store = conferencier::Confer::from_string("[App]\nport = 1\nstale = true\n")?;
+/// conferencier::SectionGuard::new(["port"]).reconcile(&store, "App").await?;
+/// assert_eq!(store.list_keys("App").await?, ["port"]);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`ConferModule`]: crate::confer_module::ConferModule
+#[derive(Debug, Clone, Default)]
 pub struct SectionGuard {
     known_keys: BTreeSet<String>,
 }
 
-#[allow(dead_code)]
 impl SectionGuard {
     /// Creates a guard from an iterator of key names.
     pub fn new<I>(keys: I) -> Self
@@ -24,4 +41,44 @@ impl SectionGuard {
     pub fn known_keys(&self) -> &BTreeSet<String> {
         &self.known_keys
     }
+
+    /// Removes every key of `section` in `store` that the guard does not know, via
+    /// [`Confer::prune_section_to`].
+    ///
+    /// A missing section is left absent. Keys that differ from a known key only in ASCII case
+    /// are kept when the store was built with [`ConferBuilder::case_insensitive`].
+    ///
+    /// [`ConferBuilder::case_insensitive`]: crate::ConferBuilder::case_insensitive
+    pub async fn reconcile(&self, store: &Confer, section: &str) -> Result<()> {
+        let known: Vec<&str> = self.known_keys.iter().map(String::as_str).collect();
+        store.prune_section_to(section, &known).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reconcile_removes_unknown_keys_only() -> Result<()> {
+        let store = Confer::from_string("[App]\nport = 1\nname = \"demo\"\nstale = true\n[Other]\nkeep = 1\n")?;
+        SectionGuard::new(["port", "name"]).reconcile(&store, "App").await?;
+
+        assert_eq!(store.list_keys("App").await?, ["name", "port"]);
+        assert_eq!(store.list_keys("Other").await?, ["keep"]);
+
+        SectionGuard::new(["port"]).reconcile(&store, "Missing").await?;
+        assert!(!store.section_exists("Missing").await);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reconcile_matches_keys_case_insensitively_when_enabled() -> Result<()> {
+        let store = Confer::builder()
+            .case_insensitive(true)
+            .build_from_str("[App]\nPort = 1\nstale = true\n")?;
+        SectionGuard::new(["port"]).reconcile(&store, "app").await?;
+        assert_eq!(store.list_keys("App").await?, ["Port"]);
+        Ok(())
+    }
 }
@@ -1,19 +1,128 @@
+use std::any::Any;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::io::ErrorKind;
+use std::ops::{Deref, DerefMut};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, SystemTime};
 
 use tokio::fs;
-use tokio::sync::RwLock;
-use toml::value::Datetime;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::{RwLock, RwLockWriteGuard};
+use toml::value::{Date, Datetime, Time};
 use toml::{Table, Value};
+use toml_edit::DocumentMut;
 
+use crate::builder::{ConferBuilder, ConferOptions, DEFAULT_MAX_DEPTH};
+use crate::byte_size::ByteSize;
+use crate::change::{ChangeEvent, ChangeKind, ChangeNotifier};
+use crate::confer_value::{FromConferValue, IntoConferValue};
+use crate::diff::ConfigChange;
 use crate::error::{ConferError, Result};
+use crate::save_options::SaveOptions;
+use crate::trace::StoreTrace;
 use crate::value_conversion;
 
+/// Placeholder written in place of redacted values.
+const REDACTED: &str = "***";
+
 /// In-memory TOML-backed configuration store guarded by an asynchronous `RwLock`.
 #[derive(Debug, Default)]
 pub struct Confer {
     table: RwLock<Table>,
+    options: ConferOptions,
+    document: Mutex<Option<DocumentMut>>,
+    load_snapshots: Mutex<HashMap<usize, LoadSnapshot>>,
+    section_claims: Mutex<HashMap<String, Vec<SectionClaim>>>,
+    changes: ChangeNotifier,
+    shared_values: Mutex<HashMap<String, HashMap<String, Arc<Value>>>>,
+    shared_sections: Mutex<HashMap<String, Arc<Table>>>,
+    file_stamp: Mutex<Option<FileStamp>>,
+    layer_sources: LayerSources,
+    #[cfg(feature = "file-lock")]
+    locked_baseline: Mutex<Option<(PathBuf, Table)>>,
+    #[cfg(feature = "arc-swap")]
+    published: arc_swap::ArcSwap<Table>,
+}
+
+/// Write access to the store's table; with the `arc-swap` feature, a table marked through
+/// [`TableWriteGuard::mark_modified`] is republished for [`Confer::read_snapshot`] when the
+/// guard is dropped.
+struct TableWriteGuard<'a> {
+    guard: RwLockWriteGuard<'a, Table>,
+    #[cfg(feature = "arc-swap")]
+    modified: bool,
+    #[cfg(feature = "arc-swap")]
+    published: &'a arc_swap::ArcSwap<Table>,
+}
+
+impl Deref for TableWriteGuard<'_> {
+    type Target = Table;
+
+    fn deref(&self) -> &Table {
+        &self.guard
+    }
+}
+
+impl DerefMut for TableWriteGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Table {
+        &mut self.guard
+    }
+}
+
+impl TableWriteGuard<'_> {
+    /// Records a completed mutation. Writers call this only once a change has been applied, so
+    /// failed writes leave the published table alone.
+    fn mark_modified(&mut self) {
+        #[cfg(feature = "arc-swap")]
+        {
+            self.modified = true;
+        }
+    }
+}
+
+#[cfg(feature = "arc-swap")]
+impl Drop for TableWriteGuard<'_> {
+    fn drop(&mut self) {
+        if self.modified {
+            self.published.store(Arc::new(self.guard.clone()));
+        }
+    }
+}
+
+/// Path and modification time of the file the store was last loaded from.
+#[derive(Debug, PartialEq)]
+pub(crate) struct FileStamp {
+    path: PathBuf,
+    modified: SystemTime,
+}
+
+/// Layer files a store was built from by [`Confer::from_layers`], and the index of the last
+/// layer that set each `section.key`.
+#[derive(Debug, Default)]
+struct LayerSources {
+    paths: Vec<PathBuf>,
+    keys: HashMap<String, HashMap<String, usize>>,
+}
+
+/// Keys of a section claimed by one module type.
+#[derive(Debug)]
+struct SectionClaim {
+    owner: String,
+    keys: Vec<String>,
+}
+
+/// Section contents observed by a module when it was last loaded or saved.
+#[derive(Debug)]
+struct LoadSnapshot {
+    module: Weak<dyn Any + Send + Sync>,
+    section: String,
+    hash: Option<u64>,
+    table: Option<Table>,
 }
 
 /// Shared reference-counted handle to a [`Confer`] instance.
@@ -22,520 +131,3944 @@ pub type SharedConfer = Arc<Confer>;
 impl Confer {
     /// Creates an empty configuration store wrapped in [`SharedConfer`].
     pub fn new() -> SharedConfer {
-        Arc::new(Self::default())
+        ConferBuilder::new().build()
+    }
+
+    /// Subscribes to notifications from [`Confer::set_value`], [`Confer::remove_key`], and
+    /// [`Confer::remove_section`], including the typed setters built on them.
+    ///
+    /// The channel is created on first subscription. Slow receivers observe
+    /// [`broadcast::error::RecvError::Lagged`](tokio::sync::broadcast::error::RecvError::Lagged)
+    /// once more than 256 events are pending.
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<ChangeEvent> {
+        self.changes.subscribe()
+    }
+
+    /// Returns a [`ConferBuilder`] for constructing a store with custom options.
+    pub fn builder() -> ConferBuilder {
+        ConferBuilder::new()
+    }
+
+    /// Creates an empty store using `options`.
+    pub(crate) fn with_options(options: ConferOptions) -> Self {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+
+    /// Wraps an already parsed table in a fresh store.
+    fn from_table(table: Table) -> Self {
+        Self {
+            #[cfg(feature = "arc-swap")]
+            published: arc_swap::ArcSwap::from_pointee(table.clone()),
+            table: RwLock::new(table),
+            ..Self::default()
+        }
     }
 
     /// Builds a store from a TOML string, returning a shared handle on success.
+    ///
+    /// A leading UTF-8 byte order mark is ignored and CRLF line endings are accepted.
     pub fn from_string(source: &str) -> Result<SharedConfer> {
-        let table = Self::parse_table(source)?;
-        Ok(Arc::new(Self {
-            table: RwLock::new(table),
-        }))
+        ConferBuilder::new().build_from_str(source)
+    }
+
+    /// Builds a store from TOML held in a byte buffer without copying it into a [`String`].
+    ///
+    /// Invalid UTF-8 fails with a [`ConferError::Io`] of kind [`ErrorKind::InvalidData`].
+    pub fn from_slice(bytes: &[u8]) -> Result<SharedConfer> {
+        ConferBuilder::new().build_from_slice(bytes)
+    }
+
+    /// Reads TOML from an async stream until EOF and constructs the shared store.
+    ///
+    /// Useful for sources without a filesystem path, such as sockets or embedded resources.
+    pub async fn from_reader(mut reader: impl AsyncRead + Unpin) -> Result<SharedConfer> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|err| ConferError::io_error(None, err))?;
+        Self::from_slice(&bytes)
+    }
+
+    /// Reads TOML from a blocking [`std::io::Read`] until EOF and constructs the shared store.
+    pub fn from_std_reader(mut reader: impl std::io::Read) -> Result<SharedConfer> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(|err| ConferError::io_error(None, err))?;
+        Self::from_slice(&bytes)
     }
 
     /// Synchronously reads a TOML file from disk and constructs the shared store.
     pub fn from_file(path: impl AsRef<Path>) -> Result<SharedConfer> {
-        let path_buf = path.as_ref().to_path_buf();
-        let contents = std::fs::read_to_string(&path_buf)
-            .map_err(|err| ConferError::io_error(Some(path_buf.clone()), err))?;
-        let table = Self::parse_table(&contents)?;
-        Ok(Arc::new(Self {
-            table: RwLock::new(table),
-        }))
+        ConferBuilder::new().build_from_file(path)
+    }
+
+    /// Memory-maps a TOML file and constructs the shared store, see
+    /// [`ConferBuilder::build_from_file_mmap`].
+    #[cfg(feature = "mmap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+    pub fn from_file_mmap(path: impl AsRef<Path>) -> Result<SharedConfer> {
+        ConferBuilder::new().build_from_file_mmap(path)
     }
 
     /// Asynchronously reads a TOML file from disk and constructs the shared store.
     pub async fn from_file_async(path: impl AsRef<Path> + Send + Sync) -> Result<SharedConfer> {
         let path_buf = path.as_ref().to_path_buf();
+        let stamp = FileStamp::read(&path_buf).await?;
         let contents = fs::read_to_string(&path_buf)
             .await
             .map_err(|err| ConferError::io_error(Some(path_buf.clone()), err))?;
-        let table = Self::parse_table(&contents)?;
-        Ok(Arc::new(Self {
-            table: RwLock::new(table),
-        }))
+        ConferBuilder::new().build_from_file_contents(&contents, stamp)
+    }
+
+    /// Synchronously builds a store from layered TOML files, such as `defaults.toml`,
+    /// `env.toml`, and `local.toml`, where later layers override earlier ones.
+    ///
+    /// Layers are deep-merged like [`Confer::patch`]: tables recurse, while scalars and arrays
+    /// replace what earlier layers set. Missing files are skipped; other I/O errors and parse
+    /// failures abort the load.
+    ///
+    /// ```no_run
+    /// # fn main() -> conferencier::Result<()> {
+    /// let store = conferencier::Confer::from_layers(&["defaults.toml", "env.toml", "local.toml"])?;
+    /// # let _ = store;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_layers(paths: &[impl AsRef<Path>]) -> Result<SharedConfer> {
+        let mut layers = Vec::with_capacity(paths.len());
+        for path in paths {
+            let path = path.as_ref();
+            match std::fs::read_to_string(path) {
+                Ok(contents) => layers.push((path, contents)),
+                Err(err) if err.kind() == ErrorKind::NotFound => {}
+                Err(err) => return Err(ConferError::io_error(Some(path.to_path_buf()), err)),
+            }
+        }
+        Self::from_layer_sources(layers)
+    }
+
+    /// Asynchronously builds a store from layered TOML files, see [`Confer::from_layers`].
+    pub async fn from_layers_async(paths: &[impl AsRef<Path> + Send + Sync]) -> Result<SharedConfer> {
+        let mut layers = Vec::with_capacity(paths.len());
+        for path in paths {
+            let path = path.as_ref();
+            match fs::read_to_string(path).await {
+                Ok(contents) => layers.push((path, contents)),
+                Err(err) if err.kind() == ErrorKind::NotFound => {}
+                Err(err) => return Err(ConferError::io_error(Some(path.to_path_buf()), err)),
+            }
+        }
+        Self::from_layer_sources(layers)
+    }
+
+    /// Parses each layer's source in order and deep-merges it over the previous layers,
+    /// recording which layer last set each `section.key`.
+    fn from_layer_sources(layers: Vec<(&Path, String)>) -> Result<SharedConfer> {
+        let mut table = Table::new();
+        let mut sources = LayerSources::default();
+        for (index, (path, contents)) in layers.into_iter().enumerate() {
+            sources.paths.push(path.to_path_buf());
+            for (section, value) in Self::parse_table(&contents, DEFAULT_MAX_DEPTH)? {
+                if let Value::Table(inner) = &value {
+                    let keys = sources.keys.entry(section.clone()).or_default();
+                    keys.extend(inner.keys().map(|key| (key.clone(), index)));
+                }
+                merge_value(&mut table, section, value);
+            }
+        }
+        let mut store = Self::from_table(table);
+        store.layer_sources = sources;
+        Ok(Arc::new(store))
+    }
+
+    /// Returns the layer file that last set `section.key` when the store was built by
+    /// [`Confer::from_layers`] or [`Confer::from_layers_async`].
+    ///
+    /// For a nested table this is the last layer that set any key below it. Provenance is
+    /// recorded at construction only; later writes and loads do not update it.
+    pub fn value_source(&self, section: &str, key: &str) -> Option<&Path> {
+        let index = *self.layer_sources.keys.get(section)?.get(key)?;
+        Some(&self.layer_sources.paths[index])
+    }
+
+    /// Rebuilds a store from dotted `(path, value)` pairs such as those returned by
+    /// [`Confer::flatten`].
+    ///
+    /// See [`Confer::flatten`] for the path encoding. Array indices must appear in ascending
+    /// order without gaps; malformed, duplicate, or conflicting paths fail with
+    /// [`ConferError::ValueParse`].
+    pub fn unflatten(pairs: impl IntoIterator<Item = (String, Value)>) -> Result<SharedConfer> {
+        let table = crate::flat::unflatten_pairs(pairs)?;
+        check_depth(&table, DEFAULT_MAX_DEPTH)?;
+        Ok(Arc::new(Self::from_table(table)))
+    }
+
+    /// Synchronously reads a JSON file from disk and constructs the shared store.
+    ///
+    /// The root object maps to sections; datetimes are expected as RFC 3339 strings and are
+    /// picked up by [`Confer::get_datetime`]'s string fallback.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn from_json_file(path: impl AsRef<Path>) -> Result<SharedConfer> {
+        let path_buf = path.as_ref().to_path_buf();
+        let contents = std::fs::read_to_string(&path_buf)
+            .map_err(|err| ConferError::io_error(Some(path_buf.clone()), err))?;
+        let table = crate::json::parse_table(&contents)?;
+        check_depth(&table, DEFAULT_MAX_DEPTH)?;
+        Ok(Arc::new(Self::from_table(table)))
+    }
+
+    /// Asynchronously reads a TOML file like [`Confer::from_file_async`], giving up after `timeout`.
+    ///
+    /// Only the read is bounded; parsing happens afterwards, and the file is remembered for
+    /// [`Confer::reload_if_changed`]. On expiry a [`ConferError::Io`] with
+    /// [`ErrorKind::TimedOut`] and the file path is returned.
+    pub async fn from_file_async_timeout(
+        path: impl AsRef<Path> + Send + Sync,
+        timeout: Duration,
+    ) -> Result<SharedConfer> {
+        let path_buf = path.as_ref().to_path_buf();
+        let read = async {
+            let stamp = FileStamp::read(&path_buf).await?;
+            let contents = fs::read_to_string(&path_buf)
+                .await
+                .map_err(|err| ConferError::io_error(Some(path_buf.clone()), err))?;
+            Ok::<_, ConferError>((contents, stamp))
+        };
+        let (contents, stamp) = match tokio::time::timeout(timeout, read).await {
+            Ok(read) => read?,
+            Err(_) => {
+                let err = std::io::Error::new(
+                    ErrorKind::TimedOut,
+                    format!("read timed out after {timeout:?}"),
+                );
+                return Err(ConferError::io_error(Some(path_buf), err));
+            }
+        };
+        ConferBuilder::new().build_from_file_contents(&contents, stamp)
     }
 
     /// Replaces the in-memory table with the contents of the provided TOML string.
     pub async fn load_str(&self, source: &str) -> Result<()> {
-        let table = Self::parse_table(source)?;
-        let mut guard = self.table.write().await;
-        *guard = table;
+        let trace = StoreTrace::start("load");
+        trace
+            .instrument(async {
+                let table = Self::parse_table(source, self.options.max_depth)?;
+                trace.sections(table.len());
+                let mut guard = self.write_table().await;
+                *guard = table;
+                guard.mark_modified();
+                self.remember_document(source);
+                Ok(())
+            })
+            .await
+    }
+
+    /// Deep-merges the TOML `fragment` into the store: tables recurse, while scalars and arrays
+    /// overwrite what is stored. Keys absent from the fragment are left untouched.
+    ///
+    /// The fragment is parsed before the store is locked, so invalid input changes nothing. Under
+    /// [`ConferBuilder::require_existing_sections`], a fragment adding a section fails before
+    /// anything is merged.
+    pub async fn patch(&self, fragment: &str) -> Result<()> {
+        let patch = Self::parse_table(fragment, self.options.max_depth)?;
+        let mut guard = self.write_table().await;
+        for (section, _) in patch.iter().filter(|(_, value)| value.is_table()) {
+            self.check_section_creation(&guard, &self.resolve(&guard, section), section)?;
+        }
+        for (section, value) in patch {
+            let name = self.resolve(&guard, &section).into_owned();
+            match (guard.get_mut(&name), value) {
+                (Some(Value::Table(existing)), Value::Table(incoming)) => {
+                    for (key, value) in incoming {
+                        let key_name = self.merge_resolved(existing, &key, value);
+                        self.changes.notify(&name, Some(&key_name), ChangeKind::Set);
+                    }
+                }
+                (_, value) => {
+                    if let Value::Table(incoming) = &value {
+                        for key in incoming.keys() {
+                            self.changes.notify(&name, Some(key), ChangeKind::Set);
+                        }
+                    }
+                    guard.insert(name, value);
+                }
+            }
+            guard.mark_modified();
+        }
+        Ok(())
+    }
+
+    /// Lists the key-level changes that turn this store into `other`, ordered by section and key.
+    pub async fn diff(&self, other: &Confer) -> Vec<ConfigChange> {
+        let old = self.table.read().await.clone();
+        let new = other.table.read().await;
+        crate::diff::diff_tables(&old, &new)
+    }
+
+    /// Compares the store with the TOML file at `path`, listing the changes a save would write
+    /// over it. An empty list means the file and the store are in sync.
+    pub async fn diff_against_file(&self, path: impl AsRef<Path> + Send + Sync) -> Result<Vec<ConfigChange>> {
+        let path_buf = path.as_ref().to_path_buf();
+        let contents = fs::read_to_string(&path_buf)
+            .await
+            .map_err(|err| ConferError::io_error(Some(path_buf), err))?;
+        let on_disk = Self::parse_table(&contents, self.options.max_depth)?;
+        let guard = self.table.read().await;
+        Ok(crate::diff::diff_tables(&on_disk, &guard))
+    }
+
+    /// Synchronously replaces the table of a store that is not shared yet.
+    pub(crate) fn replace_from_source(&self, source: &str) -> Result<()> {
+        let table = Self::parse_table(source, self.options.max_depth)?;
+        #[cfg(feature = "arc-swap")]
+        self.published.store(Arc::new(table.clone()));
+        *self.table.try_write().expect("store is not shared during construction") = table;
+        self.remember_document(source);
         Ok(())
     }
 
+    /// Retains the parsed source document when format preservation is enabled.
+    fn remember_document(&self, source: &str) {
+        if self.options.preserve_format {
+            *self.document.lock().unwrap_or_else(|err| err.into_inner()) =
+                crate::document::parse(source);
+        }
+    }
+
     /// Replaces the in-memory table with the contents of the TOML file at `path`.
     pub async fn load_file(&self, path: impl AsRef<Path> + Send + Sync) -> Result<()> {
         let path_buf = path.as_ref().to_path_buf();
+        let stamp = FileStamp::read(&path_buf).await?;
+        let contents = fs::read_to_string(&path_buf)
+            .await
+            .map_err(|err| ConferError::io_error(Some(path_buf.clone()), err))?;
+        self.load_str(&contents).await?;
+        self.remember_file_stamp(stamp);
+        Ok(())
+    }
+
+    /// Reloads `path` like [`Confer::load_file`] only when its modification time differs from
+    /// the last load of that path, returning whether a reload happened.
+    ///
+    /// Loads through [`Confer::from_file`], [`Confer::from_file_async`], and
+    /// [`Confer::load_file`] are remembered, so a cheap check can run at the top of every
+    /// request. A different `path` than last time always reloads.
+    pub async fn reload_if_changed(&self, path: impl AsRef<Path> + Send + Sync) -> Result<bool> {
+        let path_buf = path.as_ref().to_path_buf();
+        let stamp = FileStamp::read(&path_buf).await?;
+        if self.file_stamp.lock().unwrap_or_else(|err| err.into_inner()).as_ref() == Some(&stamp) {
+            return Ok(false);
+        }
         let contents = fs::read_to_string(&path_buf)
             .await
             .map_err(|err| ConferError::io_error(Some(path_buf.clone()), err))?;
-        self.load_str(&contents).await
+        self.load_str(&contents).await?;
+        self.remember_file_stamp(stamp);
+        Ok(true)
+    }
+
+    /// Records the file the store was just loaded from for [`Confer::reload_if_changed`].
+    pub(crate) fn remember_file_stamp(&self, stamp: FileStamp) {
+        *self.file_stamp.lock().unwrap_or_else(|err| err.into_inner()) = Some(stamp);
     }
 
     /// Serializes the current table to a TOML string.
+    ///
+    /// Stores built with [`ConferBuilder::preserve_format`] replay changes onto the loaded
+    /// document so untouched comments and layout survive.
     pub async fn save_str(&self) -> Result<String> {
         let guard = self.table.read().await;
-        toml::to_string(&*guard).map_err(ConferError::from)
+        self.render(&guard)
     }
 
-    /// Serializes the current table and writes it atomically to the specified file.
-    pub async fn save_file(&self, path: impl AsRef<Path> + Send + Sync) -> Result<()> {
-        let path_buf = path.as_ref().to_path_buf();
-        let serialized = self.save_str().await?;
-        write_atomic(&path_buf, serialized.as_bytes()).await
+    /// Serializes `table`, replaying it onto the loaded document under
+    /// [`ConferBuilder::preserve_format`].
+    fn render(&self, table: &Table) -> Result<String> {
+        let document = self.document.lock().unwrap_or_else(|err| err.into_inner()).clone();
+        match document {
+            Some(document) => crate::document::render(&document, table),
+            None => toml::to_string(table).map_err(ConferError::from),
+        }
     }
 
-    /// Returns the raw TOML value stored under `section.key`, if present.
-    pub async fn get_value(&self, section: &str, key: &str) -> Option<Value> {
+    /// Serializes the current table like [`Confer::save_str`], applying `options` to the output.
+    ///
+    /// The stored values are left as they are; only the serialized copy is rewritten.
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> conferencier::Result<()> {
+    /// let store = conferencier::Confer::new();
+    /// store.set_float("App", "ratio", 0.1 + 0.2).await?;
+    /// let options = conferencier::SaveOptions::default().float_precision(6);
+    /// assert_eq!(store.save_str_with(&options).await?, "[App]\nratio = 0.3\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn save_str_with(&self, options: &SaveOptions) -> Result<String> {
         let guard = self.table.read().await;
-        section_table(&guard, section)
-            .and_then(|table| table.get(key).cloned())
+        let mut table = guard.clone();
+        options.apply(&mut table);
+        options.format(self.render(&table)?)
     }
 
-    /// Returns a cloned snapshot of the table stored at `section`, if it exists.
-    pub async fn get_section_table(&self, section: &str) -> Option<Table> {
-        let guard = self.table.read().await;
-        section_table(&guard, section).map(|table| table.clone())
+    /// Serializes the current table like [`Confer::save_str`], omitting sections without keys.
+    ///
+    /// The empty sections remain in memory; only the serialized output drops them.
+    pub async fn save_str_prune_empty(&self) -> Result<String> {
+        let mut pruned = self.table.read().await.clone();
+        pruned.retain(|_, value| !matches!(value, Value::Table(inner) if inner.is_empty()));
+        self.render(&pruned)
     }
 
-    /// Inserts `value` at `section.key`, creating the section if necessary.
-    pub async fn set_value(&self, section: &str, key: &str, value: Value) -> Result<()> {
-        let mut guard = self.table.write().await;
-        match guard.entry(section.to_owned()) {
-            toml::map::Entry::Occupied(mut entry) => {
-                if let Value::Table(inner) = entry.get_mut() {
-                    inner.insert(key.to_owned(), value);
-                    Ok(())
-                } else {
-                    Err(ConferError::type_mismatch(
-                        section,
-                        "<section>",
-                        "table",
-                        value_conversion::describe(entry.get()),
-                    ))
-                }
-            }
-            toml::map::Entry::Vacant(entry) => {
-                let mut table = Table::new();
-                table.insert(key.to_owned(), value);
-                entry.insert(Value::Table(table));
-                Ok(())
+    /// Serializes the current table to a TOML string with the given `section.key` values masked.
+    ///
+    /// Each path is split at its first `.`; matching values are replaced by `"***"` and unknown
+    /// paths are ignored. Intended for log dumps that must not leak credentials.
+    pub async fn save_str_redacted(&self, paths: &[&str]) -> Result<String> {
+        let mut table = self.table.read().await.clone();
+        for path in paths {
+            if let Some((section, key)) = path.split_once('.')
+                && let Some(Value::Table(inner)) = table.get_mut(self.resolve(&table, section).as_ref())
+                && let Some(value) = inner.get_mut(self.resolve(inner, key).as_ref())
+            {
+                *value = Value::String(REDACTED.to_owned());
             }
         }
+        self.render(&table)
     }
 
-    /// Returns `true` when the store contains a table for `section`.
-    pub async fn section_exists(&self, section: &str) -> bool {
-        let guard = self.table.read().await;
-        matches!(guard.get(section), Some(Value::Table(_)))
+    /// Serializes the current table like [`Confer::save_str`] and writes it to `writer`,
+    /// flushing it afterwards.
+    pub async fn to_writer(&self, mut writer: impl AsyncWrite + Unpin) -> Result<()> {
+        let serialized = self.save_str().await?;
+        let io_error = |err| ConferError::io_error(None, err);
+        writer.write_all(serialized.as_bytes()).await.map_err(io_error)?;
+        writer.flush().await.map_err(io_error)
     }
 
-    /// Ensures that `section` exists as an empty table, returning an error on type mismatch.
-    pub async fn add_section(&self, section: &str) -> Result<()> {
-        let mut guard = self.table.write().await;
-        match guard.entry(section.to_owned()) {
-            toml::map::Entry::Occupied(entry) => {
-                if entry.get().is_table() {
-                    Ok(())
-                } else {
-                    Err(ConferError::type_mismatch(
-                        section,
-                        "<section>",
-                        "table",
-                        value_conversion::describe(entry.get()),
-                    ))
+    /// Serializes the current table and writes it atomically to the specified file.
+    pub async fn save_file(&self, path: impl AsRef<Path> + Send + Sync) -> Result<()> {
+        let trace = StoreTrace::start("save");
+        trace
+            .instrument(async {
+                let path_buf = path.as_ref().to_path_buf();
+                let serialized = self.save_str().await?;
+                trace.sections(self.table.read().await.len());
+                write_atomic(&path_buf, serialized.as_bytes()).await
+            })
+            .await
+    }
+
+    /// Loads `path` like [`Confer::load_file`] while holding a shared advisory lock on the
+    /// sidecar `<path>.lock`, so the read never overlaps a [`Confer::save_file_locked`].
+    #[cfg(feature = "file-lock")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "file-lock")))]
+    pub async fn load_file_locked(&self, path: impl AsRef<Path> + Send + Sync) -> Result<()> {
+        let path_buf = path.as_ref().to_path_buf();
+        let _lock = crate::file_lock::FileLock::shared(&path_buf).await?;
+        self.load_file(&path_buf).await?;
+        let loaded = self.table.read().await.clone();
+        self.remember_locked_baseline(path_buf, loaded);
+        Ok(())
+    }
+
+    /// Saves to `path` under an exclusive advisory lock on the sidecar `<path>.lock`.
+    ///
+    /// While the lock is held the file is re-read and a copy of the store is deep-merged on top
+    /// of it: stored values win, and keys only present on disk (written by another process) are
+    /// kept instead of being lost. Keys removed from the store since its last locked load or save
+    /// of `path` are dropped from the file as well. The output is rendered like
+    /// [`Confer::save_str`]; the store itself is left unchanged and unlocked during the file I/O.
+    #[cfg(feature = "file-lock")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "file-lock")))]
+    pub async fn save_file_locked(&self, path: impl AsRef<Path> + Send + Sync) -> Result<()> {
+        let trace = StoreTrace::start("save");
+        trace
+            .instrument(async {
+                let path_buf = path.as_ref().to_path_buf();
+                let stored = self.table.read().await.clone();
+                let _lock = crate::file_lock::FileLock::exclusive(&path_buf).await?;
+                let mut merged = match fs::read_to_string(&path_buf).await {
+                    Ok(contents) => Self::parse_table(&contents, self.options.max_depth)?,
+                    Err(err) if err.kind() == ErrorKind::NotFound => Table::new(),
+                    Err(err) => return Err(ConferError::io_error(Some(path_buf), err)),
+                };
+
+                if let Some(baseline) = self.locked_baseline(&path_buf) {
+                    self.drop_removed(&mut merged, &baseline, &stored);
                 }
-            }
-            toml::map::Entry::Vacant(entry) => {
-                entry.insert(Value::Table(Table::new()));
+                for (section, value) in &stored {
+                    self.merge_resolved(&mut merged, section, value.clone());
+                }
+                trace.sections(merged.len());
+                let serialized = self.render(&merged)?;
+                write_atomic(&path_buf, serialized.as_bytes()).await?;
+                self.remember_locked_baseline(path_buf, stored);
                 Ok(())
-            }
-        }
+            })
+            .await
     }
 
-    /// Removes `key` from `section`, ignoring missing keys or sections.
-    pub async fn remove_key(&self, section: &str, key: &str) -> Result<()> {
-        let mut guard = self.table.write().await;
-        if let Some(value) = guard.get_mut(section) {
-            if let Value::Table(inner) = value {
-                inner.remove(key);
-                Ok(())
-            } else {
-                Err(ConferError::type_mismatch(
-                    section,
-                    "<section>",
-                    "table",
-                    value_conversion::describe(value),
-                ))
+    /// Returns the table this store held at its last locked load or save of `path`.
+    #[cfg(feature = "file-lock")]
+    fn locked_baseline(&self, path: &Path) -> Option<Table> {
+        let baseline = self.locked_baseline.lock().unwrap_or_else(|err| err.into_inner());
+        baseline
+            .as_ref()
+            .filter(|(recorded, _)| recorded == path)
+            .map(|(_, table)| table.clone())
+    }
+
+    /// Records `table` as the state of the store at a locked load or save of `path`.
+    #[cfg(feature = "file-lock")]
+    fn remember_locked_baseline(&self, path: PathBuf, table: Table) {
+        *self.locked_baseline.lock().unwrap_or_else(|err| err.into_inner()) = Some((path, table));
+    }
+
+    /// Removes from `merged` every entry of `baseline` that `stored` no longer holds, recursing
+    /// into tables present on all three sides, so keys deleted from the store stay deleted.
+    #[cfg(feature = "file-lock")]
+    fn drop_removed(&self, merged: &mut Table, baseline: &Table, stored: &Table) {
+        for (key, before) in baseline {
+            let name = self.resolve(merged, key).into_owned();
+            match (stored.get(self.resolve(stored, key).as_ref()), before, merged.get_mut(&name)) {
+                (None, _, _) => {
+                    merged.remove(&name);
+                }
+                (Some(Value::Table(now)), Value::Table(before), Some(Value::Table(on_disk))) => {
+                    self.drop_removed(on_disk, before, now);
+                }
+                _ => {}
             }
-        } else {
-            Ok(())
         }
     }
 
-    /// Removes `section` from the store, ignoring missing sections.
-    pub async fn remove_section(&self, section: &str) -> Result<()> {
-        let mut guard = self.table.write().await;
-        guard.remove(section);
-        Ok(())
+    /// Serializes the current table like [`Confer::save_str_with`] and writes it atomically to
+    /// `path`, retrying transient write and rename failures as configured by `options`.
+    ///
+    /// Once retries are exhausted the last failure is returned as [`ConferError::Io`].
+    pub async fn save_file_with(
+        &self,
+        path: impl AsRef<Path> + Send + Sync,
+        options: &SaveOptions,
+    ) -> Result<()> {
+        let trace = StoreTrace::start("save");
+        trace
+            .instrument(async {
+                let path_buf = path.as_ref().to_path_buf();
+                let serialized = self.save_str_with(options).await?;
+                trace.sections(self.table.read().await.len());
+                write_atomic_with(&path_buf, serialized.as_bytes(), options).await
+            })
+            .await
     }
 
-    /// Lists all sections currently backed by a TOML table.
-    pub async fn list_sections(&self) -> Vec<String> {
-        let guard = self.table.read().await;
-        guard
-            .iter()
-            .filter_map(|(name, value)| value.as_table().map(|_| name.clone()))
-            .collect()
+    /// Serializes the current table as JSON and writes it atomically to the specified file.
+    ///
+    /// Datetimes are written as RFC 3339 strings.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub async fn save_json_file(&self, path: impl AsRef<Path> + Send + Sync) -> Result<()> {
+        let path_buf = path.as_ref().to_path_buf();
+        let serialized = {
+            let guard = self.table.read().await;
+            crate::json::to_string(&guard)?
+        };
+        write_atomic(&path_buf, serialized.as_bytes()).await
     }
 
-    /// Lists the keys contained in `section`, or an empty vector when the section is absent.
-    pub async fn list_keys(&self, section: &str) -> Result<Vec<String>> {
-        let guard = self.table.read().await;
-        match section_table(&guard, section) {
-            Some(table) => Ok(table.keys().cloned().collect()),
-            None => {
-                if guard.contains_key(section) {
-                    Err(ConferError::type_mismatch(
+    /// Writes the scalars of `section` to `path` as dotenv `PREFIX_KEY=value` lines.
+    ///
+    /// Arrays are joined with `,`; see [`Confer::save_env_file_with_separator`] to change that.
+    pub async fn save_env_file(
+        &self,
+        section: &str,
+        path: impl AsRef<Path> + Send + Sync,
+        prefix: &str,
+    ) -> Result<()> {
+        self.save_env_file_with_separator(section, path, prefix, ",")
+            .await
+    }
+
+    /// Writes the scalars of `section` to `path` as dotenv lines, joining arrays with `separator`.
+    ///
+    /// Keys are upper-cased and prefixed with `prefix`; nested tables are skipped and values with
+    /// whitespace or shell-significant characters are double-quoted. The file is written atomically.
+    pub async fn save_env_file_with_separator(
+        &self,
+        section: &str,
+        path: impl AsRef<Path> + Send + Sync,
+        prefix: &str,
+        separator: &str,
+    ) -> Result<()> {
+        let path_buf = path.as_ref().to_path_buf();
+        let rendered = {
+            let guard = self.table.read().await;
+            match guard.get(self.resolve(&guard, section).as_ref()) {
+                Some(Value::Table(table)) => crate::env_file::render(table, prefix, separator),
+                Some(other) => {
+                    return Err(ConferError::type_mismatch(
                         section,
                         "<section>",
                         "table",
-                        value_conversion::describe(guard.get(section).unwrap()),
-                    ))
-                } else {
-                    Ok(Vec::new())
+                        value_conversion::describe(other),
+                    ));
                 }
+                None => return Err(ConferError::missing_key(section, "<section>")),
             }
+        };
+        write_atomic(&path_buf, rendered.as_bytes()).await
+    }
+
+    /// Returns the raw TOML value stored under `section.key`, if present.
+    pub async fn get_value(&self, section: &str, key: &str) -> Option<Value> {
+        let guard = self.table.read().await;
+        let table = section_table(&guard, &self.resolve(&guard, section))?;
+        table.get(self.resolve(table, key).as_ref()).cloned()
+    }
+
+    /// Returns the value at `section.key` behind an [`Arc`], cloning it out of the table only on
+    /// the first read after a write.
+    ///
+    /// Repeated reads of a large array or table share one allocation. Any write to the store
+    /// drops the shared copies, so later calls observe the new contents.
+    pub async fn get_arc_value(&self, section: &str, key: &str) -> Result<Arc<Value>> {
+        let guard = self.table.read().await;
+        let section_name = self.resolve(&guard, section);
+        let table = existing_section(&guard, &section_name)?
+            .ok_or_else(|| ConferError::missing_key(section, key))?;
+        let key_name = self.resolve(table, key);
+        let value = table
+            .get(key_name.as_ref())
+            .ok_or_else(|| ConferError::missing_key(section, key))?;
+
+        let mut shared = self.shared_values.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(cached) = shared.get(section_name.as_ref()).and_then(|keys| keys.get(key_name.as_ref())) {
+            return Ok(Arc::clone(cached));
         }
+        let cached = Arc::new(value.clone());
+        shared
+            .entry(section_name.into_owned())
+            .or_default()
+            .insert(key_name.into_owned(), Arc::clone(&cached));
+        Ok(cached)
     }
 
-    /// Retrieves a string value stored at `section.key`.
-    pub async fn get_string(&self, section: &str, key: &str) -> Result<String> {
-        let value = self.fetch_value(section, key).await?;
-        value_conversion::string(section, key, value)
+    /// Returns a copy of element `index` of the array at `section.key`, without cloning the rest
+    /// of the array. Out-of-bounds indices yield `None`.
+    ///
+    /// Fails with [`ConferError::MissingKey`] when the key is absent and
+    /// [`ConferError::TypeMismatch`] when it does not hold an array.
+    pub async fn get_value_at_index(&self, section: &str, key: &str, index: usize) -> Result<Option<Value>> {
+        self.with_array(section, key, |items| items.get(index).cloned()).await
     }
 
-    /// Retrieves an integer value stored at `section.key`.
-    pub async fn get_integer(&self, section: &str, key: &str) -> Result<i64> {
-        let value = self.fetch_value(section, key).await?;
-        value_conversion::integer(section, key, value)
+    /// Returns the number of elements in the array at `section.key`, failing like
+    /// [`Confer::get_value_at_index`].
+    pub async fn array_len(&self, section: &str, key: &str) -> Result<usize> {
+        self.with_array(section, key, |items| items.len()).await
     }
 
-    /// Retrieves a floating-point value stored at `section.key`.
-    pub async fn get_float(&self, section: &str, key: &str) -> Result<f64> {
-        let value = self.fetch_value(section, key).await?;
-        value_conversion::float(section, key, value)
+    /// Runs `f` on the array stored at `section.key` under the read lock.
+    async fn with_array<T>(&self, section: &str, key: &str, f: impl FnOnce(&[Value]) -> T) -> Result<T> {
+        let guard = self.table.read().await;
+        let table = existing_section(&guard, &self.resolve(&guard, section))?
+            .ok_or_else(|| ConferError::missing_key(section, key))?;
+        match table.get(self.resolve(table, key).as_ref()) {
+            Some(Value::Array(items)) => Ok(f(items)),
+            Some(other) => Err(ConferError::type_mismatch(
+                section,
+                key,
+                "array",
+                value_conversion::describe(other),
+            )),
+            None => Err(ConferError::missing_key(section, key)),
+        }
     }
 
-    /// Retrieves a boolean value stored at `section.key`.
-    pub async fn get_boolean(&self, section: &str, key: &str) -> Result<bool> {
-        let value = self.fetch_value(section, key).await?;
-        value_conversion::boolean(section, key, value)
+    /// Returns `true` when `section.key` holds a value; a non-table section counts as absent.
+    pub async fn contains_key(&self, section: &str, key: &str) -> bool {
+        self.value_type(section, key).await.is_some()
     }
 
-    /// Retrieves a [`Datetime`] value stored at `section.key`, parsing strings when necessary.
-    pub async fn get_datetime(
+    /// Describes the TOML type stored at `section.key` (e.g. `"integer"`), without cloning it.
+    pub async fn value_type(&self, section: &str, key: &str) -> Option<&'static str> {
+        let guard = self.table.read().await;
+        let table = section_table(&guard, &self.resolve(&guard, section))?;
+        table.get(self.resolve(table, key).as_ref()).map(value_conversion::describe)
+    }
+
+    /// Calls `f` with a borrowed reference to the value at `section.key` under the read lock.
+    ///
+    /// Returns `Ok(None)` when the section or key is absent, avoiding the clone made by
+    /// [`Confer::get_value`]. `f` runs while the lock is held, so it should be quick.
+    pub async fn with_value<R>(
         &self,
         section: &str,
         key: &str,
-    ) -> Result<Datetime> {
-        let value = self.fetch_value(section, key).await?;
-        value_conversion::datetime(section, key, value)
+        f: impl FnOnce(&Value) -> R,
+    ) -> Result<Option<R>> {
+        let guard = self.table.read().await;
+        let Some(table) = existing_section(&guard, &self.resolve(&guard, section))? else {
+            return Ok(None);
+        };
+        Ok(table.get(self.resolve(table, key).as_ref()).map(f))
     }
 
-    /// Retrieves a string array stored at `section.key`.
+    /// Lends the whole root table to `f` under the read lock.
+    pub async fn with_table<R>(&self, f: impl FnOnce(&Table) -> R) -> R {
+        let guard = self.table.read().await;
+        f(&guard)
+    }
+
+    /// Calls `f` with the path and value of every scalar in the store, under the read lock.
+    ///
+    /// Paths start with the section name; array elements contribute their index, so
+    /// `[App] ports = [80]` yields `["App", "ports", "0"]`. Empty tables and arrays have no
+    /// leaves and are skipped. `f` runs while the lock is held, so it should be quick.
+    pub async fn for_each_leaf(&self, mut f: impl FnMut(&[&str], &Value)) {
+        let guard = self.table.read().await;
+        let mut path = Vec::new();
+        for (name, value) in guard.iter() {
+            path.push(name.clone());
+            walk_leaves(&mut path, value, &mut f);
+            path.pop();
+        }
+    }
+
+    /// Lists every scalar in the store with its dotted path, e.g. `server.tls.port`.
+    ///
+    /// Keys other than ASCII letters, digits, `_`, and `-` are quoted like TOML keys
+    /// (`server."a.b"`), and array elements append `[index]` (`server.ports[0]`). Empty tables
+    /// and arrays produce no pairs. [`Confer::unflatten`] reverses the mapping.
+    pub async fn flatten(&self) -> Vec<(String, Value)> {
+        crate::flat::flatten_table(&*self.table.read().await)
+    }
+
+    /// Lends the whole root table to `f` under the write lock.
+    ///
+    /// Edits made here bypass setter validation, including the nesting limit, and do not emit
+    /// [`Confer::subscribe`] events.
+    pub async fn with_table_mut<R>(&self, f: impl FnOnce(&mut Table) -> R) -> R {
+        let mut guard = self.write_table().await;
+        guard.mark_modified();
+        f(&mut guard)
+    }
+
+    /// Returns a cloned snapshot of the table stored at `section`, if it exists.
+    pub async fn get_section_table(&self, section: &str) -> Option<Table> {
+        let guard = self.table.read().await;
+        section_table(&guard, &self.resolve(&guard, section)).cloned()
+    }
+
+    /// Returns the table stored at `section` behind an [`Arc`], cloning it out of the store only
+    /// on the first call after a write.
+    ///
+    /// Unlike [`Confer::get_section_table`], repeated calls share one allocation. The returned
+    /// table stays valid after later writes, which drop the shared copy instead of mutating it.
+    pub async fn section_snapshot(&self, section: &str) -> Option<Arc<Table>> {
+        let guard = self.table.read().await;
+        let name = self.resolve(&guard, section);
+        let table = section_table(&guard, &name)?;
+
+        let mut shared = self.shared_sections.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(cached) = shared.get(name.as_ref()) {
+            return Some(Arc::clone(cached));
+        }
+        let cached = Arc::new(table.clone());
+        shared.insert(name.into_owned(), Arc::clone(&cached));
+        Some(cached)
+    }
+
+    /// Returns a detached store holding only `section`, copied under a single read lock.
+    ///
+    /// Reads against the snapshot behave like reads against `self` at the time of the call but
+    /// never contend for its lock; derived modules load their fields this way.
+    pub async fn snapshot_section(&self, section: &str) -> Confer {
+        let guard = self.table.read().await;
+        let name = self.resolve(&guard, section);
+        let table = guard
+            .get(name.as_ref())
+            .map(|value| Table::from_iter([(name.into_owned(), value.clone())]))
+            .unwrap_or_default();
+        Confer {
+            options: self.options,
+            ..Confer::from_table(table)
+        }
+    }
+
+    /// Inserts `value` at `section.key`, creating the section if necessary unless the store was
+    /// built with [`ConferBuilder::require_existing_sections`].
+    ///
+    /// Replacing a nested table or array with a scalar fails with [`ConferError::TypeMismatch`];
+    /// use [`Confer::set_value_force`] when that is intended.
+    pub async fn set_value(&self, section: &str, key: &str, value: Value) -> Result<()> {
+        self.insert_value(section, key, value, false).await
+    }
+
+    /// Inserts `value` at `section.key` like [`Confer::set_value`], replacing whatever is stored.
+    pub async fn set_value_force(&self, section: &str, key: &str, value: Value) -> Result<()> {
+        self.insert_value(section, key, value, true).await
+    }
+
+    /// Shared implementation of [`Confer::set_value`] and [`Confer::set_value_force`].
+    async fn insert_value(&self, section: &str, key: &str, value: Value, force: bool) -> Result<()> {
+        if exceeds_depth(&value, 2, self.options.max_depth) {
+            return Err(depth_error(section, key, self.options.max_depth));
+        }
+        let mut guard = self.write_table().await;
+        let section_name = self.resolve(&guard, section).into_owned();
+        match guard.entry(section_name) {
+            toml::map::Entry::Occupied(mut entry) => {
+                if let Value::Table(inner) = entry.get_mut() {
+                    let key_name = self.resolve(inner, key).into_owned();
+                    if !force {
+                        check_replacement(section, key, inner.get(&key_name), &value)?;
+                    }
+                    inner.insert(key_name.clone(), value);
+                    self.changes.notify(entry.key(), Some(&key_name), ChangeKind::Set);
+                    guard.mark_modified();
+                    Ok(())
+                } else {
+                    Err(ConferError::type_mismatch(
+                        section,
+                        "<section>",
+                        "table",
+                        value_conversion::describe(entry.get()),
+                    ))
+                }
+            }
+            toml::map::Entry::Vacant(_) if self.options.require_existing_sections => {
+                Err(ConferError::missing_key(section, "<section>"))
+            }
+            toml::map::Entry::Vacant(entry) => {
+                let mut table = Table::new();
+                table.insert(key.to_owned(), value);
+                self.changes.notify(entry.key(), Some(key), ChangeKind::Set);
+                entry.insert(Value::Table(table));
+                guard.mark_modified();
+                Ok(())
+            }
+        }
+    }
+
+    /// Inserts every `(key, value)` pair into `section` under a single write lock, creating the
+    /// section if necessary unless the store was built with
+    /// [`ConferBuilder::require_existing_sections`].
+    ///
+    /// Entries are checked like [`Confer::set_value`] before anything is written, so on error
+    /// the store is left unchanged.
+    pub async fn set_many(
+        &self,
+        section: &str,
+        entries: impl IntoIterator<Item = (String, Value)>,
+    ) -> Result<()> {
+        let entries: Vec<_> = entries.into_iter().collect();
+        let mut guard = self.write_table().await;
+        let section_name = self.resolve(&guard, section).into_owned();
+        self.check_section_creation(&guard, &section_name, section)?;
+        let mut resolved = Vec::with_capacity(entries.len());
+        {
+            let existing = existing_section(&guard, &section_name)?;
+            for (key, value) in entries {
+                if exceeds_depth(&value, 2, self.options.max_depth) {
+                    return Err(depth_error(section, &key, self.options.max_depth));
+                }
+                let key_name = match existing {
+                    Some(inner) => {
+                        let key_name = self.resolve(inner, &key).into_owned();
+                        check_replacement(section, &key, inner.get(&key_name), &value)?;
+                        key_name
+                    }
+                    None => key,
+                };
+                resolved.push((key_name, value));
+            }
+        }
+
+        let Value::Table(inner) = guard
+            .entry(section_name.clone())
+            .or_insert_with(|| Value::Table(Table::new()))
+        else {
+            unreachable!("section type was checked above");
+        };
+        for (key, value) in resolved {
+            self.changes.notify(&section_name, Some(&key), ChangeKind::Set);
+            inner.insert(key, value);
+        }
+        guard.mark_modified();
+        Ok(())
+    }
+
+    /// Returns a hash of the current contents of `section`, or `None` when the section is absent.
+    ///
+    /// The hash is only meaningful for comparisons within the same process.
+    pub async fn section_hash(&self, section: &str) -> Option<u64> {
+        let guard = self.table.read().await;
+        section_table(&guard, &self.resolve(&guard, section)).map(hash_table)
+    }
+
+    /// Records `section` as observed by `module`, replacing any previous snapshot.
+    pub(crate) async fn record_load_snapshot(
+        &self,
+        module: Arc<dyn Any + Send + Sync>,
+        section: &str,
+    ) {
+        let table = self.get_section_table(section).await;
+        self.remember_load_snapshot(&module, section, table);
+    }
+
+    /// Records `section` as held by `snapshot`, the detached copy `module` just loaded from.
+    pub(crate) async fn record_snapshot_of(
+        &self,
+        module: Arc<dyn Any + Send + Sync>,
+        section: &str,
+        snapshot: &Confer,
+    ) {
+        let table = snapshot.get_section_table(section).await;
+        self.remember_load_snapshot(&module, section, table);
+    }
+
+    /// Stores `table` as the contents of `section` last seen by `module`.
+    fn remember_load_snapshot(&self, module: &Arc<dyn Any + Send + Sync>, section: &str, table: Option<Table>) {
+        let hash = table.as_ref().map(hash_table);
+        let mut snapshots = self.load_snapshots.lock().unwrap_or_else(|err| err.into_inner());
+        snapshots.retain(|_, snapshot| snapshot.module.strong_count() > 0);
+        snapshots.insert(
+            snapshot_id(module),
+            LoadSnapshot {
+                module: Arc::downgrade(module),
+                section: section.to_owned(),
+                hash,
+                table,
+            },
+        );
+    }
+
+    /// Returns the contents of `section` recorded for `module` at its last load or save.
+    pub(crate) fn load_snapshot_table(
+        &self,
+        module: Arc<dyn Any + Send + Sync>,
+        section: &str,
+    ) -> Option<Table> {
+        let snapshots = self.load_snapshots.lock().unwrap_or_else(|err| err.into_inner());
+        live_snapshot(&snapshots, &module)
+            .filter(|snapshot| snapshot.section == section)
+            .and_then(|snapshot| snapshot.table.clone())
+    }
+
+    /// Writes the entries of `rendered` that differ from `baseline` into `section`.
+    ///
+    /// Owned keys present in `baseline` but missing from `rendered` are removed; every other key
+    /// of the section is left untouched.
+    pub(crate) async fn apply_section_delta(
+        &self,
+        section: &str,
+        rendered: Table,
+        baseline: &Table,
+        owned: impl Fn(&str) -> bool,
+    ) -> Result<()> {
+        for key in baseline.keys() {
+            if owned(key) && !rendered.contains_key(key) {
+                self.remove_key(section, key).await?;
+            }
+        }
+        for (key, value) in rendered {
+            if baseline.get(&key) != Some(&value) {
+                self.set_value_force(section, &key, value).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` when `current`, the hash of `section`, matches the snapshot recorded for
+    /// `module`.
+    fn snapshot_hash_matches(&self, module: &Arc<dyn Any + Send + Sync>, section: &str, current: Option<u64>) -> bool {
+        let snapshots = self.load_snapshots.lock().unwrap_or_else(|err| err.into_inner());
+        live_snapshot(&snapshots, module).is_some_and(|snapshot| snapshot.section == section && snapshot.hash == current)
+    }
+
+    /// Writes `rendered` into `section` like a derived module's `save`, but only while the section
+    /// still matches the snapshot recorded for `module`. The check and the write happen under one
+    /// write lock, so no other writer can land in between.
+    ///
+    /// Keys for which `owned` holds are removed when `rendered` lacks them, and with `known` every
+    /// key it does not list is pruned. Returns `false`, leaving the store untouched, when the
+    /// section changed since the snapshot was taken.
+    pub(crate) async fn commit_if_unchanged(
+        &self,
+        module: Arc<dyn Any + Send + Sync>,
+        section: &str,
+        rendered: Table,
+        owned: impl Fn(&str) -> bool,
+        known: Option<&[&str]>,
+    ) -> Result<bool> {
+        let mut guard = self.write_table().await;
+        let section_name = self.resolve(&guard, section).into_owned();
+        let current = section_table(&guard, &section_name).map(hash_table);
+        if !self.snapshot_hash_matches(&module, section, current) {
+            return Ok(false);
+        }
+        existing_section(&guard, &section_name)?;
+        self.check_section_creation(&guard, &section_name, section)?;
+
+        let Value::Table(inner) = guard
+            .entry(section_name.clone())
+            .or_insert_with(|| Value::Table(Table::new()))
+        else {
+            unreachable!("section type was checked above");
+        };
+        let stale: Vec<String> = inner
+            .keys()
+            .filter(|key| {
+                let dropped = owned(key) && !rendered.keys().any(|name| self.same_name(name, key));
+                let unknown = known.is_some_and(|known| !known.iter().any(|name| self.same_name(name, key)));
+                dropped || unknown
+            })
+            .cloned()
+            .collect();
+        for key in stale {
+            inner.remove(&key);
+            self.changes.notify(&section_name, Some(&key), ChangeKind::Removed);
+        }
+        for (key, value) in rendered {
+            let key_name = self.resolve(inner, &key).into_owned();
+            self.changes.notify(&section_name, Some(&key_name), ChangeKind::Set);
+            inner.insert(key_name, value);
+        }
+        let saved = inner.clone();
+        guard.mark_modified();
+        self.remember_load_snapshot(&module, section, Some(saved));
+        Ok(true)
+    }
+
+    /// Upgrades `section` to schema `version`, running `migrate` under a single write lock.
+    ///
+    /// The stored version is read from `version_key` (absent means `0`). Newer stored versions
+    /// are rejected; older ones are passed to `migrate` and the key is bumped afterwards.
+    /// Missing sections are left alone since there is nothing to migrate.
+    pub(crate) async fn apply_schema_version<F>(
+        &self,
+        section: &str,
+        version_key: &str,
+        version: u32,
+        migrate: Option<F>,
+    ) -> Result<()>
+    where
+        F: FnOnce(&mut Table, u32) -> Result<()>,
+    {
+        let mut guard = self.write_table().await;
+        let section_name = self.resolve(&guard, section).into_owned();
+        let Some(Value::Table(table)) = guard.get_mut(&section_name) else {
+            return Ok(());
+        };
+        let version_key = self.resolve(table, version_key).into_owned();
+        let version_key = version_key.as_str();
+        let stored = match table.get(version_key) {
+            None => 0,
+            Some(Value::Integer(raw)) => u32::try_from(*raw).map_err(|_| {
+                ConferError::value_parse_owned(section, version_key, format!("invalid schema version {raw}"))
+            })?,
+            Some(other) => {
+                return Err(ConferError::type_mismatch(
+                    section,
+                    version_key,
+                    "integer",
+                    value_conversion::describe(other),
+                ));
+            }
+        };
+        if stored > version {
+            return Err(ConferError::value_parse_owned(
+                section,
+                version_key,
+                format!("stored schema version {stored} is newer than supported version {version}"),
+            ));
+        }
+        if stored < version
+            && let Some(migrate) = migrate
+        {
+            let mut migrated = table.clone();
+            migrate(&mut migrated, stored)?;
+            migrated.insert(version_key.to_owned(), Value::Integer(i64::from(version)));
+            *table = migrated;
+            guard.mark_modified();
+        }
+        Ok(())
+    }
+
+    /// Records that `type_name` owns `keys` within `section`.
+    ///
+    /// Fails with [`ConferError::SectionConflict`] when a different type already claimed one of
+    /// the keys; registering the same type again replaces its previous claim. Derived modules call
+    /// this from `from_confer` in stores built with [`ConferBuilder::detect_section_conflicts`],
+    /// so overlapping modules are reported before either one saves.
+    pub fn register_module_section(
+        &self,
+        type_name: &str,
+        section: &str,
+        keys: &[&str],
+    ) -> Result<()> {
+        let mut claims = self.section_claims.lock().unwrap_or_else(|err| err.into_inner());
+        let claim_section = if self.options.case_insensitive {
+            section.to_ascii_lowercase()
+        } else {
+            section.to_owned()
+        };
+        let claims = claims.entry(claim_section).or_default();
+        for claim in claims.iter().filter(|claim| claim.owner != type_name) {
+            if let Some(key) = keys
+                .iter()
+                .find(|key| claim.keys.iter().any(|owned| self.same_name(owned, key)))
+            {
+                return Err(ConferError::section_conflict(section, *key, &claim.owner, type_name));
+            }
+        }
+        claims.retain(|claim| claim.owner != type_name);
+        claims.push(SectionClaim {
+            owner: type_name.to_owned(),
+            keys: keys.iter().map(|key| (*key).to_owned()).collect(),
+        });
+        Ok(())
+    }
+
+    /// Registers a derived module's keys like [`Confer::register_module_section`] when the store
+    /// was built with [`ConferBuilder::detect_section_conflicts`], and does nothing otherwise.
+    pub(crate) fn claim_module_section(&self, type_name: &str, section: &str, keys: &[&str]) -> Result<()> {
+        if !self.options.detect_section_conflicts {
+            return Ok(());
+        }
+        self.register_module_section(type_name, section, keys)
+    }
+
+    /// Returns `true` when the store contains a table for `section`.
+    pub async fn section_exists(&self, section: &str) -> bool {
+        let guard = self.table.read().await;
+        matches!(guard.get(self.resolve(&guard, section).as_ref()), Some(Value::Table(_)))
+    }
+
+    /// Ensures that `section` exists as an empty table, returning an error on type mismatch.
+    pub async fn add_section(&self, section: &str) -> Result<()> {
+        let mut guard = self.write_table().await;
+        let section_name = self.resolve(&guard, section).into_owned();
+        match guard.entry(section_name) {
+            toml::map::Entry::Occupied(entry) => {
+                if entry.get().is_table() {
+                    Ok(())
+                } else {
+                    Err(ConferError::type_mismatch(
+                        section,
+                        "<section>",
+                        "table",
+                        value_conversion::describe(entry.get()),
+                    ))
+                }
+            }
+            toml::map::Entry::Vacant(entry) => {
+                entry.insert(Value::Table(Table::new()));
+                guard.mark_modified();
+                Ok(())
+            }
+        }
+    }
+
+    /// Removes `key` from `section`, ignoring missing keys or sections.
+    pub async fn remove_key(&self, section: &str, key: &str) -> Result<()> {
+        self.take_key(section, key).await.map(drop)
+    }
+
+    /// Removes `key` from `section` and returns its previous value, or `None` when absent.
+    ///
+    /// Fails like [`Confer::remove_key`] when `section` names a non-table value.
+    pub async fn take_key(&self, section: &str, key: &str) -> Result<Option<Value>> {
+        let mut guard = self.write_table().await;
+        let section_name = self.resolve(&guard, section).into_owned();
+        match guard.get_mut(&section_name) {
+            Some(Value::Table(inner)) => {
+                let key_name = self.resolve(inner, key).into_owned();
+                let removed = inner.remove(&key_name);
+                if removed.is_some() {
+                    self.changes.notify(&section_name, Some(&key_name), ChangeKind::Removed);
+                    guard.mark_modified();
+                }
+                Ok(removed)
+            }
+            Some(other) => Err(ConferError::type_mismatch(
+                section,
+                "<section>",
+                "table",
+                value_conversion::describe(other),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Fails with [`ConferError::UnknownKey`] on the first key of `section` not listed in
+    /// `known_keys`, comparing names like [`Confer::prune_section_to`].
+    pub(crate) async fn reject_unknown_keys(&self, section: &str, known_keys: &[&str]) -> Result<()> {
+        let guard = self.table.read().await;
+        let Some(table) = existing_section(&guard, &self.resolve(&guard, section))? else {
+            return Ok(());
+        };
+        match table.keys().find(|key| !known_keys.iter().any(|known| self.same_name(known, key))) {
+            Some(key) => Err(ConferError::unknown_key(section, key.as_str())),
+            None => Ok(()),
+        }
+    }
+
+    /// Removes every key of `section` not listed in `known_keys`, as derived `save` does for
+    /// modules without `#[confer(shared_section)]`.
+    ///
+    /// In [`ConferBuilder::case_insensitive`] stores, a key matching a known key up to ASCII
+    /// case is kept. Each removal emits a [`ChangeKind::Removed`] event. A missing section is left absent;
+    /// a non-table section fails like [`Confer::remove_key`].
+    pub async fn prune_section_to(&self, section: &str, known_keys: &[&str]) -> Result<()> {
+        let mut guard = self.write_table().await;
+        let section_name = self.resolve(&guard, section).into_owned();
+        match guard.get_mut(&section_name) {
+            Some(Value::Table(inner)) => {
+                let stale: Vec<String> = inner
+                    .keys()
+                    .filter(|key| !known_keys.iter().any(|known| self.same_name(known, key)))
+                    .cloned()
+                    .collect();
+                if !stale.is_empty() {
+                    for key in stale {
+                        inner.remove(&key);
+                        self.changes.notify(&section_name, Some(&key), ChangeKind::Removed);
+                    }
+                    guard.mark_modified();
+                }
+                Ok(())
+            }
+            Some(other) => Err(ConferError::type_mismatch(
+                section,
+                "<section>",
+                "table",
+                value_conversion::describe(other),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Removes `section` from the store, returning whether it was present.
+    ///
+    /// Missing sections are not an error; they leave the store untouched and yield `false`.
+    pub async fn remove_section(&self, section: &str) -> Result<bool> {
+        let mut guard = self.write_table().await;
+        let section_name = self.resolve(&guard, section).into_owned();
+        let removed = guard.remove(&section_name).is_some();
+        if removed {
+            self.changes.notify(&section_name, None, ChangeKind::SectionRemoved);
+            guard.mark_modified();
+        }
+        Ok(removed)
+    }
+
+    /// Counts every scalar and array value in the store, descending into nested tables.
+    ///
+    /// Tables themselves are not counted, only the leaves they contain.
+    pub async fn total_values(&self) -> usize {
+        let guard = self.table.read().await;
+        count_leaves(&guard)
+    }
+
+    /// Replaces the whole of `section` with `table` under one write lock, creating the section
+    /// when it is absent unless the store was built with
+    /// [`ConferBuilder::require_existing_sections`].
+    ///
+    /// Fails with [`ConferError::TypeMismatch`] when `section` names a non-table value, leaving
+    /// the store untouched.
+    pub async fn replace_section(&self, section: &str, table: Table) -> Result<()> {
+        if let Some((key, _)) = table
+            .iter()
+            .find(|(_, value)| exceeds_depth(value, 2, self.options.max_depth))
+        {
+            return Err(depth_error(section, key, self.options.max_depth));
+        }
+        let mut guard = self.write_table().await;
+        let section_name = self.resolve(&guard, section).into_owned();
+        if existing_section(&guard, &section_name)?.is_some() {
+            self.changes.notify(&section_name, None, ChangeKind::SectionRemoved);
+        } else {
+            self.check_section_creation(&guard, &section_name, section)?;
+        }
+        for key in table.keys() {
+            self.changes.notify(&section_name, Some(key), ChangeKind::Set);
+        }
+        guard.insert(section_name, Value::Table(table));
+        guard.mark_modified();
+        Ok(())
+    }
+
+    /// Checks the whole store for structural inconsistencies, such as those left behind by
+    /// [`Confer::patch`] or [`Confer::with_table_mut`] when assembling configuration from several
+    /// sources, and returns the first one found.
+    ///
+    /// Reports, in this order: tables nested deeper than the configured limit, top-level
+    /// entries that are not tables, keys colliding case-insensitively (only for
+    /// [`ConferBuilder::case_insensitive`] stores), arrays mixing tables with other values, and
+    /// arrays of tables whose elements give one key different types. Mismatches are reported as
+    /// [`ConferError::TypeMismatch`] with the element index; case collisions as
+    /// [`ConferError::ValueParse`].
+    pub async fn validate(&self) -> Result<()> {
+        let guard = self.table.read().await;
+        check_depth(&guard, self.options.max_depth)?;
+        crate::validate::check_structure(&guard, self.options.case_insensitive)
+    }
+
+    /// Removes every section and key, leaving an empty store behind the same handle.
+    ///
+    /// Emits a [`ChangeKind::SectionRemoved`] event for every top-level entry.
+    pub async fn clear(&self) {
+        let mut guard = self.write_table().await;
+        if guard.is_empty() {
+            return;
+        }
+        for name in guard.keys() {
+            self.changes.notify(name, None, ChangeKind::SectionRemoved);
+        }
+        guard.clear();
+        guard.mark_modified();
+    }
+
+    /// Removes all keys from `section` while keeping the empty section, ignoring missing sections.
+    /// Each removed key emits a [`ChangeKind::Removed`] event.
+    pub async fn clear_section(&self, section: &str) -> Result<()> {
+        let mut guard = self.write_table().await;
+        let section_name = self.resolve(&guard, section).into_owned();
+        match guard.get_mut(&section_name) {
+            Some(Value::Table(inner)) => {
+                if !inner.is_empty() {
+                    for key in inner.keys() {
+                        self.changes.notify(&section_name, Some(key), ChangeKind::Removed);
+                    }
+                    inner.clear();
+                    guard.mark_modified();
+                }
+                Ok(())
+            }
+            Some(other) => Err(ConferError::type_mismatch(
+                section,
+                "<section>",
+                "table",
+                value_conversion::describe(other),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Deep-copies the table at `from` into a new section `to`.
+    ///
+    /// Fails with [`ConferError::MissingKey`] when `from` is absent and with
+    /// [`ConferError::SectionExists`] when `to` already exists, unless `overwrite` is set. Under
+    /// [`ConferBuilder::require_existing_sections`], a missing `to` fails with
+    /// [`ConferError::MissingKey`].
+    ///
+    /// An overwritten `to` emits a [`ChangeKind::SectionRemoved`] event, followed by a
+    /// [`ChangeKind::Set`] event for every copied key.
+    pub async fn copy_section(&self, from: &str, to: &str, overwrite: bool) -> Result<()> {
+        let mut guard = self.write_table().await;
+        let (from_name, to_name) = self.transfer_names(&guard, from, to, overwrite)?;
+        let source = guard[&from_name].clone();
+        self.notify_section_replaced(&guard, &to_name, &source);
+        guard.insert(to_name, source);
+        guard.mark_modified();
+        Ok(())
+    }
+
+    /// Renames section `from` to `to`, with the same error rules as [`Confer::copy_section`].
+    ///
+    /// Emits a [`ChangeKind::SectionRemoved`] event for `from`, then the events of
+    /// [`Confer::copy_section`] for `to`.
+    pub async fn move_section(&self, from: &str, to: &str, overwrite: bool) -> Result<()> {
+        let mut guard = self.write_table().await;
+        let (from_name, to_name) = self.transfer_names(&guard, from, to, overwrite)?;
+        if from_name != to_name {
+            let source = guard.remove(&from_name).expect("source section checked above");
+            self.changes.notify(&from_name, None, ChangeKind::SectionRemoved);
+            self.notify_section_replaced(&guard, &to_name, &source);
+            guard.insert(to_name, source);
+            guard.mark_modified();
+        }
+        Ok(())
+    }
+
+    /// Announces that `section` is about to be overwritten with `table`: a
+    /// [`ChangeKind::SectionRemoved`] event for an existing entry, then a [`ChangeKind::Set`]
+    /// event per key of `table`.
+    fn notify_section_replaced(&self, root: &Table, section: &str, table: &Value) {
+        if root.contains_key(section) {
+            self.changes.notify(section, None, ChangeKind::SectionRemoved);
+        }
+        for key in table.as_table().into_iter().flat_map(Table::keys) {
+            self.changes.notify(section, Some(key), ChangeKind::Set);
+        }
+    }
+
+    /// Resolves the stored names of a copy or move from `from` to `to`, failing when `from` is
+    /// not a table or `to` already exists without `overwrite`.
+    fn transfer_names(&self, root: &Table, from: &str, to: &str, overwrite: bool) -> Result<(String, String)> {
+        let from_name = self.resolve(root, from).into_owned();
+        existing_section(root, &from_name)?.ok_or_else(|| ConferError::missing_key(from, "<section>"))?;
+        let to_name = self.resolve(root, to).into_owned();
+        if !overwrite && root.contains_key(&to_name) {
+            return Err(ConferError::section_exists(to));
+        }
+        self.check_section_creation(root, &to_name, to)?;
+        Ok((from_name, to_name))
+    }
+
+    /// Lists all sections currently backed by a TOML table.
+    pub async fn list_sections(&self) -> Vec<String> {
+        let guard = self.table.read().await;
+        guard
+            .iter()
+            .filter_map(|(name, value)| value.as_table().map(|_| name.clone()))
+            .collect()
+    }
+
+    /// Lists sections like [`Confer::list_sections`], sorted by name.
+    ///
+    /// The raw order follows the underlying [`Table`], which keeps insertion order when
+    /// `toml`'s `preserve_order` feature is enabled anywhere in the build.
+    pub async fn list_sections_sorted(&self) -> Vec<String> {
+        let mut sections = self.list_sections().await;
+        sections.sort_unstable();
+        sections
+    }
+
+    /// Counts the sections [`Confer::list_sections`] would return, without allocating.
+    pub async fn section_count(&self) -> usize {
+        let guard = self.table.read().await;
+        guard.values().filter(|value| value.is_table()).count()
+    }
+
+    /// Returns `true` when the store holds no sections.
+    pub async fn is_empty(&self) -> bool {
+        let guard = self.table.read().await;
+        !guard.values().any(Value::is_table)
+    }
+
+    /// Returns every section name paired with a clone of its table, skipping non-table entries.
+    ///
+    /// This is the export half of [`Confer::import_sections`], for backing up whole sections.
+    pub async fn sections(&self) -> Vec<(String, Table)> {
+        let guard = self.table.read().await;
+        guard
+            .iter()
+            .filter_map(|(name, value)| value.as_table().map(|table| (name.clone(), table.clone())))
+            .collect()
+    }
+
+    /// Inserts whole sections, typically from [`Confer::sections`].
+    ///
+    /// Existing sections fail with [`ConferError::SectionExists`] unless `overwrite` is set, in
+    /// which case they are replaced wholesale. A non-table root value with the same name always
+    /// fails, as does a new section under [`ConferBuilder::require_existing_sections`]. Every
+    /// name is checked before anything is written.
+    pub async fn import_sections(
+        &self,
+        sections: impl IntoIterator<Item = (String, Table)>,
+        overwrite: bool,
+    ) -> Result<()> {
+        let sections: Vec<_> = sections.into_iter().collect();
+        let mut guard = self.write_table().await;
+        let mut resolved = Vec::with_capacity(sections.len());
+        for (name, table) in sections {
+            if let Some((key, _)) = table
+                .iter()
+                .find(|(_, value)| exceeds_depth(value, 2, self.options.max_depth))
+            {
+                return Err(depth_error(&name, key, self.options.max_depth));
+            }
+            let section_name = self.resolve(&guard, &name).into_owned();
+            if existing_section(&guard, &section_name)?.is_some() && !overwrite {
+                return Err(ConferError::section_exists(name));
+            }
+            self.check_section_creation(&guard, &section_name, &name)?;
+            resolved.push((section_name, table));
+        }
+
+        for (name, table) in resolved {
+            if guard.remove(&name).is_some() {
+                self.changes.notify(&name, None, ChangeKind::SectionRemoved);
+            }
+            for key in table.keys() {
+                self.changes.notify(&name, Some(key), ChangeKind::Set);
+            }
+            guard.insert(name, Value::Table(table));
+            guard.mark_modified();
+        }
+        Ok(())
+    }
+
+    /// Calls `f` with each section name and table without cloning them.
+    ///
+    /// The read lock is held for the whole iteration, so `f` should be quick.
+    pub async fn for_each_section<F>(&self, mut f: F)
+    where
+        F: FnMut(&str, &Table),
+    {
+        let guard = self.table.read().await;
+        for (name, value) in guard.iter() {
+            if let Value::Table(table) = value {
+                f(name, table);
+            }
+        }
+    }
+
+    /// Lists the keys contained in `section`, or an empty vector when the section is absent.
+    pub async fn list_keys(&self, section: &str) -> Result<Vec<String>> {
+        let guard = self.table.read().await;
+        Ok(existing_section(&guard, &self.resolve(&guard, section))?
+            .map(|table| table.keys().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    /// Lists the keys of `section` like [`Confer::list_keys`], sorted by name.
+    pub async fn list_keys_sorted(&self, section: &str) -> Result<Vec<String>> {
+        let mut keys = self.list_keys(section).await?;
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    /// Counts the keys in `section` without allocating, or `0` when the section is absent.
+    ///
+    /// Like [`Confer::list_keys`], fails when `section` names a non-table value.
+    pub async fn key_count(&self, section: &str) -> Result<usize> {
+        let guard = self.table.read().await;
+        Ok(existing_section(&guard, &self.resolve(&guard, section))?.map_or(0, Table::len))
+    }
+
+    /// Returns cloned key/value pairs of `section`, or an empty vector when the section is absent.
+    ///
+    /// Pairs follow the underlying [`Table`]: sorted by key, not in file order, unless `toml`'s
+    /// `preserve_order` feature is enabled anywhere in the build.
+    pub async fn entries(&self, section: &str) -> Result<Vec<(String, Value)>> {
+        let guard = self.table.read().await;
+        Ok(existing_section(&guard, &self.resolve(&guard, section))?
+            .map(|table| {
+                table
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Like [`Confer::entries`], additionally describing each value's TOML type (e.g. `"integer"`).
+    pub async fn entries_typed(&self, section: &str) -> Result<Vec<(String, &'static str, Value)>> {
+        Ok(self
+            .entries(section)
+            .await?
+            .into_iter()
+            .map(|(key, value)| {
+                let kind = value_conversion::describe(&value);
+                (key, kind, value)
+            })
+            .collect())
+    }
+
+    /// Retrieves the value at `section.key` converted to any [`FromConferValue`] type.
+    ///
+    /// Equivalent to the matching `get_*` method, with range checks for narrower numeric types.
+    pub async fn get<T: FromConferValue>(&self, section: &str, key: &str) -> Result<T> {
+        let value = self.fetch_value(section, key).await?;
+        T::from_confer_value(section, key, value)
+    }
+
+    /// Returns the table as of the last completed write without touching the lock.
+    ///
+    /// The load is wait-free, so readers never queue behind writers; in exchange every write
+    /// clones the whole table to publish it.
+    #[cfg(feature = "arc-swap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "arc-swap")))]
+    pub fn read_snapshot(&self) -> Arc<Table> {
+        self.published.load_full()
+    }
+
+    /// Reads `section.key` like [`Confer::get`], but from [`Confer::read_snapshot`] and without
+    /// awaiting.
+    #[cfg(feature = "arc-swap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "arc-swap")))]
+    pub fn get_snapshot<T: FromConferValue>(&self, section: &str, key: &str) -> Result<T> {
+        let root = self.published.load();
+        let value = self.lookup_value(&root, section, key)?.clone();
+        T::from_confer_value(section, key, value)
+    }
+
+    /// Stores any [`IntoConferValue`] type at `section.key`, creating the section if needed.
+    pub async fn set<T: IntoConferValue>(&self, section: &str, key: &str, value: T) -> Result<()> {
+        let value = value.into_confer_value(section, key)?;
+        self.set_value(section, key, value).await
+    }
+
+    /// Retrieves a string value stored at `section.key`.
+    pub async fn get_string(&self, section: &str, key: &str) -> Result<String> {
+        let value = self.fetch_scalar(section, key).await?;
+        value_conversion::string(section, key, value)
+    }
+
+    /// Retrieves a string value, replacing a [`ConferError::MissingKey`] with the error from `f`.
+    ///
+    /// Other failures, such as type mismatches, are returned unchanged.
+    pub async fn get_string_or_else<F>(&self, section: &str, key: &str, f: F) -> Result<String>
+    where
+        F: FnOnce() -> ConferError,
+    {
+        match self.get_string(section, key).await {
+            Err(ConferError::MissingKey { .. }) => Err(f()),
+            other => other,
+        }
+    }
+
+    /// Retrieves an integer value stored at `section.key`.
+    pub async fn get_integer(&self, section: &str, key: &str) -> Result<i64> {
+        let value = self.fetch_scalar(section, key).await?;
+        value_conversion::integer(section, key, value)
+    }
+
+    /// Retrieves an integer like [`Confer::get_integer`], but also accepts whole floats such as
+    /// `8080.0` and strings such as `"8080"`.
+    ///
+    /// Values that cannot be coerced fail with [`ConferError::ValueParse`].
+    pub async fn get_integer_coerce(&self, section: &str, key: &str) -> Result<i64> {
+        let value = self.fetch_scalar(section, key).await?;
+        value_conversion::integer_coerce(section, key, value)
+    }
+
+    /// Retrieves a floating-point value stored at `section.key`.
+    pub async fn get_float(&self, section: &str, key: &str) -> Result<f64> {
+        let value = self.fetch_scalar(section, key).await?;
+        value_conversion::float(section, key, value)
+    }
+
+    /// Retrieves a boolean value stored at `section.key`.
+    pub async fn get_boolean(&self, section: &str, key: &str) -> Result<bool> {
+        let value = self.fetch_scalar(section, key).await?;
+        value_conversion::boolean(section, key, value)
+    }
+
+    /// Retrieves a boolean like [`Confer::get_boolean`], but also accepts the integers `0`/`1`
+    /// and the strings `true`/`false`, `yes`/`no`, `on`/`off`, `1`/`0` in any ASCII case.
+    ///
+    /// Any other value fails with [`ConferError::ValueParse`].
+    pub async fn get_boolean_lenient(&self, section: &str, key: &str) -> Result<bool> {
+        let value = self.fetch_scalar(section, key).await?;
+        value_conversion::boolean_lenient(section, key, value)
+    }
+
+    /// Retrieves a [`Datetime`] value stored at `section.key`, parsing strings when necessary.
+    pub async fn get_datetime(
+        &self,
+        section: &str,
+        key: &str,
+    ) -> Result<Datetime> {
+        let value = self.fetch_scalar(section, key).await?;
+        value_conversion::datetime(section, key, value)
+    }
+
+    /// Retrieves a [`Datetime`] like [`Confer::get_datetime`], but fails with
+    /// [`ConferError::ValueParse`] unless it carries an explicit offset such as `Z` or `+02:00`.
+    ///
+    /// Use it where local datetimes would be ambiguous, e.g. schedules interpreted as UTC.
+    pub async fn get_datetime_offset(&self, section: &str, key: &str) -> Result<Datetime> {
+        let value = self.fetch_scalar(section, key).await?;
+        value_conversion::datetime_offset(section, key, value)
+    }
+
+    /// Retrieves a date-only value such as `2025-01-01` stored at `section.key`.
+    ///
+    /// Values that also carry a time or offset fail with [`ConferError::ValueParse`].
+    pub async fn get_date(&self, section: &str, key: &str) -> Result<Date> {
+        let value = self.fetch_scalar(section, key).await?;
+        value_conversion::date(section, key, value)
+    }
+
+    /// Retrieves a time-only value such as `08:30:00` stored at `section.key`.
+    ///
+    /// Values that also carry a date fail with [`ConferError::ValueParse`].
+    pub async fn get_time(&self, section: &str, key: &str) -> Result<Time> {
+        let value = self.fetch_scalar(section, key).await?;
+        value_conversion::time(section, key, value)
+    }
+
+    /// Retrieves a string array stored at `section.key`.
     pub async fn get_string_vec(&self, section: &str, key: &str) -> Result<Vec<String>> {
-        let value = self.fetch_value(section, key).await?;
+        let value = self.fetch_array(section, key).await?;
         value_conversion::string_vec(section, key, value)
     }
 
-    /// Retrieves an integer array stored at `section.key`.
-    pub async fn get_integer_vec(&self, section: &str, key: &str) -> Result<Vec<i64>> {
-        let value = self.fetch_value(section, key).await?;
-        value_conversion::integer_vec(section, key, value)
+    /// Retrieves an integer array stored at `section.key`.
+    pub async fn get_integer_vec(&self, section: &str, key: &str) -> Result<Vec<i64>> {
+        let value = self.fetch_array(section, key).await?;
+        value_conversion::integer_vec(section, key, value)
+    }
+
+    /// Retrieves an integer array like [`Confer::get_integer_vec`], but also accepts whole
+    /// float elements, so `[1, 2.0, 3]` reads as `[1, 2, 3]`.
+    ///
+    /// Elements with a fractional part fail with [`ConferError::ValueParse`] naming their index.
+    pub async fn get_integer_vec_coerce(&self, section: &str, key: &str) -> Result<Vec<i64>> {
+        let value = self.fetch_array(section, key).await?;
+        value_conversion::integer_vec_coerce(section, key, value)
+    }
+
+    /// Retrieves a floating-point array stored at `section.key`. Integer elements are upcast,
+    /// making this the tolerant accessor for mixed numeric arrays such as `[1, 2.5, 3]`.
+    pub async fn get_float_vec(&self, section: &str, key: &str) -> Result<Vec<f64>> {
+        let value = self.fetch_array(section, key).await?;
+        value_conversion::float_vec(section, key, value)
+    }
+
+    /// Retrieves a boolean array stored at `section.key`.
+    pub async fn get_boolean_vec(&self, section: &str, key: &str) -> Result<Vec<bool>> {
+        let value = self.fetch_array(section, key).await?;
+        value_conversion::boolean_vec(section, key, value)
+    }
+
+    /// Retrieves a [`Datetime`] array stored at `section.key`, parsing string values when necessary.
+    pub async fn get_datetime_vec(
+        &self,
+        section: &str,
+        key: &str,
+    ) -> Result<Vec<Datetime>> {
+        let value = self.fetch_array(section, key).await?;
+        value_conversion::datetime_vec(section, key, value)
+    }
+
+    /// Retrieves bytes stored at `section.key` as a padded base64 string.
+    ///
+    /// Malformed base64 fails with [`ConferError::ValueParse`].
+    pub async fn get_base64(&self, section: &str, key: &str) -> Result<Vec<u8>> {
+        let value = self.fetch_value(section, key).await?;
+        value_conversion::base64_bytes(section, key, value)
+    }
+
+    /// Retrieves bytes stored at `section.key` as a hexadecimal string.
+    ///
+    /// Malformed hex fails with [`ConferError::ValueParse`].
+    pub async fn get_hex(&self, section: &str, key: &str) -> Result<Vec<u8>> {
+        let value = self.fetch_value(section, key).await?;
+        value_conversion::hex_bytes(section, key, value)
+    }
+
+    /// Retrieves a date-only array stored at `section.key`.
+    pub async fn get_date_vec(&self, section: &str, key: &str) -> Result<Vec<Date>> {
+        let value = self.fetch_array(section, key).await?;
+        value_conversion::date_vec(section, key, value)
+    }
+
+    /// Retrieves a time-only array stored at `section.key`.
+    pub async fn get_time_vec(&self, section: &str, key: &str) -> Result<Vec<Time>> {
+        let value = self.fetch_array(section, key).await?;
+        value_conversion::time_vec(section, key, value)
+    }
+
+    /// Retrieves a [`ByteSize`] stored at `section.key` as raw bytes or a string like `"10MB"`.
+    pub async fn get_byte_size(&self, section: &str, key: &str) -> Result<ByteSize> {
+        let value = self.fetch_scalar(section, key).await?;
+        value_conversion::byte_size(section, key, value)
+    }
+
+    /// Retrieves a [`ByteSize`] vector stored at `section.key`.
+    pub async fn get_byte_size_vec(&self, section: &str, key: &str) -> Result<Vec<ByteSize>> {
+        let value = self.fetch_array(section, key).await?;
+        value_conversion::byte_size_vec(section, key, value)
+    }
+
+    /// Retrieves the nested table stored at `section.key`, e.g. `[App.limits]`.
+    pub async fn get_table(&self, section: &str, key: &str) -> Result<Table> {
+        let value = self.fetch_value(section, key).await?;
+        value_conversion::table(section, key, value)
+    }
+
+    /// Stores a string at `section.key`, creating the section if needed.
+    ///
+    /// Strings containing control characters that TOML cannot represent are rejected with
+    /// [`ConferError::ValueParse`].
+    pub async fn set_string(&self, section: &str, key: &str, value: String) -> Result<()> {
+        value_conversion::validate_string(section, key, &value)?;
+        self.set_value(section, key, Value::String(value)).await
+    }
+
+    /// Stores an integer at `section.key`, creating the section if needed.
+    pub async fn set_integer(&self, section: &str, key: &str, value: i64) -> Result<()> {
+        self.set_value(section, key, Value::Integer(value)).await
+    }
+
+    /// Stores a floating-point number at `section.key`, creating the section if needed.
+    pub async fn set_float(&self, section: &str, key: &str, value: f64) -> Result<()> {
+        self.set_value(section, key, Value::Float(value)).await
+    }
+
+    /// Stores a boolean at `section.key`, creating the section if needed.
+    pub async fn set_boolean(&self, section: &str, key: &str, value: bool) -> Result<()> {
+        self.set_value(section, key, Value::Boolean(value)).await
+    }
+
+    /// Stores a TOML [`Datetime`] at `section.key`, creating the section if needed.
+    pub async fn set_datetime(
+        &self,
+        section: &str,
+        key: &str,
+        value: Datetime,
+    ) -> Result<()> {
+        self.set_value(section, key, Value::Datetime(value)).await
+    }
+
+    /// Stores a string array at `section.key`, creating the section if needed.
+    pub async fn set_string_vec(
+        &self,
+        section: &str,
+        key: &str,
+        value: Vec<String>,
+    ) -> Result<()> {
+        for (index, item) in value.iter().enumerate() {
+            value_conversion::validate_string(section, key, item)
+                .map_err(|err| value_conversion::annotate_with_index(err, index))?;
+        }
+        let array = value.into_iter().map(Value::String).collect();
+        self.set_value(section, key, Value::Array(array)).await
+    }
+
+    /// Stores an integer array at `section.key`, creating the section if needed.
+    pub async fn set_integer_vec(
+        &self,
+        section: &str,
+        key: &str,
+        value: Vec<i64>,
+    ) -> Result<()> {
+        let array = value.into_iter().map(Value::Integer).collect();
+        self.set_value(section, key, Value::Array(array)).await
+    }
+
+    /// Stores a floating-point array at `section.key`, creating the section if needed.
+    pub async fn set_float_vec(
+        &self,
+        section: &str,
+        key: &str,
+        value: Vec<f64>,
+    ) -> Result<()> {
+        let array = value.into_iter().map(Value::Float).collect();
+        self.set_value(section, key, Value::Array(array)).await
+    }
+
+    /// Stores a boolean array at `section.key`, creating the section if needed.
+    pub async fn set_boolean_vec(
+        &self,
+        section: &str,
+        key: &str,
+        value: Vec<bool>,
+    ) -> Result<()> {
+        let array = value.into_iter().map(Value::Boolean).collect();
+        self.set_value(section, key, Value::Array(array)).await
+    }
+
+    /// Stores a [`Datetime`] array at `section.key`, creating the section if needed.
+    pub async fn set_datetime_vec(
+        &self,
+        section: &str,
+        key: &str,
+        value: Vec<Datetime>,
+    ) -> Result<()> {
+        let array = value.into_iter().map(Value::Datetime).collect();
+        self.set_value(section, key, Value::Array(array)).await
+    }
+
+    /// Stores `value` at `section.key` as a padded base64 string.
+    pub async fn set_base64(&self, section: &str, key: &str, value: Vec<u8>) -> Result<()> {
+        let encoded = crate::binary::encode_base64(&value);
+        self.set_value(section, key, Value::String(encoded)).await
+    }
+
+    /// Stores `value` at `section.key` as a lowercase hexadecimal string.
+    pub async fn set_hex(&self, section: &str, key: &str, value: Vec<u8>) -> Result<()> {
+        let encoded = crate::binary::encode_hex(&value);
+        self.set_value(section, key, Value::String(encoded)).await
+    }
+
+    /// Stores a date-only value at `section.key`.
+    pub async fn set_date(&self, section: &str, key: &str, value: Date) -> Result<()> {
+        self.set_value(section, key, Value::Datetime(value.into())).await
+    }
+
+    /// Stores a time-only value at `section.key`.
+    pub async fn set_time(&self, section: &str, key: &str, value: Time) -> Result<()> {
+        self.set_value(section, key, Value::Datetime(value.into())).await
+    }
+
+    /// Stores a date-only array at `section.key`.
+    pub async fn set_date_vec(&self, section: &str, key: &str, value: Vec<Date>) -> Result<()> {
+        let array = value.into_iter().map(|date| Value::Datetime(date.into())).collect();
+        self.set_value(section, key, Value::Array(array)).await
+    }
+
+    /// Stores a time-only array at `section.key`.
+    pub async fn set_time_vec(&self, section: &str, key: &str, value: Vec<Time>) -> Result<()> {
+        let array = value.into_iter().map(|time| Value::Datetime(time.into())).collect();
+        self.set_value(section, key, Value::Array(array)).await
+    }
+
+    /// Stores a [`ByteSize`] at `section.key` as a string in its largest exact unit, e.g. `"4GiB"`.
+    pub async fn set_byte_size(&self, section: &str, key: &str, value: ByteSize) -> Result<()> {
+        self.set_value(section, key, Value::String(value.to_string())).await
+    }
+
+    /// Stores a [`ByteSize`] vector at `section.key`, formatting each element like
+    /// [`Confer::set_byte_size`].
+    pub async fn set_byte_size_vec(&self, section: &str, key: &str, value: Vec<ByteSize>) -> Result<()> {
+        let array = value.into_iter().map(|size| Value::String(size.to_string())).collect();
+        self.set_value(section, key, Value::Array(array)).await
+    }
+
+    /// Stores `value` as a nested table at `section.key`, replacing any previous table wholesale.
+    pub async fn set_table(&self, section: &str, key: &str, value: Table) -> Result<()> {
+        self.set_value(section, key, Value::Table(value)).await
+    }
+
+    /// Returns the string at `section.key`, inserting `default` first when the key is absent.
+    pub async fn get_or_insert_string(
+        &self,
+        section: &str,
+        key: &str,
+        default: String,
+    ) -> Result<String> {
+        value_conversion::validate_string(section, key, &default)?;
+        self.get_or_insert_value(section, key, Value::String(default), value_conversion::string)
+            .await
+    }
+
+    /// Returns the integer at `section.key`, inserting `default` first when the key is absent.
+    pub async fn get_or_insert_integer(&self, section: &str, key: &str, default: i64) -> Result<i64> {
+        self.get_or_insert_value(section, key, Value::Integer(default), value_conversion::integer)
+            .await
+    }
+
+    /// Returns the float at `section.key`, inserting `default` first when the key is absent.
+    pub async fn get_or_insert_float(&self, section: &str, key: &str, default: f64) -> Result<f64> {
+        self.get_or_insert_value(section, key, Value::Float(default), value_conversion::float)
+            .await
+    }
+
+    /// Returns the boolean at `section.key`, inserting `default` first when the key is absent.
+    pub async fn get_or_insert_boolean(
+        &self,
+        section: &str,
+        key: &str,
+        default: bool,
+    ) -> Result<bool> {
+        self.get_or_insert_value(section, key, Value::Boolean(default), value_conversion::boolean)
+            .await
+    }
+
+    /// Returns the [`Datetime`] at `section.key`, inserting `default` first when the key is absent.
+    pub async fn get_or_insert_datetime(
+        &self,
+        section: &str,
+        key: &str,
+        default: Datetime,
+    ) -> Result<Datetime> {
+        self.get_or_insert_value(section, key, Value::Datetime(default), value_conversion::datetime)
+            .await
+    }
+
+    /// Converts the value at `section.key`, or inserts and converts `default`, under one write lock.
+    /// An inserted default emits a [`ChangeKind::Set`] event.
+    async fn get_or_insert_value<T>(
+        &self,
+        section: &str,
+        key: &str,
+        default: Value,
+        convert: fn(&str, &str, Value) -> Result<T>,
+    ) -> Result<T> {
+        let mut guard = self.write_table().await;
+        let section_name = self.resolve(&guard, section).into_owned();
+        self.check_section_creation(&guard, &section_name, section)?;
+        let section_value = guard
+            .entry(section_name.clone())
+            .or_insert_with(|| Value::Table(Table::new()));
+        let described = value_conversion::describe(section_value);
+        let table = section_value.as_table_mut().ok_or_else(|| {
+            ConferError::type_mismatch(section, "<section>", "table", described)
+        })?;
+        let key_name = self.resolve(table, key).into_owned();
+        let inserted = !table.contains_key(&key_name);
+        let value = table.entry(key_name.clone()).or_insert(default).clone();
+        if inserted {
+            self.changes.notify(&section_name, Some(&key_name), ChangeKind::Set);
+            guard.mark_modified();
+        }
+        convert(section, key, value)
+    }
+
+    /// Fetches the raw TOML [`Value`] stored at `section.key`, producing detailed errors.
+    async fn fetch_value(&self, section: &str, key: &str) -> Result<Value> {
+        let guard = self.table.read().await;
+        self.lookup_value(&guard, section, key).cloned()
+    }
+
+    /// Fetches `section.key` for a scalar accessor, unwrapping a one-element array when
+    /// [`ConferBuilder::coerce_singleton_arrays`] is enabled.
+    async fn fetch_scalar(&self, section: &str, key: &str) -> Result<Value> {
+        match self.fetch_value(section, key).await? {
+            Value::Array(mut items) if self.options.coerce_singleton_arrays && items.len() == 1 => {
+                Ok(items.remove(0))
+            }
+            value => Ok(value),
+        }
+    }
+
+    /// Fetches `section.key` for an array accessor, wrapping a bare scalar in a one-element
+    /// array when [`ConferBuilder::coerce_singleton_arrays`] is enabled.
+    async fn fetch_array(&self, section: &str, key: &str) -> Result<Value> {
+        match self.fetch_value(section, key).await? {
+            value @ (Value::Array(_) | Value::Table(_)) => Ok(value),
+            value if self.options.coerce_singleton_arrays => Ok(Value::Array(vec![value])),
+            value => Ok(value),
+        }
+    }
+
+    /// Finds `section.key` within `root`, failing like [`Confer::get_value`]'s typed callers.
+    fn lookup_value<'t>(&self, root: &'t Table, section: &str, key: &str) -> Result<&'t Value> {
+        let section_value = root
+            .get(self.resolve(root, section).as_ref())
+            .ok_or_else(|| ConferError::missing_key(section, key))?;
+        let table = section_value.as_table().ok_or_else(|| {
+            ConferError::type_mismatch(
+                section,
+                "<section>",
+                "table",
+                value_conversion::describe(section_value),
+            )
+        })?;
+        table
+            .get(self.resolve(table, key).as_ref())
+            .ok_or_else(|| ConferError::missing_key(section, key))
+    }
+
+    /// Takes the write lock, discarding the values shared by [`Confer::get_arc_value`] and
+    /// [`Confer::section_snapshot`].
+    async fn write_table(&self) -> TableWriteGuard<'_> {
+        let guard = self.table.write().await;
+        self.shared_values.lock().unwrap_or_else(|err| err.into_inner()).clear();
+        self.shared_sections.lock().unwrap_or_else(|err| err.into_inner()).clear();
+        TableWriteGuard {
+            guard,
+            #[cfg(feature = "arc-swap")]
+            modified: false,
+            #[cfg(feature = "arc-swap")]
+            published: &self.published,
+        }
+    }
+
+    /// Fails with [`ConferError::MissingKey`] (key `<section>`) when writing `section`, stored as
+    /// `name`, would create it in a store built with [`ConferBuilder::require_existing_sections`].
+    fn check_section_creation(&self, root: &Table, name: &str, section: &str) -> Result<()> {
+        if self.options.require_existing_sections && !root.contains_key(name) {
+            return Err(ConferError::missing_key(section, "<section>"));
+        }
+        Ok(())
+    }
+
+    /// Merges `value` into `table` at `key` like [`merge_value`], resolving the key at every
+    /// level, and returns the stored spelling of `key`.
+    fn merge_resolved(&self, table: &mut Table, key: &str, value: Value) -> String {
+        let name = self.resolve(table, key).into_owned();
+        match (table.get_mut(&name), value) {
+            (Some(Value::Table(existing)), Value::Table(incoming)) => {
+                for (key, value) in incoming {
+                    self.merge_resolved(existing, &key, value);
+                }
+            }
+            (_, value) => {
+                table.insert(name.clone(), value);
+            }
+        }
+        name
+    }
+
+    /// Returns `true` when `left` and `right` name the same entry, ignoring ASCII case in
+    /// case-insensitive stores.
+    fn same_name(&self, left: &str, right: &str) -> bool {
+        left == right || (self.options.case_insensitive && left.eq_ignore_ascii_case(right))
+    }
+
+    /// Maps `name` onto the stored spelling of a case-insensitive match in `map`.
+    ///
+    /// Returns `name` unchanged when case-insensitive lookup is disabled or an exact match exists.
+    fn resolve<'n>(&self, map: &Table, name: &'n str) -> Cow<'n, str> {
+        if !self.options.case_insensitive || map.contains_key(name) {
+            return Cow::Borrowed(name);
+        }
+        map.keys()
+            .find(|candidate| candidate.eq_ignore_ascii_case(name))
+            .map_or(Cow::Borrowed(name), |candidate| Cow::Owned(candidate.clone()))
+    }
+
+    /// Parses a TOML table from `source`, mapping parsing failures into [`ConferError`].
+    ///
+    /// A leading UTF-8 byte order mark is skipped and CRLF line endings are accepted, so files
+    /// saved by Windows editors load as-is. Tables nested deeper than `max_depth` are rejected.
+    fn parse_table(source: &str, max_depth: usize) -> Result<Table> {
+        let source = strip_bom(source);
+        let table = toml::from_str(source).map_err(|err| ConferError::parse_error(source, err))?;
+        check_depth(&table, max_depth)?;
+        Ok(table)
+    }
+}
+
+/// Removes a single leading UTF-8 byte order mark, leaving any other prefix untouched.
+pub(crate) fn strip_bom(source: &str) -> &str {
+    source.strip_prefix('\u{feff}').unwrap_or(source)
+}
+
+impl FileStamp {
+    /// Stats `path`, capturing its modification time before the file is read.
+    pub(crate) async fn read(path: &Path) -> Result<Self> {
+        let modified = fs::metadata(path)
+            .await
+            .and_then(|metadata| metadata.modified())
+            .map_err(|err| ConferError::io_error(Some(path.to_path_buf()), err))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            modified,
+        })
+    }
+
+    /// Blocking form of [`FileStamp::read`].
+    pub(crate) fn read_sync(path: &Path) -> Result<Self> {
+        let modified = std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .map_err(|err| ConferError::io_error(Some(path.to_path_buf()), err))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            modified,
+        })
+    }
+}
+
+/// Parses a store from TOML, like [`Confer::from_string`] without the [`Arc`].
+///
+/// ```
+/// # fn main() -> conferencier::Result<()> {
+/// let store: conferencier::Confer = "[App]\nport = 8080\n".parse()?;
+/// let shared = std::sync::Arc::new(store);
+/// # let _ = shared;
+/// # Ok(())
+/// # }
+/// ```
+impl FromStr for Confer {
+    type Err = ConferError;
+
+    fn from_str(source: &str) -> Result<Self> {
+        let store = Self::default();
+        store.replace_from_source(source)?;
+        Ok(store)
+    }
+}
+
+impl TryFrom<&str> for Confer {
+    type Error = ConferError;
+
+    fn try_from(source: &str) -> Result<Self> {
+        source.parse()
+    }
+}
+
+/// Serializes the whole table as a map of sections.
+///
+/// `Serialize` is synchronous, so the table is read with `try_read` instead of awaiting the
+/// lock: serialization fails while a writer holds or waits for it. In async code, prefer
+/// serializing a clone taken through [`Confer::with_table`]. With the `arc-swap` feature the last
+/// published table is serialized instead, which never fails on contention.
+impl serde::Serialize for Confer {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        #[cfg(feature = "arc-swap")]
+        {
+            self.published.load().serialize(serializer)
+        }
+        #[cfg(not(feature = "arc-swap"))]
+        {
+            match self.table.try_read() {
+                Ok(table) => table.serialize(serializer),
+                Err(_) => Err(serde::ser::Error::custom("configuration store is locked for writing")),
+            }
+        }
+    }
+}
+
+/// Keys a module's load snapshot by the address of its shared allocation.
+fn snapshot_id(module: &Arc<dyn Any + Send + Sync>) -> usize {
+    Arc::as_ptr(module) as *const () as usize
+}
+
+/// Returns the snapshot recorded for `module`, ignoring an entry left by a dropped module whose
+/// address was reused.
+fn live_snapshot<'s>(
+    snapshots: &'s HashMap<usize, LoadSnapshot>,
+    module: &Arc<dyn Any + Send + Sync>,
+) -> Option<&'s LoadSnapshot> {
+    snapshots.get(&snapshot_id(module)).filter(|snapshot| {
+        snapshot
+            .module
+            .upgrade()
+            .is_some_and(|recorded| Arc::ptr_eq(&recorded, module))
+    })
+}
+
+/// Stores `value` at `key`, recursing into tables present on both sides instead of replacing them.
+fn merge_value(table: &mut Table, key: String, value: Value) {
+    match (table.get_mut(&key), value) {
+        (Some(Value::Table(existing)), Value::Table(incoming)) => {
+            for (key, value) in incoming {
+                merge_value(existing, key, value);
+            }
+        }
+        (_, value) => {
+            table.insert(key, value);
+        }
+    }
+}
+
+/// Recursively reports the scalars below `value`, extending `path` with keys and indices.
+fn walk_leaves(path: &mut Vec<String>, value: &Value, f: &mut impl FnMut(&[&str], &Value)) {
+    match value {
+        Value::Table(table) => {
+            for (key, value) in table {
+                path.push(key.clone());
+                walk_leaves(path, value, f);
+                path.pop();
+            }
+        }
+        Value::Array(items) => {
+            for (index, value) in items.iter().enumerate() {
+                path.push(index.to_string());
+                walk_leaves(path, value, f);
+                path.pop();
+            }
+        }
+        leaf => {
+            let parts: Vec<&str> = path.iter().map(String::as_str).collect();
+            f(&parts, leaf);
+        }
+    }
+}
+
+/// Retrieves the table stored within `root` at `section`, if it exists and is a table.
+fn section_table<'a>(root: &'a Table, section: &str) -> Option<&'a Table> {
+    root.get(section)?.as_table()
+}
+
+/// Refuses to replace a nested table or array at `section.key` with a scalar.
+fn check_replacement(section: &str, key: &str, existing: Option<&Value>, value: &Value) -> Result<()> {
+    match existing {
+        Some(existing @ (Value::Table(_) | Value::Array(_)))
+            if !matches!(value, Value::Table(_) | Value::Array(_)) =>
+        {
+            Err(ConferError::type_mismatch(
+                section,
+                key,
+                value_conversion::describe(existing),
+                value_conversion::describe(value),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Looks up `section`, treating a missing section as `None` and a non-table value as an error.
+fn existing_section<'a>(root: &'a Table, section: &str) -> Result<Option<&'a Table>> {
+    match root.get(section) {
+        Some(Value::Table(table)) => Ok(Some(table)),
+        Some(other) => Err(ConferError::type_mismatch(
+            section,
+            "<section>",
+            "table",
+            value_conversion::describe(other),
+        )),
+        None => Ok(None),
+    }
+}
+/// Fails with [`ConferError::ValueParse`] naming the first entry of `root` that nests deeper
+/// than `limit`.
+fn check_depth(root: &Table, limit: usize) -> Result<()> {
+    for (section, value) in root {
+        if !exceeds_depth(value, 1, limit) {
+            continue;
+        }
+        let key = match value {
+            Value::Table(inner) => inner
+                .iter()
+                .find(|(_, value)| exceeds_depth(value, 2, limit))
+                .map_or("<section>", |(key, _)| key.as_str()),
+            _ => "<section>",
+        };
+        return Err(depth_error(section, key, limit));
+    }
+    Ok(())
+}
+
+/// Returns `true` when `value`, found at nesting `depth`, contains containers deeper than `limit`.
+///
+/// Recursion stops at `limit`, so the check itself cannot overflow the stack.
+fn exceeds_depth(value: &Value, depth: usize, limit: usize) -> bool {
+    match value {
+        Value::Table(table) => {
+            depth > limit || table.values().any(|child| exceeds_depth(child, depth + 1, limit))
+        }
+        Value::Array(items) => {
+            depth > limit || items.iter().any(|child| exceeds_depth(child, depth + 1, limit))
+        }
+        _ => false,
+    }
+}
+
+/// Builds the error reported when input nests deeper than the configured limit.
+fn depth_error(section: &str, key: &str, limit: usize) -> ConferError {
+    ConferError::value_parse_owned(
+        section,
+        key,
+        format!("maximum nesting depth of {limit} exceeded"),
+    )
+}
+
+/// Recursively counts the non-table values contained in `table`.
+fn count_leaves(table: &Table) -> usize {
+    table
+        .values()
+        .map(|value| match value {
+            Value::Table(inner) => count_leaves(inner),
+            _ => 1,
+        })
+        .sum()
+}
+
+/// Hashes a table through its canonical TOML serialization.
+fn hash_table(table: &Table) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    table.to_string().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Writes `contents` to a sibling temporary file, syncs it, and renames it over `path`.
+///
+/// The parent directory is synced after the rename so the new file survives a crash. When the
+/// rename would cross devices the temporary file is copied over `path` instead, which is
+/// durable but not atomic.
+async fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    write_atomic_with(path, contents, &SaveOptions::default()).await
+}
+
+/// [`write_atomic`] with the temporary write and the rename retried according to `options`.
+async fn write_atomic_with(path: &Path, contents: &[u8], options: &SaveOptions) -> Result<()> {
+    let tmp_path = temporary_path(path);
+    if let Err(err) = options.retry(|| write_synced(&tmp_path, contents)).await {
+        let _ = fs::remove_file(&tmp_path).await;
+        return Err(ConferError::io_error(Some(tmp_path), err));
+    }
+
+    let replaced = match options.retry(|| fs::rename(&tmp_path, path)).await {
+        Err(err) if err.kind() == ErrorKind::CrossesDevices => {
+            let copied = copy_synced(&tmp_path, path).await;
+            let _ = fs::remove_file(&tmp_path).await;
+            copied
+        }
+        Err(err) => {
+            let _ = fs::remove_file(&tmp_path).await;
+            Err(err)
+        }
+        Ok(()) => Ok(()),
+    };
+    replaced.map_err(|err| ConferError::io_error(Some(path.to_path_buf()), err))?;
+    sync_parent_dir(path)
+        .await
+        .map_err(|err| ConferError::io_error(Some(path.to_path_buf()), err))
+}
+
+/// Creates or truncates `path`, writes `contents`, and flushes them to disk.
+async fn write_synced(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut file = fs::File::create(path).await?;
+    file.write_all(contents).await?;
+    file.sync_all().await
+}
+
+/// Copies `from` over `to` and flushes the copy to disk.
+async fn copy_synced(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::copy(from, to).await?;
+    fs::OpenOptions::new().write(true).open(to).await?.sync_all().await
+}
+
+/// Syncs the directory containing `path` so a completed rename is durable.
+#[cfg(unix)]
+async fn sync_parent_dir(path: &Path) -> std::io::Result<()> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::File::open(parent).await?.sync_all().await
+}
+
+/// Directory handles cannot be synced on this platform; the rename itself is relied upon.
+#[cfg(not(unix))]
+async fn sync_parent_dir(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Computes a temporary sibling path used during atomic write operations.
+fn temporary_path(path: &Path) -> PathBuf {
+    let mut file_name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_else(|| "conferencier".into());
+    file_name.push(".tmp");
+
+    path.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{ConferError, Result};
+
+    use tempfile::NamedTempFile;
+
+    #[tokio::test]
+    async fn new_store_is_empty() {
+        let store = Confer::new();
+        assert!(store.list_sections().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn set_and_get_string_roundtrip() -> Result<()> {
+        let store = Confer::new();
+        store.set_string("App", "name", "demo".into()).await?;
+        assert_eq!(store.get_string("App", "name").await?, "demo");
+        assert_eq!(
+            store.get_value("App", "name").await,
+            Some(Value::String("demo".into()))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn datetime_fallback_from_string() -> Result<()> {
+        let store = Confer::new();
+        store
+            .set_string("Build", "time", "2024-01-01T00:00:00Z".into())
+            .await?;
+        let dt = store.get_datetime("Build", "time").await?;
+        assert_eq!(dt.to_string(), "2024-01-01T00:00:00Z");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn float_vec_accepts_integers() -> Result<()> {
+        let store = Confer::new();
+        store
+            .set_value(
+                "App",
+                "thresholds",
+                Value::Array(vec![Value::Integer(1), Value::Float(2.5)]),
+            )
+            .await?;
+        let values = store.get_float_vec("App", "thresholds").await?;
+        assert_eq!(values, vec![1.0, 2.5]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn set_string_rejects_control_characters() -> Result<()> {
+        let store = Confer::new();
+        let err = store
+            .set_string("App", "name", "bad\u{0001}name".into())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ConferError::ValueParse { ref key, .. } if key == "name"));
+        assert!(store.get_value("App", "name").await.is_none());
+
+        let err = store
+            .set_string_vec("App", "tags", vec!["ok".into(), "\u{007f}".into()])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("at index 1"));
+
+        store.set_string("App", "motd", "line\tone\nline two".into()).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn section_hash_tracks_changes() -> Result<()> {
+        let store = Confer::new();
+        assert!(store.section_hash("App").await.is_none());
+
+        store.set_integer("App", "port", 80).await?;
+        let first = store.section_hash("App").await;
+        assert_eq!(first, store.section_hash("App").await);
+
+        store.set_integer("App", "port", 81).await?;
+        assert_ne!(first, store.section_hash("App").await);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn load_snapshots_drop_entries_of_dropped_modules() -> Result<()> {
+        let store = Confer::from_string("[App]\nport = 1\n")?;
+        let first: Arc<dyn std::any::Any + Send + Sync> = Arc::new(1_u8);
+        let current = store.section_hash("App").await;
+        store.record_load_snapshot(first.clone(), "App").await;
+        assert!(store.snapshot_hash_matches(&first, "App", current));
+        drop(first);
+
+        let second: Arc<dyn std::any::Any + Send + Sync> = Arc::new(2_u8);
+        store.record_load_snapshot(second.clone(), "App").await;
+        assert_eq!(store.load_snapshots.lock().unwrap().len(), 1);
+        assert!(store.snapshot_hash_matches(&second, "App", current));
+        let third: Arc<dyn std::any::Any + Send + Sync> = Arc::new(3_u8);
+        assert!(!store.snapshot_hash_matches(&third, "App", current));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn record_snapshot_of_keeps_the_loaded_copy() -> Result<()> {
+        let store = Confer::from_string("[App]\nport = 1\n")?;
+        let module: Arc<dyn std::any::Any + Send + Sync> = Arc::new(1_u8);
+        let snapshot = store.snapshot_section("App").await;
+        store.set_integer("App", "port", 2).await?;
+
+        store.record_snapshot_of(module.clone(), "App", &snapshot).await;
+        assert_eq!(store.load_snapshot_table(module.clone(), "App"), snapshot.get_section_table("App").await);
+        assert!(!store.snapshot_hash_matches(&module, "App", store.section_hash("App").await));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn commit_if_unchanged_rejects_writes_landing_after_the_snapshot() -> Result<()> {
+        let store = Confer::from_string("[App]\nport = 1\nstale = true\n")?;
+        let module: Arc<dyn std::any::Any + Send + Sync> = Arc::new(1_u8);
+        store.record_load_snapshot(module.clone(), "App").await;
+        let rendered: Table = toml::from_str("port = 2\n")?;
+        let owned = |key: &str| key == "port";
+
+        store.set_integer("App", "port", 5).await?;
+        assert!(!store.commit_if_unchanged(module.clone(), "App", rendered.clone(), owned, None).await?);
+        assert_eq!(store.get_integer("App", "port").await?, 5);
+
+        store.record_load_snapshot(module.clone(), "App").await;
+        assert!(store.commit_if_unchanged(module.clone(), "App", rendered, owned, Some(&["port"])).await?);
+        assert_eq!(store.list_keys("App").await?, ["port"]);
+        assert_eq!(store.get_integer("App", "port").await?, 2);
+        assert!(store.commit_if_unchanged(module, "App", Table::new(), owned, None).await?);
+        assert!(store.list_keys("App").await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn total_values_counts_nested_leaves() -> Result<()> {
+        let store = Confer::from_string(
+            "top = 1\n[App]\nname = \"demo\"\ntags = [\"a\", \"b\"]\n[App.limits]\ncpu = 2\n[Empty]\n",
+        )?;
+        assert_eq!(store.total_values().await, 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_or_insert_returns_existing_or_default() -> Result<()> {
+        let store = Confer::from_string("[Srv]\nport = 8080\n")?;
+        assert_eq!(store.get_or_insert_integer("Srv", "port", 80).await?, 8080);
+        assert_eq!(store.get_or_insert_integer("Srv", "workers", 4).await?, 4);
+        assert_eq!(store.get_integer("Srv", "workers").await?, 4);
+        assert!(store.get_or_insert_boolean("Flags", "beta", true).await?);
+        assert!(store.get_boolean("Flags", "beta").await?);
+
+        let err = store
+            .get_or_insert_string("Srv", "port", "x".into())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ConferError::TypeMismatch { expected: "string", .. }));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn save_str_redacted_masks_requested_paths() -> Result<()> {
+        let store = Confer::from_string("[Auth]\nuser = \"admin\"\npassword = \"hunter2\"\n")?;
+        let dump = store
+            .save_str_redacted(&["Auth.password", "Auth.missing", "Nope.key"])
+            .await?;
+        assert!(dump.contains("user = \"admin\""));
+        assert!(dump.contains("password = \"***\""));
+        assert!(!dump.contains("hunter2"));
+        assert_eq!(store.get_string("Auth", "password").await?, "hunter2");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn save_str_redacted_keeps_comments_of_preserved_documents() -> Result<()> {
+        let source = "[Auth]\n# login\nuser = \"admin\"\npassword = \"hunter2\" # rotate monthly\n";
+        let store = Confer::builder().preserve_format(true).build_from_str(source)?;
+
+        let dump = store.save_str_redacted(&["Auth.password"]).await?;
+        assert_eq!(dump, "[Auth]\n# login\nuser = \"admin\"\npassword = \"***\" # rotate monthly\n");
+        Ok(())
+    }
+
+    #[test]
+    fn parse_errors_report_location() {
+        let err = Confer::from_string("[App]\nname = \"demo\"\nport = = 1\n").unwrap_err();
+        let location = err.parse_location().expect("location");
+        assert_eq!(location.line, 3);
+        assert_eq!(location.snippet, "port = = 1");
+        assert!(err.to_string().starts_with(&format!("3:{}: failed to parse TOML", location.column)));
+    }
+
+    #[tokio::test]
+    async fn apply_schema_version_migrates_older_sections() -> Result<()> {
+        let store = Confer::from_string("[App]\nold = 1\n[Next]\n__version = 9\n")?;
+        store
+            .apply_schema_version("App", "__version", 2, Some(|table: &mut Table, from: u32| {
+                assert_eq!(from, 0);
+                let old = table.remove("old").expect("old key");
+                table.insert("new".into(), old);
+                Ok(())
+            }))
+            .await?;
+        assert_eq!(store.get_integer("App", "new").await?, 1);
+        assert_eq!(store.get_integer("App", "__version").await?, 2);
+
+        let err = store
+            .apply_schema_version::<fn(&mut Table, u32) -> Result<()>>("Next", "__version", 2, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ConferError::ValueParse { .. }));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn missing_key_yields_error() {
+        let store = Confer::new();
+        let err = store.get_string("Missing", "key").await.unwrap_err();
+        assert!(matches!(err, ConferError::MissingKey { .. }));
+    }
+
+    #[tokio::test]
+    async fn get_string_or_else_replaces_only_missing_key() -> Result<()> {
+        let store = Confer::from_string("[Db]\nport = 5432\n")?;
+        let custom = || ConferError::value_parse("Db", "url", "DATABASE_URL must be configured");
+
+        let err = store.get_string_or_else("Db", "url", custom).await.unwrap_err();
+        assert!(err.to_string().contains("DATABASE_URL must be configured"));
+
+        let err = store.get_string_or_else("Db", "port", custom).await.unwrap_err();
+        assert!(matches!(err, ConferError::TypeMismatch { .. }));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn load_str_replaces_content() -> Result<()> {
+        let store = Confer::new();
+        store.set_string("App", "name", "demo".into()).await?;
+        store.load_str("[Srv]\nport = 8080\n").await?;
+
+        assert!(store.get_value("App", "name").await.is_none());
+        assert_eq!(store.get_integer("Srv", "port").await?, 8080);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn save_file_replaces_existing_file_without_leftovers() -> Result<()> {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("app.toml");
+        let store = Confer::from_string("[App]\nport = 1\n")?;
+        store.save_file(&path).await?;
+        store.set_integer("App", "port", 2).await?;
+        store.save_file(&path).await?;
+
+        assert_eq!(std::fs::read_to_string(&path)?, "[App]\nport = 2\n");
+        assert!(!temporary_path(&path).exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn parses_through_standard_conversions() -> Result<()> {
+        let store: Confer = "[App]\nport = 8080\n".parse()?;
+        assert_eq!(store.get_integer("App", "port").await?, 8080);
+        let store = Confer::try_from("[App]\nport = 1\n")?;
+        assert_eq!(store.get_integer("App", "port").await?, 1);
+        assert!(matches!("[App".parse::<Confer>(), Err(ConferError::Parse { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn nesting_beyond_max_depth_is_rejected() -> Result<()> {
+        let store = Confer::builder().max_depth(3).build();
+        store.load_str("[App]\nlimits = { cpu = [1, 2] }\n").await?;
+
+        let err = store.load_str("[App]\nlimits = { cpu = [[1], [2]] }\n").await.unwrap_err();
+        assert!(matches!(err, ConferError::ValueParse { ref section, ref key, .. } if section == "App" && key == "limits"));
+        assert!(err.to_string().contains("maximum nesting depth of 3 exceeded"), "{err}");
+        assert!(store.contains_key("App", "limits").await);
+
+        let deep = Value::Array(vec![Value::Array(vec![Value::Array(Vec::new())])]);
+        let err = store.set_value("App", "deep", deep.clone()).await.unwrap_err();
+        assert!(matches!(err, ConferError::ValueParse { .. }));
+        let err = store.set_many("App", [("deep".to_string(), deep)]).await.unwrap_err();
+        assert!(matches!(err, ConferError::ValueParse { .. }));
+
+        let default_store = Confer::new();
+        let deep = (0..200).fold(Value::Integer(1), |inner, _| Value::Array(vec![inner]));
+        let err = default_store.set_value("App", "deep", deep).await.unwrap_err();
+        assert!(err.to_string().contains("maximum nesting depth of 128 exceeded"), "{err}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn export_and_import_sections() -> Result<()> {
+        let source = Confer::from_string("title = \"demo\"\n[App]\nport = 1\n[Db]\nurl = \"x\"\n")?;
+        let exported = source.sections().await;
+        assert_eq!(exported.len(), 2);
+
+        let target = Confer::from_string("[App]\nport = 2\n")?;
+        let err = target.import_sections(exported.clone(), false).await.unwrap_err();
+        assert!(matches!(err, ConferError::SectionExists { .. }));
+        assert!(!target.section_exists("Db").await);
+
+        target.import_sections(exported.clone(), true).await?;
+        assert_eq!(target.get_integer("App", "port").await?, 1);
+        assert_eq!(target.get_string("Db", "url").await?, "x");
+
+        let err = source
+            .import_sections([("title".to_string(), Table::new())], true)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ConferError::TypeMismatch { .. }));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn take_key_returns_removed_value() -> Result<()> {
+        let store = Confer::from_string("title = \"demo\"\n[App]\nport = 8080\n")?;
+        assert_eq!(store.take_key("App", "port").await?, Some(Value::Integer(8080)));
+        assert_eq!(store.take_key("App", "port").await?, None);
+        assert_eq!(store.take_key("Missing", "port").await?, None);
+        assert!(matches!(store.take_key("title", "port").await, Err(ConferError::TypeMismatch { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn contains_key_and_value_type() -> Result<()> {
+        let store = Confer::from_string("title = \"demo\"\n[App]\nport = 1\nhosts = [\"a\"]\n")?;
+        assert!(store.contains_key("App", "port").await);
+        assert!(!store.contains_key("App", "missing").await);
+        assert!(!store.contains_key("title", "port").await);
+        assert_eq!(store.value_type("App", "port").await, Some("integer"));
+        assert_eq!(store.value_type("App", "hosts").await, Some("array"));
+        assert_eq!(store.value_type("Missing", "port").await, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn set_many_inserts_all_or_nothing() -> Result<()> {
+        let store = Confer::from_string("title = \"demo\"\n[App]\nports = [1, 2]\n")?;
+        store
+            .set_many(
+                "Seed",
+                [("a".to_string(), Value::Integer(1)), ("b".to_string(), Value::Boolean(true))],
+            )
+            .await?;
+        assert_eq!(store.key_count("Seed").await?, 2);
+
+        let err = store
+            .set_many("title", [("a".to_string(), Value::Integer(1))])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ConferError::TypeMismatch { .. }));
+
+        let err = store
+            .set_many(
+                "App",
+                [("name".to_string(), Value::from("x")), ("ports".to_string(), Value::Integer(3))],
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ConferError::TypeMismatch { expected: "array", .. }));
+        assert_eq!(store.get_value("App", "name").await, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn counts_sections_and_keys() -> Result<()> {
+        let store = Confer::new();
+        assert!(store.is_empty().await);
+        assert_eq!(store.section_count().await, 0);
+
+        store.load_str("title = \"demo\"\n[App]\nport = 1\nhost = \"x\"\n[Empty]\n").await?;
+        assert!(!store.is_empty().await);
+        assert_eq!(store.section_count().await, 2);
+        assert_eq!(store.key_count("App").await?, 2);
+        assert_eq!(store.key_count("Empty").await?, 0);
+        assert_eq!(store.key_count("Missing").await?, 0);
+        assert!(matches!(store.key_count("title").await, Err(ConferError::TypeMismatch { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn list_keys_missing_section_empty() -> Result<()> {
+        let store = Confer::new();
+        assert!(store.list_keys("Unknown").await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sections_skip_non_table_entries() -> Result<()> {
+        let store = Confer::from_string("top = 1\n[A]\nx = 1\n[B]\ny = 2\nz = 3\n")?;
+        let sections = store.sections().await;
+        let names: Vec<_> = sections.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["A", "B"]);
+        assert_eq!(sections[1].1.len(), 2);
+
+        let mut total_keys = 0;
+        store.for_each_section(|_, table| total_keys += table.len()).await;
+        assert_eq!(total_keys, 3);
+        Ok(())
     }
 
-    /// Retrieves a floating-point array stored at `section.key`.
-    pub async fn get_float_vec(&self, section: &str, key: &str) -> Result<Vec<f64>> {
-        let value = self.fetch_value(section, key).await?;
-        value_conversion::float_vec(section, key, value)
+    #[tokio::test]
+    async fn entries_return_pairs_and_types() -> Result<()> {
+        let store = Confer::from_string("scalar = 1\n[App]\nport = 80\nname = \"demo\"\n")?;
+        assert_eq!(
+            store.entries("App").await?,
+            vec![
+                ("name".to_string(), Value::String("demo".into())),
+                ("port".to_string(), Value::Integer(80)),
+            ]
+        );
+        let typed = store.entries_typed("App").await?;
+        assert_eq!(typed[1].1, "integer");
+        assert!(store.entries("Missing").await?.is_empty());
+        assert!(matches!(
+            store.entries("scalar").await,
+            Err(ConferError::TypeMismatch { .. })
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn add_section_is_idempotent() -> Result<()> {
+        let store = Confer::new();
+        store.add_section("Srv").await?;
+        store.add_section("Srv").await?;
+        assert!(store.section_exists("Srv").await);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn remove_key_missing_is_ok() -> Result<()> {
+        let store = Confer::new();
+        store.remove_key("Srv", "unknown").await?;
+        store.add_section("Srv").await?;
+        store.remove_key("Srv", "missing").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn clear_and_clear_section() -> Result<()> {
+        let store = Confer::from_string("[App]\nname = \"demo\"\n[Srv]\nport = 1\n")?;
+        store.clear_section("App").await?;
+        store.clear_section("Missing").await?;
+        assert!(store.section_exists("App").await);
+        assert!(store.list_keys("App").await?.is_empty());
+        assert_eq!(store.get_integer("Srv", "port").await?, 1);
+
+        store.clear().await;
+        assert!(store.list_sections().await.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn copy_and_move_sections() -> Result<()> {
+        let store = Confer::from_string(
+            "[template]\nhosts = [\"a\"]\n[template.limits]\ncpu = 2\n[tenant_b]\nx = 1\n",
+        )?;
+        store.copy_section("template", "tenant_a", false).await?;
+        store.set_string_vec("tenant_a", "hosts", vec!["b".into()]).await?;
+        assert_eq!(store.get_string_vec("template", "hosts").await?, vec!["a".to_string()]);
+        assert_eq!(
+            store.get_section_table("tenant_a").await,
+            Some(toml::from_str("hosts = [\"b\"]\n[limits]\ncpu = 2\n")?)
+        );
+
+        let err = store.copy_section("template", "tenant_b", false).await.unwrap_err();
+        assert!(matches!(err, ConferError::SectionExists { .. }));
+        let err = store.copy_section("missing", "other", false).await.unwrap_err();
+        assert!(matches!(err, ConferError::MissingKey { .. }));
+
+        store.move_section("tenant_a", "tenant_b", true).await?;
+        assert!(!store.section_exists("tenant_a").await);
+        assert_eq!(store.get_string_vec("tenant_b", "hosts").await?, vec!["b".to_string()]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn binary_accessors_encode_strings() -> Result<()> {
+        let store = Confer::new();
+        store.set_base64("Tls", "key", b"secret".to_vec()).await?;
+        store.set_hex("Tls", "fingerprint", vec![0xde, 0xad]).await?;
+        assert_eq!(store.get_string("Tls", "key").await?, "c2VjcmV0");
+        assert_eq!(store.get_string("Tls", "fingerprint").await?, "dead");
+        assert_eq!(store.get_base64("Tls", "key").await?, b"secret");
+        assert_eq!(store.get_hex("Tls", "fingerprint").await?, [0xde, 0xad]);
+        assert!(matches!(store.get_hex("Tls", "key").await, Err(ConferError::ValueParse { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn byte_size_accessors_parse_units_and_raw_bytes() -> Result<()> {
+        let store = Confer::from_string(
+            "[Http]\nmax_body = \"10MB\"\nraw = 512\nnegative = -1\nlimits = [\"1KiB\", 2048]\n",
+        )?;
+        assert_eq!(store.get_byte_size("Http", "max_body").await?, ByteSize(10_000_000));
+        assert_eq!(store.get_byte_size("Http", "raw").await?, ByteSize(512));
+        assert_eq!(
+            store.get_byte_size_vec("Http", "limits").await?,
+            [ByteSize(1024), ByteSize(2048)]
+        );
+        assert!(matches!(
+            store.get_byte_size("Http", "negative").await,
+            Err(ConferError::ValueParse { .. })
+        ));
+
+        store.set_byte_size("Http", "max_body", ByteSize(4 << 30)).await?;
+        assert_eq!(store.get_string("Http", "max_body").await?, "4GiB");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reload_if_changed_tracks_modification_time() -> Result<()> {
+        let temp = NamedTempFile::new().expect("temp file");
+        std::fs::write(temp.path(), "[App]\nport = 1\n")?;
+        let store = Confer::from_file(temp.path())?;
+        assert!(!store.reload_if_changed(temp.path()).await?);
+
+        std::fs::write(temp.path(), "[App]\nport = 2\n")?;
+        let file = std::fs::File::options().write(true).open(temp.path())?;
+        file.set_modified(SystemTime::now() + Duration::from_secs(60))?;
+        assert!(store.reload_if_changed(temp.path()).await?);
+        assert_eq!(store.get_integer("App", "port").await?, 2);
+        assert!(!store.reload_if_changed(temp.path()).await?);
+
+        let empty = Confer::new();
+        assert!(empty.reload_if_changed(temp.path()).await?);
+        assert_eq!(empty.get_integer("App", "port").await?, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn save_file_with_applies_options() -> Result<()> {
+        let store = Confer::new();
+        store.set_float("App", "ratio", 0.1 + 0.2).await?;
+        let temp = NamedTempFile::new().expect("temp file");
+        let options = SaveOptions::default()
+            .float_precision(2)
+            .retries(3)
+            .backoff(Duration::from_millis(1));
+        store.save_file_with(temp.path(), &options).await?;
+        assert_eq!(std::fs::read_to_string(temp.path())?, "[App]\nratio = 0.3\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn save_str_with_rounds_floats_only() -> Result<()> {
+        let store = Confer::from_string("[App]\nport = 8080\nname = \"demo\"\n")?;
+        store.set_float("App", "ratio", 0.1 + 0.2).await?;
+        store.set_float_vec("App", "weights", vec![1.0 / 3.0, 2.5]).await?;
+
+        let options = SaveOptions::default().float_precision(3);
+        let rendered = store.save_str_with(&options).await?;
+        assert!(rendered.contains("ratio = 0.3\n"));
+        assert!(rendered.contains("weights = [0.333, 2.5]"));
+        assert!(rendered.contains("port = 8080\n"));
+        assert!(rendered.contains("name = \"demo\"\n"));
+        assert_eq!(store.get_float("App", "ratio").await?, 0.1 + 0.2);
+
+        let unchanged = store.save_str_with(&SaveOptions::default()).await?;
+        assert_eq!(unchanged, store.save_str().await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn save_str_with_splits_long_arrays() -> Result<()> {
+        let store = Confer::new();
+        store.set_string_vec("App", "hosts", vec!["a".into(), "b".into(), "c".into()]).await?;
+        store.set_integer_vec("App", "ports", vec![80, 443]).await?;
+
+        let options = SaveOptions::default().multiline_arrays(true).multiline_threshold(2);
+        assert_eq!(
+            store.save_str_with(&options).await?,
+            "[App]\nhosts = [\n    \"a\",\n    \"b\",\n    \"c\",\n]\nports = [80, 443]\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn save_str_prune_empty_drops_emptied_sections() -> Result<()> {
+        let store = Confer::from_string("[App]\nname = \"demo\"\n[Old]\nkey = 1\n")?;
+        store.remove_key("Old", "key").await?;
+
+        assert!(store.save_str().await?.contains("[Old]"));
+        let pruned = store.save_str_prune_empty().await?;
+        assert!(!pruned.contains("[Old]"));
+        assert!(pruned.contains("[App]"));
+        assert!(store.section_exists("Old").await);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn save_str_prune_empty_keeps_comments_of_preserved_documents() -> Result<()> {
+        let source = "# app settings\n[App]\nname = \"demo\" # shown in the title\n[Old]\nkey = 1\n";
+        let store = Confer::builder().preserve_format(true).build_from_str(source)?;
+        store.remove_key("Old", "key").await?;
+
+        let pruned = store.save_str_prune_empty().await?;
+        assert_eq!(pruned, "# app settings\n[App]\nname = \"demo\" # shown in the title\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn save_and_load_file_roundtrip() -> Result<()> {
+        let store = Confer::new();
+        store.set_string("App", "name", "demo".into()).await?;
+        store.set_integer("App", "port", 3000).await?;
+
+        let temp = NamedTempFile::new().expect("temp file");
+        let path_buf = temp.path().to_path_buf();
+        store.save_file(&path_buf).await?;
+
+        let restored = Confer::from_file(&path_buf)?;
+        assert_eq!(restored.get_string("App", "name").await?, "demo");
+        assert_eq!(restored.get_integer("App", "port").await?, 3000);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn from_file_async_timeout_reads_file() -> Result<()> {
+        let temp = NamedTempFile::new().expect("temp file");
+        std::fs::write(temp.path(), "[App]\nport = 1\n")?;
+
+        let store = Confer::from_file_async_timeout(temp.path(), Duration::from_secs(30)).await?;
+        assert_eq!(store.get_integer("App", "port").await?, 1);
+        assert!(!store.reload_if_changed(temp.path()).await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sorted_listings_are_alphabetical() -> Result<()> {
+        let store = Confer::new();
+        for section in ["Zeta", "Alpha", "Mid"] {
+            store.set_integer(section, "zz", 1).await?;
+            store.set_integer(section, "aa", 2).await?;
+        }
+        store.set_integer("Alpha", "mm", 3).await?;
+
+        assert_eq!(store.list_sections_sorted().await, ["Alpha", "Mid", "Zeta"]);
+        assert_eq!(store.list_keys_sorted("Alpha").await?, ["aa", "mm", "zz"]);
+        assert!(store.list_keys_sorted("Missing").await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reader_and_writer_round_trip() -> Result<()> {
+        let store = Confer::from_reader(&b"[App]\nport = 7\n"[..]).await?;
+        assert_eq!(store.get_integer("App", "port").await?, 7);
+
+        let mut output = Vec::new();
+        store.to_writer(&mut output).await?;
+        assert_eq!(output, b"[App]\nport = 7\n");
+
+        let restored = Confer::from_std_reader(std::io::Cursor::new(output))?;
+        assert_eq!(restored.get_integer("App", "port").await?, 7);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn from_slice_parses_bytes_and_rejects_invalid_utf8() -> Result<()> {
+        let store = Confer::from_slice(b"[App]\nport = 1\n")?;
+        assert_eq!(store.get_integer("App", "port").await?, 1);
+
+        let err = Confer::from_slice(b"[App]\nname = \"\xff\"\n").unwrap_err();
+        assert!(matches!(err, ConferError::Io { ref source, .. } if source.kind() == ErrorKind::InvalidData));
+        Ok(())
+    }
+
+    #[cfg(feature = "arc-swap")]
+    #[tokio::test]
+    async fn read_snapshot_tracks_completed_writes() -> Result<()> {
+        let store = Confer::from_string("[App]\nport = 1\n")?;
+        let before = store.read_snapshot();
+        assert_eq!(store.get_snapshot::<u16>("App", "port")?, 1);
+
+        store.set_integer("App", "port", 2).await?;
+        store.patch("[Db]\nhost = \"db\"\n").await?;
+        assert_eq!(store.get_snapshot::<u16>("App", "port")?, 2);
+        assert_eq!(store.get_snapshot::<String>("Db", "host")?, "db");
+        assert_eq!(before["App"]["port"].as_integer(), Some(1));
+        assert!(matches!(
+            store.get_snapshot::<u16>("App", "missing"),
+            Err(ConferError::MissingKey { .. })
+        ));
+
+        let published = store.read_snapshot();
+        assert!(store.copy_section("Missing", "Copy", false).await.is_err());
+        assert!(!store.remove_section("Missing").await?);
+        assert!(Arc::ptr_eq(&published, &store.read_snapshot()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn from_layers_merges_later_layers_over_earlier() -> Result<()> {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let defaults = dir.path().join("defaults.toml");
+        let local = dir.path().join("local.toml");
+        std::fs::write(&defaults, "[App]\nport = 80\nhosts = [\"a\", \"b\"]\n[App.tls]\non = false\nmode = \"strict\"\n")?;
+        std::fs::write(&local, "[App]\nhosts = [\"c\"]\n[App.tls]\non = true\n[Db]\nurl = \"x\"\n")?;
+        let layers = [defaults.clone(), dir.path().join("env.toml"), local.clone()];
+
+        for store in [Confer::from_layers(&layers)?, Confer::from_layers_async(&layers).await?] {
+            assert_eq!(store.get_integer("App", "port").await?, 80);
+            assert_eq!(store.get_string_vec("App", "hosts").await?, ["c"]);
+            let tls = store.get_table("App", "tls").await?;
+            assert_eq!((tls["on"].as_bool(), tls["mode"].as_str()), (Some(true), Some("strict")));
+            assert_eq!(store.get_string("Db", "url").await?, "x");
+        }
+
+        let store = Confer::from_layers(&layers)?;
+        assert_eq!(store.value_source("App", "port"), Some(defaults.as_path()));
+        assert_eq!(store.value_source("App", "hosts"), Some(local.as_path()));
+        assert_eq!(store.value_source("App", "tls"), Some(local.as_path()));
+        assert_eq!(store.value_source("App", "missing"), None);
+        assert_eq!(Confer::from_string("[App]\nport = 1\n")?.value_source("App", "port"), None);
+
+        std::fs::write(&local, "[App\n")?;
+        assert!(matches!(Confer::from_layers(&layers), Err(ConferError::Parse { .. })));
+        Ok(())
+    }
+
+    #[cfg(feature = "file-lock")]
+    #[tokio::test]
+    async fn save_file_locked_keeps_concurrent_writes() -> Result<()> {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("shared.toml");
+        tokio::fs::write(&path, "[App]\nport = 1\n").await?;
+
+        let first = Confer::new();
+        let second = Confer::new();
+        first.load_file_locked(&path).await?;
+        second.load_file_locked(&path).await?;
+        first.set_string("App", "name", "one".into()).await?;
+        second.set_integer("App", "port", 2).await?;
+        first.save_file_locked(&path).await?;
+        second.save_file_locked(&path).await?;
+
+        let saved = Confer::from_file(&path)?;
+        assert_eq!(saved.get_string("App", "name").await?, "one");
+        assert_eq!(saved.get_integer("App", "port").await?, 2);
+        assert!(second.get_value("App", "name").await.is_none());
+        assert!(dir.path().join("shared.toml.lock").exists());
+        Ok(())
+    }
+
+    #[cfg(feature = "file-lock")]
+    #[tokio::test]
+    async fn save_file_locked_keeps_removals_from_the_store() -> Result<()> {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("shared.toml");
+        tokio::fs::write(&path, "[App]\nport = 1\nold = true\n[Legacy]\nx = 1\n").await?;
+
+        let store = Confer::new();
+        store.load_file_locked(&path).await?;
+        store.remove_key("App", "old").await?;
+        assert!(store.remove_section("Legacy").await?);
+        tokio::fs::write(&path, "[App]\nport = 1\nold = true\nname = \"other\"\n[Legacy]\nx = 1\n").await?;
+
+        store.save_file_locked(&path).await?;
+        let saved = Confer::from_file(&path)?;
+        assert_eq!(saved.list_keys("App").await?, ["name", "port"]);
+        assert!(!saved.section_exists("Legacy").await);
+        Ok(())
+    }
+
+    #[cfg(feature = "file-lock")]
+    #[tokio::test]
+    async fn save_file_locked_preserves_format_and_leaves_the_store_alone() -> Result<()> {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("shared.toml");
+        tokio::fs::write(&path, "# shared settings\n[App]\nport = 1 # default\n").await?;
+
+        let store = Confer::builder().preserve_format(true).build();
+        store.load_file_locked(&path).await?;
+        tokio::fs::write(&path, "# shared settings\n[App]\nport = 1 # default\nname = \"other\"\n").await?;
+        store.set_integer("App", "port", 2).await?;
+
+        let mut events = store.subscribe();
+        store.save_file_locked(&path).await?;
+        assert!(events.try_recv().is_err());
+        assert!(store.get_value("App", "name").await.is_none());
+
+        let saved = tokio::fs::read_to_string(&path).await?;
+        assert!(saved.starts_with("# shared settings\n[App]\nport = 2 # default\n"), "{saved}");
+        assert!(saved.contains("name = \"other\""), "{saved}");
+        Ok(())
+    }
+
+    #[cfg(feature = "mmap")]
+    #[tokio::test]
+    async fn from_file_mmap_reads_file() -> Result<()> {
+        let temp = NamedTempFile::new().expect("temp file");
+        std::fs::write(temp.path(), "[App]\nport = 2\n")?;
+
+        let store = Confer::from_file_mmap(temp.path())?;
+        assert_eq!(store.get_integer("App", "port").await?, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn serialize_embeds_the_table_as_a_map() -> Result<()> {
+        #[derive(serde::Serialize)]
+        struct Response<'a> {
+            status: &'a str,
+            config: &'a Confer,
+        }
+
+        let store = Confer::from_string("[App]\nport = 8080\n")?;
+        let rendered = toml::to_string(&Response { status: "ok", config: &store })?;
+        let parsed: Table = toml::from_str(&rendered).expect("serialized response");
+        assert_eq!(parsed["config"]["App"]["port"].as_integer(), Some(8080));
+
+        let _guard = store.write_table().await;
+        let locked = toml::to_string(&Response { status: "ok", config: &store });
+        assert_eq!(locked.is_err(), cfg!(not(feature = "arc-swap")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_boolean_lenient_accepts_common_spellings() -> Result<()> {
+        let store = Confer::from_string(
+            "[App]\nflag = true\nword = \" Yes \"\noff = \"OFF\"\nbit = 1\ntwo = 2\nmaybe = \"maybe\"\nratio = 1.0\n",
+        )?;
+        assert!(store.get_boolean_lenient("App", "flag").await?);
+        assert!(store.get_boolean_lenient("App", "word").await?);
+        assert!(!store.get_boolean_lenient("App", "off").await?);
+        assert!(store.get_boolean_lenient("App", "bit").await?);
+        assert!(store.get_boolean("App", "word").await.unwrap_err().is_type_mismatch());
+        for key in ["two", "maybe", "ratio"] {
+            assert!(store.get_boolean_lenient("App", key).await.unwrap_err().is_value_parse());
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_integer_coerce_accepts_whole_floats_and_strings() -> Result<()> {
+        let store = Confer::from_string(
+            "[App]\nport = 8080.0\nquoted = \" 9090 \"\nfraction = 1.5\nword = \"eighty\"\nflag = true\n",
+        )?;
+        assert_eq!(store.get_integer_coerce("App", "port").await?, 8080);
+        assert_eq!(store.get_integer_coerce("App", "quoted").await?, 9090);
+        assert!(matches!(store.get_integer("App", "port").await, Err(ConferError::TypeMismatch { .. })));
+        assert!(matches!(
+            store.get_integer_coerce("App", "fraction").await,
+            Err(ConferError::ValueParse { .. })
+        ));
+        assert!(matches!(
+            store.get_integer_coerce("App", "word").await,
+            Err(ConferError::ValueParse { .. })
+        ));
+        assert!(matches!(
+            store.get_integer_coerce("App", "flag").await,
+            Err(ConferError::TypeMismatch { .. })
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_integer_vec_coerce_accepts_whole_float_elements() -> Result<()> {
+        let store = Confer::from_string("[App]\nids = [1, 2.0, 3]\nweights = [1, 2.5, 3]\n")?;
+        assert_eq!(store.get_integer_vec_coerce("App", "ids").await?, [1, 2, 3]);
+        assert_eq!(store.get_float_vec("App", "weights").await?, [1.0, 2.5, 3.0]);
+        assert!(store.get_integer_vec("App", "ids").await.is_err());
+
+        let err = store.get_integer_vec_coerce("App", "weights").await.unwrap_err();
+        assert!(matches!(err, ConferError::ValueParse { .. }));
+        assert!(err.to_string().contains("(at index 1)"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn type_mismatch_reports_element_index() {
+        let err = value_conversion::annotate_with_index(
+            ConferError::type_mismatch("App", "langs", "string", "integer"),
+            3,
+        );
+        assert!(matches!(err, ConferError::TypeMismatch { index: Some(3), .. }));
+        assert_eq!(err.to_string(), "expected string at App.langs but found integer (at index 3)");
+    }
+
+    #[tokio::test]
+    async fn error_predicates_and_accessors_cover_keyed_variants() -> Result<()> {
+        let store = Confer::from_string("[App]\nport = \"x\"\n")?;
+        let missing = store.get_integer("App", "host").await.unwrap_err();
+        assert!(missing.is_missing_key() && !missing.is_type_mismatch());
+        assert_eq!((missing.section(), missing.key()), (Some("App"), Some("host")));
+
+        let mismatch = store.get_integer("App", "port").await.unwrap_err();
+        assert!(mismatch.is_type_mismatch());
+        assert_eq!(mismatch.key(), Some("port"));
+
+        let exists = ConferError::section_exists("Db");
+        assert_eq!((exists.section(), exists.key()), (Some("Db"), None));
+        let io = ConferError::from(std::io::Error::other("boom"));
+        assert!(io.is_io() && io.section().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn errors_expose_their_underlying_cause() {
+        use std::error::Error as _;
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        let missing = dir.path().join("missing.toml");
+        let err = Confer::from_file(&missing).unwrap_err();
+        assert!(matches!(err, ConferError::Io { ref path, .. } if path.as_deref() == Some(missing.as_path())));
+        let source = err.source().expect("io source");
+        assert_eq!(source.downcast_ref::<std::io::Error>().map(|err| err.kind()), Some(ErrorKind::NotFound));
+
+        let err = Confer::from_string("[App\n").unwrap_err();
+        assert!(err.source().and_then(|source| source.downcast_ref::<toml::de::Error>()).is_some());
+
+        assert!(ConferError::missing_key("App", "port").source().is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn from_file_async_timeout_reports_expiry() -> Result<()> {
+        // Opening a FIFO for reading blocks until a writer shows up, simulating a hung mount.
+        let dir = tempfile::tempdir().expect("temp dir");
+        let fifo = dir.path().join("stuck.toml");
+        let status = std::process::Command::new("mkfifo").arg(&fifo).status()?;
+        assert!(status.success());
+
+        let err = Confer::from_file_async_timeout(&fifo, Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ConferError::Io { ref path, ref source }
+                if path.as_deref() == Some(fifo.as_path()) && source.kind() == ErrorKind::TimedOut
+        ));
+
+        // Release the blocked reader so the runtime can shut down.
+        drop(std::fs::OpenOptions::new().write(true).open(&fifo)?);
+        Ok(())
+    }
+
+    #[cfg(feature = "json")]
+    #[tokio::test]
+    async fn json_file_roundtrip() -> Result<()> {
+        let store = Confer::from_string("[App]\nname = \"demo\"\nstarted = 2024-01-01T00:00:00Z\n")?;
+        let temp = NamedTempFile::new().expect("temp file");
+        store.save_json_file(temp.path()).await?;
+
+        let restored = Confer::from_json_file(temp.path())?;
+        assert_eq!(restored.get_string("App", "name").await?, "demo");
+        assert_eq!(
+            restored.get_datetime("App", "started").await?.to_string(),
+            "2024-01-01T00:00:00Z"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn save_env_file_writes_prefixed_lines() -> Result<()> {
+        let store = Confer::from_string("[Srv]\nhost = \"0.0.0.0\"\nport = 80\n")?;
+        let temp = NamedTempFile::new().expect("temp file");
+        store.save_env_file("Srv", temp.path(), "SRV").await?;
+
+        let contents = tokio::fs::read_to_string(temp.path()).await?;
+        assert_eq!(contents, "SRV_HOST=0.0.0.0\nSRV_PORT=80\n");
+
+        let err = store.save_env_file("Missing", temp.path(), "X").await.unwrap_err();
+        assert!(matches!(err, ConferError::MissingKey { .. }));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn builder_case_insensitive_lookups_reuse_stored_names() -> Result<()> {
+        let store = Confer::builder()
+            .case_insensitive(true)
+            .build_from_str("[Server]\nHost = \"localhost\"\n")?;
+
+        assert_eq!(store.get_string("server", "host").await?, "localhost");
+        store.set_string("SERVER", "HOST", "example.org".to_string()).await?;
+        assert_eq!(store.list_sections().await, vec!["Server".to_string()]);
+        assert_eq!(store.list_keys("server").await?, vec!["Host".to_string()]);
+
+        let strict = Confer::from_string("[Server]\nHost = \"localhost\"\n")?;
+        assert!(matches!(
+            strict.get_string("server", "host").await,
+            Err(ConferError::MissingKey { .. })
+        ));
+        Ok(())
+    }
+
+    fn case_insensitive(source: &str) -> Result<SharedConfer> {
+        Confer::builder().case_insensitive(true).build_from_str(source)
+    }
+
+    #[tokio::test]
+    async fn case_insensitive_copy_and_move_resolve_section_names() -> Result<()> {
+        let store = case_insensitive("[Server]\nport = 1\n[Backup]\nport = 0\n")?;
+        assert!(matches!(
+            store.copy_section("server", "BACKUP", false).await,
+            Err(ConferError::SectionExists { .. })
+        ));
+        store.copy_section("server", "BACKUP", true).await?;
+        assert_eq!(store.list_sections().await, ["Backup", "Server"]);
+        assert_eq!(store.get_integer("Backup", "port").await?, 1);
+
+        store.move_section("SERVER", "primary", false).await?;
+        assert_eq!(store.list_sections().await, ["Backup", "primary"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn case_insensitive_redaction_resolves_paths() -> Result<()> {
+        let store = case_insensitive("[Auth]\nToken = \"secret\"\n")?;
+        let redacted = store.save_str_redacted(&["auth.token"]).await?;
+        assert!(!redacted.contains("secret"), "{redacted}");
+        assert!(redacted.contains("Token"), "{redacted}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn case_insensitive_patch_resolves_nested_keys() -> Result<()> {
+        let store = case_insensitive("[App]\n[App.Limits]\nMax = 1\nmin = 0\n")?;
+        store.patch("[app.limits]\nmax = 5\n").await?;
+        assert_eq!(store.save_str().await?, "[App.Limits]\nMax = 5\nmin = 0\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn case_insensitive_section_claims_compare_names() -> Result<()> {
+        let store = case_insensitive("")?;
+        store.register_module_section("First", "App", &["Port"])?;
+        let err = store.register_module_section("Second", "app", &["port"]).unwrap_err();
+        assert!(matches!(err, ConferError::SectionConflict { .. }));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn case_insensitive_schema_version_reuses_stored_names() -> Result<()> {
+        let store = case_insensitive("[Cache]\n__VERSION = 1\nttl = 5\n")?;
+        store
+            .apply_schema_version("cache", "__version", 2, Some(|table: &mut Table, from: u32| {
+                assert_eq!(from, 1);
+                table.insert("ttl".to_owned(), Value::Integer(10));
+                Ok(())
+            }))
+            .await?;
+        assert_eq!(store.save_str().await?, "[Cache]\n__VERSION = 2\nttl = 10\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn case_insensitive_get_or_insert_reports_mismatch_without_inserting() -> Result<()> {
+        let store = case_insensitive("[App]\nPort = \"eighty\"\n")?;
+        let err = store.get_or_insert_integer("app", "port", 80).await.unwrap_err();
+        assert!(err.is_type_mismatch());
+        assert_eq!(store.save_str().await?, "[App]\nPort = \"eighty\"\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn builder_require_existing_sections_rejects_new_sections() -> Result<()> {
+        let store = Confer::builder()
+            .require_existing_sections(true)
+            .build_from_str("[Server]\nport = 80\n")?;
+        store.set_integer("Server", "port", 8080).await?;
+        let err = store.set_integer("Servr", "port", 8080).await.unwrap_err();
+        assert!(matches!(err, ConferError::MissingKey { ref key, .. } if key == "<section>"));
+        assert!(!store.section_exists("Servr").await);
+
+        store.add_section("Client").await?;
+        store.set_integer("Client", "retries", 3).await?;
+        assert_eq!(store.get_integer("Client", "retries").await?, 3);
+        Ok(())
     }
 
-    /// Retrieves a boolean array stored at `section.key`.
-    pub async fn get_boolean_vec(&self, section: &str, key: &str) -> Result<Vec<bool>> {
-        let value = self.fetch_value(section, key).await?;
-        value_conversion::boolean_vec(section, key, value)
+    fn existing_sections_only(source: &str) -> Result<SharedConfer> {
+        Confer::builder().require_existing_sections(true).build_from_str(source)
     }
 
-    /// Retrieves a [`Datetime`] array stored at `section.key`, parsing string values when necessary.
-    pub async fn get_datetime_vec(
-        &self,
-        section: &str,
-        key: &str,
-    ) -> Result<Vec<Datetime>> {
-        let value = self.fetch_value(section, key).await?;
-        value_conversion::datetime_vec(section, key, value)
+    fn is_missing_section(result: Result<()>) -> bool {
+        matches!(result, Err(ConferError::MissingKey { ref key, .. }) if key == "<section>")
     }
 
-    /// Stores a string at `section.key`, creating the section if needed.
-    pub async fn set_string(&self, section: &str, key: &str, value: String) -> Result<()> {
-        self.set_value(section, key, Value::String(value)).await
+    #[tokio::test]
+    async fn require_existing_sections_covers_set_many() -> Result<()> {
+        let store = existing_sections_only("[Server]\nport = 80\n")?;
+        let entries = || vec![("port".to_string(), Value::Integer(81))];
+        assert!(is_missing_section(store.set_many("Servr", entries()).await));
+        store.set_many("Server", entries()).await?;
+        assert_eq!(store.list_sections().await, ["Server"]);
+        Ok(())
     }
 
-    /// Stores an integer at `section.key`, creating the section if needed.
-    pub async fn set_integer(&self, section: &str, key: &str, value: i64) -> Result<()> {
-        self.set_value(section, key, Value::Integer(value)).await
+    #[tokio::test]
+    async fn require_existing_sections_covers_patch() -> Result<()> {
+        let store = existing_sections_only("[Server]\nport = 80\n")?;
+        assert!(is_missing_section(store.patch("[Server]\nport = 81\n[Servr]\nport = 82\n").await));
+        assert_eq!(store.get_integer("Server", "port").await?, 80);
+        store.patch("[Server]\nport = 81\n").await?;
+        assert_eq!(store.get_integer("Server", "port").await?, 81);
+        Ok(())
     }
 
-    /// Stores a floating-point number at `section.key`, creating the section if needed.
-    pub async fn set_float(&self, section: &str, key: &str, value: f64) -> Result<()> {
-        self.set_value(section, key, Value::Float(value)).await
+    #[tokio::test]
+    async fn require_existing_sections_covers_import_sections() -> Result<()> {
+        let store = existing_sections_only("[Server]\nport = 80\n")?;
+        let sections = |name: &str| vec![(name.to_string(), Table::new())];
+        assert!(is_missing_section(store.import_sections(sections("Servr"), true).await));
+        store.import_sections(sections("Server"), true).await?;
+        assert_eq!(store.list_sections().await, ["Server"]);
+        Ok(())
     }
 
-    /// Stores a boolean at `section.key`, creating the section if needed.
-    pub async fn set_boolean(&self, section: &str, key: &str, value: bool) -> Result<()> {
-        self.set_value(section, key, Value::Boolean(value)).await
+    #[tokio::test]
+    async fn require_existing_sections_covers_copy_and_move() -> Result<()> {
+        let store = existing_sections_only("[Server]\nport = 80\n[Backup]\n")?;
+        assert!(is_missing_section(store.copy_section("Server", "Servr", false).await));
+        assert!(is_missing_section(store.move_section("Server", "Servr", false).await));
+        store.copy_section("Server", "Backup", true).await?;
+        assert_eq!(store.get_integer("Backup", "port").await?, 80);
+        assert_eq!(store.list_sections().await, ["Backup", "Server"]);
+        Ok(())
     }
 
-    /// Stores a TOML [`Datetime`] at `section.key`, creating the section if needed.
-    pub async fn set_datetime(
-        &self,
-        section: &str,
-        key: &str,
-        value: Datetime,
-    ) -> Result<()> {
-        self.set_value(section, key, Value::Datetime(value)).await
+    #[tokio::test]
+    async fn require_existing_sections_covers_replace_section() -> Result<()> {
+        let store = existing_sections_only("[Server]\nport = 80\n")?;
+        assert!(is_missing_section(store.replace_section("Servr", Table::new()).await));
+        store.replace_section("Server", Table::new()).await?;
+        assert_eq!(store.list_sections().await, ["Server"]);
+        Ok(())
     }
 
-    /// Stores a string array at `section.key`, creating the section if needed.
-    pub async fn set_string_vec(
-        &self,
-        section: &str,
-        key: &str,
-        value: Vec<String>,
-    ) -> Result<()> {
-        let array = value.into_iter().map(Value::String).collect();
-        self.set_value(section, key, Value::Array(array)).await
+    #[tokio::test]
+    async fn require_existing_sections_covers_get_or_insert() -> Result<()> {
+        let store = existing_sections_only("[Server]\n")?;
+        let err = store.get_or_insert_integer("Servr", "port", 80).await.map(drop);
+        assert!(is_missing_section(err));
+        assert_eq!(store.get_or_insert_integer("Server", "port", 80).await?, 80);
+        assert_eq!(store.list_sections().await, ["Server"]);
+        Ok(())
     }
 
-    /// Stores an integer array at `section.key`, creating the section if needed.
-    pub async fn set_integer_vec(
-        &self,
-        section: &str,
-        key: &str,
-        value: Vec<i64>,
-    ) -> Result<()> {
-        let array = value.into_iter().map(Value::Integer).collect();
-        self.set_value(section, key, Value::Array(array)).await
+    #[tokio::test]
+    async fn builder_coerce_singleton_arrays_bridges_scalars_and_arrays() -> Result<()> {
+        let source = "[App]\nhost = [\"a\"]\nports = [80, 443]\nmirror = \"b\"\n";
+        let store = Confer::builder().coerce_singleton_arrays(true).build_from_str(source)?;
+        assert_eq!(store.get_string("App", "host").await?, "a");
+        assert_eq!(store.get_string_vec("App", "mirror").await?, ["b"]);
+        assert!(store.get_integer("App", "ports").await.unwrap_err().is_type_mismatch());
+        assert_eq!(store.get_integer_vec("App", "ports").await?, [80, 443]);
+
+        let strict = Confer::from_string(source)?;
+        assert!(strict.get_string("App", "host").await.unwrap_err().is_type_mismatch());
+        assert!(strict.get_string_vec("App", "mirror").await.unwrap_err().is_type_mismatch());
+        Ok(())
     }
 
-    /// Stores a floating-point array at `section.key`, creating the section if needed.
-    pub async fn set_float_vec(
-        &self,
-        section: &str,
-        key: &str,
-        value: Vec<f64>,
-    ) -> Result<()> {
-        let array = value.into_iter().map(Value::Float).collect();
-        self.set_value(section, key, Value::Array(array)).await
+    #[tokio::test]
+    async fn snapshot_section_copies_one_section() -> Result<()> {
+        let store = Confer::builder()
+            .case_insensitive(true)
+            .build_from_str("[App]\nport = 1\n[Other]\nkeep = true\nflat = 3\n")?;
+        let snapshot = store.snapshot_section("app").await;
+        assert_eq!(snapshot.get_integer("APP", "Port").await?, 1);
+        assert!(!snapshot.section_exists("Other").await);
+
+        store.set_integer("App", "port", 2).await?;
+        assert_eq!(snapshot.get_integer("App", "port").await?, 1);
+        assert!(!store.snapshot_section("Missing").await.section_exists("Missing").await);
+        Ok(())
     }
 
-    /// Stores a boolean array at `section.key`, creating the section if needed.
-    pub async fn set_boolean_vec(
-        &self,
-        section: &str,
-        key: &str,
-        value: Vec<bool>,
-    ) -> Result<()> {
-        let array = value.into_iter().map(Value::Boolean).collect();
-        self.set_value(section, key, Value::Array(array)).await
-    }
+    #[tokio::test]
+    async fn leading_bom_and_crlf_are_accepted() -> Result<()> {
+        let store = Confer::from_string("\u{feff}[App]\r\nname = \"demo\"\r\nport = 1\r\n")?;
+        assert_eq!(store.get_string("App", "name").await?, "demo");
+        assert_eq!(store.list_sections().await, ["App"]);
 
-    /// Stores a [`Datetime`] array at `section.key`, creating the section if needed.
-    pub async fn set_datetime_vec(
-        &self,
-        section: &str,
-        key: &str,
-        value: Vec<Datetime>,
-    ) -> Result<()> {
-        let array = value.into_iter().map(Value::Datetime).collect();
-        self.set_value(section, key, Value::Array(array)).await
+        assert!(Confer::from_string(" \u{feff}[App]\n").is_err());
+        assert!(Confer::from_string("[App]\nname = \"\u{feff}x\"\n").is_ok());
+        Ok(())
     }
 
-    /// Fetches the raw TOML [`Value`] stored at `section.key`, producing detailed errors.
-    async fn fetch_value(&self, section: &str, key: &str) -> Result<Value> {
-        let guard = self.table.read().await;
-        let section_value = guard
-            .get(section)
-            .ok_or_else(|| ConferError::missing_key(section, key))?;
-        let table = section_value.as_table().ok_or_else(|| {
-            ConferError::type_mismatch(
-                section,
-                "<section>",
-                "table",
-                value_conversion::describe(section_value),
-            )
-        })?;
-        table
-            .get(key)
-            .cloned()
-            .ok_or_else(|| ConferError::missing_key(section, key))
+    #[tokio::test]
+    async fn patch_deep_merges_fragments() -> Result<()> {
+        let store = Confer::from_string(
+            "[server]\nhost = \"a\"\nport = 80\ntags = [\"x\"]\n[server.tls]\ncert = \"c\"\nkey = \"k\"\n",
+        )?;
+        store
+            .patch("[server]\nport = 9090\ntags = [\"y\"]\n[server.tls]\nkey = \"k2\"\n[client]\nretries = 3\n")
+            .await?;
+
+        assert_eq!(store.get_string("server", "host").await?, "a");
+        assert_eq!(store.get_integer("server", "port").await?, 9090);
+        assert_eq!(store.get_string_vec("server", "tags").await?, ["y"]);
+        let tls = store.get_table("server", "tls").await?;
+        assert_eq!(tls.get("cert").and_then(Value::as_str), Some("c"));
+        assert_eq!(tls.get("key").and_then(Value::as_str), Some("k2"));
+        assert_eq!(store.get_integer("client", "retries").await?, 3);
+
+        assert!(store.patch("[server\nport = 1\n").await.is_err());
+        assert_eq!(store.get_integer("server", "port").await?, 9090);
+        Ok(())
     }
 
-    /// Parses a TOML table from `source`, mapping parsing failures into [`ConferError`].
-    fn parse_table(source: &str) -> Result<Table> {
-        toml::from_str(source).map_err(ConferError::from)
+    #[tokio::test]
+    async fn indexed_array_access() -> Result<()> {
+        let store = Confer::from_string("[App]\nservers = [{ host = \"a\" }, { host = \"b\" }]\nname = \"x\"\n")?;
+        let second = store.get_value_at_index("App", "servers", 1).await?.expect("in bounds");
+        assert_eq!(second.get("host").and_then(Value::as_str), Some("b"));
+        assert_eq!(store.get_value_at_index("App", "servers", 2).await?, None);
+        assert_eq!(store.array_len("App", "servers").await?, 2);
+
+        let err = store.get_value_at_index("App", "name", 0).await.unwrap_err();
+        assert!(matches!(err, ConferError::TypeMismatch { expected: "array", found: "string", .. }));
+        assert!(matches!(
+            store.array_len("App", "missing").await,
+            Err(ConferError::MissingKey { .. })
+        ));
+        Ok(())
     }
-}
 
-/// Retrieves the table stored within `root` at `section`, if it exists and is a table.
-fn section_table<'a>(root: &'a Table, section: &str) -> Option<&'a Table> {
-    root.get(section)?.as_table()
-}
+    #[tokio::test]
+    async fn diff_against_file_reports_drift() -> Result<()> {
+        let temp = tempfile::NamedTempFile::new().expect("temp file");
+        std::fs::write(temp.path(), "[App]\nport = 1\nname = \"demo\"\n").expect("write");
+        let store = Confer::from_file(temp.path())?;
+        assert!(store.diff_against_file(temp.path()).await?.is_empty());
+
+        store.set_integer("App", "port", 2).await?;
+        store.remove_key("App", "name").await?;
+        let changes = store.diff_against_file(temp.path()).await?;
+        assert!(matches!(&changes[..], [
+            ConfigChange::Removed { key: removed, .. },
+            ConfigChange::Modified { key: modified, .. },
+        ] if removed == "name" && modified == "port"));
+
+        let other = Confer::from_string("[App]\nport = 2\n")?;
+        assert!(store.diff(&other).await.is_empty());
+        Ok(())
+    }
 
-/// Atomically persists `contents` to `path`, ensuring the file is fully replaced on success.
-async fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
-    let tmp_path = temporary_path(path);
-    fs::write(&tmp_path, contents)
-        .await
-        .map_err(|err| ConferError::io_error(Some(tmp_path.clone()), err))?;
+    #[tokio::test]
+    async fn builder_preserve_format_keeps_comments() -> Result<()> {
+        let source = "# top comment\n[Server]\n# the port\nport = 80 # trailing\nhost = \"a\"\n";
+        let store = Confer::builder().preserve_format(true).build_from_str(source)?;
+        store.set_integer("Server", "port", 8080).await?;
+        store.set_boolean("Server", "tls", true).await?;
+
+        let saved = store.save_str().await?;
+        assert!(saved.starts_with("# top comment\n[Server]\n# the port\nport = 8080 # trailing\n"));
+        assert!(saved.contains("host = \"a\"\n"));
+        assert!(saved.contains("tls = true"));
+
+        store.load_str("[Other]\nvalue = 1 # note\n").await?;
+        assert_eq!(store.save_str().await?, "[Other]\nvalue = 1 # note\n");
+        Ok(())
+    }
 
-    match fs::rename(&tmp_path, path).await {
-        Ok(()) => Ok(()),
-        Err(err) if err.kind() == ErrorKind::AlreadyExists => {
-            fs::remove_file(path)
-                .await
-                .map_err(|remove_err| ConferError::io_error(Some(path.to_path_buf()), remove_err))?;
-            fs::rename(&tmp_path, path)
-                .await
-                .map_err(|err| ConferError::io_error(Some(path.to_path_buf()), err))
-        }
-        Err(err) => {
-            let _ = fs::remove_file(&tmp_path).await;
-            Err(ConferError::io_error(Some(path.to_path_buf()), err))
+    #[tokio::test]
+    async fn date_and_time_accessors_reject_other_forms() -> Result<()> {
+        let store = Confer::from_string(
+            "[Release]\nday = 2025-01-01\nat = 08:30:00\nfull = 2025-01-01T08:30:00\nquoted = \"2025-02-01\"\n",
+        )?;
+        assert_eq!(store.get_date("Release", "day").await?.to_string(), "2025-01-01");
+        assert_eq!(store.get_date("Release", "quoted").await?.month, 2);
+        assert_eq!(store.get_time("Release", "at").await?.to_string(), "08:30:00");
+        for (key, date) in [("full", true), ("at", true), ("full", false), ("day", false)] {
+            let result = if date {
+                store.get_date("Release", key).await.map(drop)
+            } else {
+                store.get_time("Release", key).await.map(drop)
+            };
+            assert!(matches!(result, Err(ConferError::ValueParse { .. })), "{key}");
         }
+
+        let day = store.get_date("Release", "day").await?;
+        store.set_date_vec("Release", "days", vec![day, day]).await?;
+        assert_eq!(store.get_date_vec("Release", "days").await?, [day, day]);
+        assert!(store.get_time_vec("Release", "days").await.is_err());
+        Ok(())
     }
-}
 
-/// Computes a temporary sibling path used during atomic write operations.
-fn temporary_path(path: &Path) -> PathBuf {
-    let mut file_name = path
-        .file_name()
-        .map(|name| name.to_os_string())
-        .unwrap_or_else(|| "conferencier".into());
-    file_name.push(".tmp");
+    #[tokio::test]
+    async fn get_datetime_offset_rejects_local_datetimes() -> Result<()> {
+        let store = Confer::from_string(
+            "[Jobs]\nutc = 2024-05-01T08:00:00Z\nlocal = 2024-05-01T08:00:00\nday = 2024-05-01\nquoted = \"2024-05-01T08:00:00+02:00\"\n",
+        )?;
+        assert!(store.get_datetime_offset("Jobs", "utc").await?.offset.is_some());
+        assert!(store.get_datetime_offset("Jobs", "quoted").await?.offset.is_some());
+        assert!(store.get_datetime("Jobs", "local").await.is_ok());
+        for key in ["local", "day"] {
+            assert!(matches!(
+                store.get_datetime_offset("Jobs", key).await,
+                Err(ConferError::ValueParse { .. })
+            ));
+        }
+        Ok(())
+    }
 
-    path.with_file_name(file_name)
-}
+    #[tokio::test]
+    async fn prune_section_to_keeps_known_keys() -> Result<()> {
+        let store = Confer::from_string("[App]\nport = 1\nstale = 2\nold = 3\n[Other]\nstale = 4\n")?;
+        let mut events = store.subscribe();
+        store.prune_section_to("App", &["port"]).await?;
+
+        assert_eq!(store.list_keys("App").await?, ["port"]);
+        assert_eq!(store.list_keys("Other").await?, ["stale"]);
+        assert_eq!(events.try_recv().expect("event").kind, ChangeKind::Removed);
+        assert_eq!(events.try_recv().expect("event").kind, ChangeKind::Removed);
+
+        store.prune_section_to("Missing", &[]).await?;
+        assert!(!store.section_exists("Missing").await);
+        Ok(())
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::error::{ConferError, Result};
+    #[tokio::test]
+    async fn get_arc_value_shares_until_written() -> Result<()> {
+        let store = Confer::from_string("[Access]\nallow = [\"a\", \"b\"]\n")?;
+        let first = store.get_arc_value("Access", "allow").await?;
+        let second = store.get_arc_value("Access", "allow").await?;
+        assert!(Arc::ptr_eq(&first, &second));
+
+        store.set_string_vec("Access", "allow", vec!["c".into()]).await?;
+        let third = store.get_arc_value("Access", "allow").await?;
+        assert!(!Arc::ptr_eq(&first, &third));
+        assert_eq!(*third, Value::Array(vec![Value::String("c".into())]));
+        assert_eq!(*first, Value::Array(vec![Value::String("a".into()), Value::String("b".into())]));
+
+        assert!(matches!(
+            store.get_arc_value("Access", "deny").await,
+            Err(ConferError::MissingKey { .. })
+        ));
+        Ok(())
+    }
 
-    use tempfile::NamedTempFile;
+    #[tokio::test]
+    async fn section_snapshot_shares_until_written() -> Result<()> {
+        let store = Confer::from_string("[Routes]\nhome = \"/\"\n")?;
+        let first = store.section_snapshot("Routes").await.expect("section");
+        let second = store.section_snapshot("Routes").await.expect("section");
+        assert!(Arc::ptr_eq(&first, &second));
+
+        store.set_string("Routes", "about", "/about".into()).await?;
+        let third = store.section_snapshot("Routes").await.expect("section");
+        assert!(!Arc::ptr_eq(&first, &third));
+        assert_eq!(first.len(), 1);
+        assert_eq!(third.len(), 2);
+        assert!(store.section_snapshot("Missing").await.is_none());
+        Ok(())
+    }
 
     #[tokio::test]
-    async fn new_store_is_empty() {
-        let store = Confer::new();
-        assert!(store.list_sections().await.is_empty());
+    async fn replace_section_swaps_the_whole_table() -> Result<()> {
+        let store = Confer::from_string("[Routes]\nold = \"/old\"\n[App]\nport = 1\nflag = 3\n")?;
+        let mut routes = Table::new();
+        routes.insert("home".into(), Value::String("/".into()));
+        store.replace_section("Routes", routes.clone()).await?;
+        assert_eq!(store.get_section_table("Routes").await, Some(routes.clone()));
+
+        store.replace_section("Fresh", routes.clone()).await?;
+        assert_eq!(store.get_string("Fresh", "home").await?, "/");
+
+        store.with_table_mut(|root| root.insert("flag".into(), Value::Integer(1))).await;
+        assert!(matches!(
+            store.replace_section("flag", routes).await,
+            Err(ConferError::TypeMismatch { .. })
+        ));
+        assert_eq!(store.with_table(|root| root.get("flag").cloned()).await, Some(Value::Integer(1)));
+        Ok(())
     }
 
     #[tokio::test]
-    async fn set_and_get_string_roundtrip() -> Result<()> {
-        let store = Confer::new();
-        store.set_string("App", "name", "demo".into()).await?;
-        assert_eq!(store.get_string("App", "name").await?, "demo");
+    async fn for_each_leaf_visits_nested_paths() -> Result<()> {
+        let store = Confer::from_string(
+            "[App]\nname = \"svc\"\nports = [80, 443]\nempty = []\n[App.tls]\non = true\n",
+        )?;
+        let mut leaves = Vec::new();
+        store
+            .for_each_leaf(|path, value| leaves.push((path.join("."), value.to_string())))
+            .await;
         assert_eq!(
-            store.get_value("App", "name").await,
-            Some(Value::String("demo".into()))
+            leaves,
+            [
+                ("App.name".to_string(), "\"svc\"".to_string()),
+                ("App.ports.0".to_string(), "80".to_string()),
+                ("App.ports.1".to_string(), "443".to_string()),
+                ("App.tls.on".to_string(), "true".to_string()),
+            ]
         );
         Ok(())
     }
 
     #[tokio::test]
-    async fn datetime_fallback_from_string() -> Result<()> {
-        let store = Confer::new();
-        store
-            .set_string("Build", "time", "2024-01-01T00:00:00Z".into())
+    async fn with_value_borrows_without_cloning() -> Result<()> {
+        let store = Confer::from_string("[App]\nlangs = [\"en\", \"de\", \"fr\"]\nflat = 1\n")?;
+        let len = store
+            .with_value("App", "langs", |value| value.as_array().map(Vec::len))
             .await?;
-        let dt = store.get_datetime("Build", "time").await?;
-        assert_eq!(dt.to_string(), "2024-01-01T00:00:00Z");
+        assert_eq!(len, Some(Some(3)));
+        assert_eq!(store.with_value("App", "missing", |_| ()).await?, None);
+        assert_eq!(store.with_value("Nope", "langs", |_| ()).await?, None);
+
+        let err = Confer::from_string("App = 1\n")?
+            .with_value("App", "x", |_| ())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ConferError::TypeMismatch { .. }));
         Ok(())
     }
 
     #[tokio::test]
-    async fn float_vec_accepts_integers() -> Result<()> {
-        let store = Confer::new();
-        store
-            .set_value(
-                "App",
-                "thresholds",
-                Value::Array(vec![Value::Integer(1), Value::Float(2.5)]),
-            )
-            .await?;
-        let values = store.get_float_vec("App", "thresholds").await?;
-        assert_eq!(values, vec![1.0, 2.5]);
+    async fn set_value_refuses_to_replace_structures_with_scalars() -> Result<()> {
+        let store = Confer::from_string("[Server]\ntls = { cert = \"a.pem\" }\nports = [80]\n")?;
+
+        let err = store.set_string("Server", "tls", "x".into()).await.unwrap_err();
+        assert!(matches!(err, ConferError::TypeMismatch { expected: "table", found: "string", .. }));
+        let err = store.set_integer("Server", "ports", 443).await.unwrap_err();
+        assert!(matches!(err, ConferError::TypeMismatch { expected: "array", .. }));
+
+        store.set_integer_vec("Server", "ports", vec![443]).await?;
+        store.set_value_force("Server", "tls", Value::Boolean(false)).await?;
+        assert!(!store.get_boolean("Server", "tls").await?);
         Ok(())
     }
 
     #[tokio::test]
-    async fn missing_key_yields_error() {
+    async fn subscribers_receive_mutation_events() -> Result<()> {
         let store = Confer::new();
-        let err = store.get_string("Missing", "key").await.unwrap_err();
-        assert!(matches!(err, ConferError::MissingKey { .. }));
+        store.set_integer("Early", "ignored", 1).await?;
+        let mut events = store.subscribe();
+
+        store.set_integer("App", "port", 80).await?;
+        store.remove_key("App", "port").await?;
+        store.remove_key("App", "port").await?;
+        assert!(store.remove_section("App").await?);
+        assert!(!store.remove_section("App").await?);
+
+        let expected = [
+            (Some("port"), ChangeKind::Set),
+            (Some("port"), ChangeKind::Removed),
+            (None, ChangeKind::SectionRemoved),
+        ];
+        for (key, kind) in expected {
+            let event = events.try_recv().expect("event");
+            assert_eq!(event.section, "App");
+            assert_eq!(event.key.as_deref(), key);
+            assert_eq!(event.kind, kind);
+        }
+        assert!(events.try_recv().is_err());
+        Ok(())
     }
 
-    #[tokio::test]
-    async fn load_str_replaces_content() -> Result<()> {
-        let store = Confer::new();
-        store.set_string("App", "name", "demo".into()).await?;
-        store.load_str("[Srv]\nport = 8080\n").await?;
+    fn drain_events(
+        events: &mut tokio::sync::broadcast::Receiver<ChangeEvent>,
+    ) -> Vec<(String, Option<String>, ChangeKind)> {
+        std::iter::from_fn(|| events.try_recv().ok())
+            .map(|event| (event.section, event.key, event.kind))
+            .collect()
+    }
 
-        assert!(store.get_value("App", "name").await.is_none());
-        assert_eq!(store.get_integer("Srv", "port").await?, 8080);
-        Ok(())
+    fn event(section: &str, key: Option<&str>, kind: ChangeKind) -> (String, Option<String>, ChangeKind) {
+        (section.to_owned(), key.map(str::to_owned), kind)
     }
 
     #[tokio::test]
-    async fn list_keys_missing_section_empty() -> Result<()> {
-        let store = Confer::new();
-        assert!(store.list_keys("Unknown").await?.is_empty());
+    async fn clear_emits_section_removed_events() -> Result<()> {
+        let store = Confer::from_string("[A]\nx = 1\n[B]\ny = 2\n")?;
+        let mut events = store.subscribe();
+        store.clear().await;
+        store.clear().await;
+        assert_eq!(
+            drain_events(&mut events),
+            [event("A", None, ChangeKind::SectionRemoved), event("B", None, ChangeKind::SectionRemoved)]
+        );
         Ok(())
     }
 
     #[tokio::test]
-    async fn add_section_is_idempotent() -> Result<()> {
-        let store = Confer::new();
-        store.add_section("Srv").await?;
-        store.add_section("Srv").await?;
-        assert!(store.section_exists("Srv").await);
+    async fn clear_section_emits_removed_events() -> Result<()> {
+        let store = Confer::from_string("[App]\nport = 1\nname = \"demo\"\n")?;
+        let mut events = store.subscribe();
+        store.clear_section("App").await?;
+        store.clear_section("App").await?;
+        assert_eq!(
+            drain_events(&mut events),
+            [event("App", Some("name"), ChangeKind::Removed), event("App", Some("port"), ChangeKind::Removed)]
+        );
         Ok(())
     }
 
     #[tokio::test]
-    async fn remove_key_missing_is_ok() -> Result<()> {
-        let store = Confer::new();
-        store.remove_key("Srv", "unknown").await?;
-        store.add_section("Srv").await?;
-        store.remove_key("Srv", "missing").await?;
+    async fn copy_and_move_section_emit_events() -> Result<()> {
+        let store = Confer::from_string("[From]\nport = 1\n[To]\nold = 2\n")?;
+        let mut events = store.subscribe();
+        store.copy_section("From", "To", true).await?;
+        assert_eq!(
+            drain_events(&mut events),
+            [event("To", None, ChangeKind::SectionRemoved), event("To", Some("port"), ChangeKind::Set)]
+        );
+
+        store.move_section("From", "Moved", false).await?;
+        assert_eq!(
+            drain_events(&mut events),
+            [event("From", None, ChangeKind::SectionRemoved), event("Moved", Some("port"), ChangeKind::Set)]
+        );
         Ok(())
     }
 
     #[tokio::test]
-    async fn save_and_load_file_roundtrip() -> Result<()> {
+    async fn get_or_insert_emits_set_only_when_inserting() -> Result<()> {
         let store = Confer::new();
-        store.set_string("App", "name", "demo".into()).await?;
-        store.set_integer("App", "port", 3000).await?;
+        let mut events = store.subscribe();
+        assert_eq!(store.get_or_insert_integer("App", "port", 80).await?, 80);
+        assert_eq!(store.get_or_insert_integer("App", "port", 81).await?, 80);
+        assert_eq!(drain_events(&mut events), [event("App", Some("port"), ChangeKind::Set)]);
+        Ok(())
+    }
 
-        let temp = NamedTempFile::new().expect("temp file");
-        let path_buf = temp.path().to_path_buf();
-        store.save_file(&path_buf).await?;
+    #[tokio::test]
+    async fn with_table_lends_the_root_table() -> Result<()> {
+        let store = Confer::from_string("[A]\nx = 1\n[B]\ny = [1, 2]\n")?;
+        let leaves = store.with_table(count_leaves).await;
+        assert_eq!(leaves, 2);
 
-        let restored = Confer::from_file(&path_buf)?;
-        assert_eq!(restored.get_string("App", "name").await?, "demo");
-        assert_eq!(restored.get_integer("App", "port").await?, 3000);
+        store
+            .with_table_mut(|table| {
+                table.remove("A");
+                table.insert("C".into(), Value::Table(Table::new()));
+            })
+            .await;
+        assert_eq!(store.list_sections().await, vec!["B".to_string(), "C".to_string()]);
         Ok(())
     }
 
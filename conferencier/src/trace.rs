@@ -0,0 +1,298 @@
+//! Optional [`tracing`](https://docs.rs/tracing) instrumentation.
+//!
+//! Every hook compiles to nothing unless the `tracing` feature is enabled. Spans and events use
+//! the `conferencier` target at `DEBUG` level and carry structured fields only. The exception is
+//! [`deprecated_key`], a `WARN` event that falls back to stderr without the feature.
+
+#[cfg(feature = "tracing")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+use crate::change::ChangeKind;
+use crate::error::Result;
+
+/// Records a single store mutation.
+pub(crate) fn mutation(section: &str, key: Option<&str>, kind: ChangeKind) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(target: "conferencier", section, key, kind = ?kind, "store mutation");
+    #[cfg(not(feature = "tracing"))]
+    let _ = (section, key, kind);
+}
+
+/// Warns that `section.key` is set although the field reading it is deprecated.
+///
+/// Without the `tracing` feature the warning goes to stderr, once per key per process.
+pub(crate) fn deprecated_key(section: &str, key: &str, message: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(target: "conferencier", section, key, "deprecated configuration key: {message}");
+    #[cfg(not(feature = "tracing"))]
+    {
+        use std::collections::HashSet;
+        use std::sync::{LazyLock, Mutex};
+
+        static WARNED: LazyLock<Mutex<HashSet<(String, String)>>> = LazyLock::new(Default::default);
+        let first = WARNED
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert((section.to_owned(), key.to_owned()));
+        if first {
+            eprintln!("warning: configuration key {section}.{key} is deprecated: {message}");
+        }
+    }
+}
+
+/// Times a whole-store operation such as `load_str` or `save_file`.
+///
+/// [`StoreTrace::instrument`] runs the operation inside a `store_operation` span; the closing
+/// event fires on drop.
+pub(crate) struct StoreTrace {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    op: &'static str,
+    #[cfg(feature = "tracing")]
+    started: Instant,
+    #[cfg(feature = "tracing")]
+    sections: AtomicUsize,
+}
+
+impl StoreTrace {
+    /// Starts timing `op`.
+    pub fn start(op: &'static str) -> Self {
+        #[cfg(not(feature = "tracing"))]
+        let _ = op;
+        Self {
+            #[cfg(feature = "tracing")]
+            span: tracing::debug_span!(
+                target: "conferencier",
+                "store_operation",
+                op,
+                sections = tracing::field::Empty
+            ),
+            #[cfg(feature = "tracing")]
+            op,
+            #[cfg(feature = "tracing")]
+            started: Instant::now(),
+            #[cfg(feature = "tracing")]
+            sections: AtomicUsize::new(0),
+        }
+    }
+
+    /// Records how many sections the operation covered.
+    pub fn sections(&self, count: usize) {
+        #[cfg(feature = "tracing")]
+        {
+            self.sections.store(count, Ordering::Relaxed);
+            self.span.record("sections", count);
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = count;
+    }
+
+    /// Runs `operation` inside this trace's span.
+    pub fn instrument<F, T>(&self, operation: F) -> impl Future<Output = Result<T>> + use<F, T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        #[cfg(feature = "tracing")]
+        return tracing::Instrument::instrument(operation, self.span.clone());
+        #[cfg(not(feature = "tracing"))]
+        operation
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for StoreTrace {
+    fn drop(&mut self) {
+        let _entered = self.span.enter();
+        tracing::debug!(
+            target: "conferencier",
+            op = self.op,
+            sections = self.sections.load(Ordering::Relaxed),
+            elapsed_us = self.started.elapsed().as_micros() as u64,
+            "store operation"
+        );
+    }
+}
+
+/// Times a generated module `load` or `save`.
+///
+/// [`ModuleTrace::instrument`] runs the operation inside a `module_operation` span; the closing
+/// event fires on drop, including on error.
+pub struct ModuleTrace {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    op: &'static str,
+    #[cfg(feature = "tracing")]
+    module: &'static str,
+    #[cfg(feature = "tracing")]
+    section: &'static str,
+    #[cfg(feature = "tracing")]
+    keys: usize,
+    #[cfg(feature = "tracing")]
+    started: Instant,
+}
+
+impl ModuleTrace {
+    /// Starts timing `op` for `module`, which owns `keys` keys in `section`.
+    pub fn start(
+        op: &'static str,
+        module: &'static str,
+        section: &'static str,
+        keys: &crate::__private::KeySet,
+    ) -> Self {
+        #[cfg(not(feature = "tracing"))]
+        let _ = (op, module, section, keys);
+        Self {
+            #[cfg(feature = "tracing")]
+            span: tracing::debug_span!(
+                target: "conferencier",
+                "module_operation",
+                op,
+                module,
+                section,
+                keys = keys.len()
+            ),
+            #[cfg(feature = "tracing")]
+            op,
+            #[cfg(feature = "tracing")]
+            module,
+            #[cfg(feature = "tracing")]
+            section,
+            #[cfg(feature = "tracing")]
+            keys: keys.len(),
+            #[cfg(feature = "tracing")]
+            started: Instant::now(),
+        }
+    }
+
+    /// Runs `operation` inside this trace's span.
+    pub fn instrument<F, T>(&self, operation: F) -> impl Future<Output = Result<T>> + use<F, T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        #[cfg(feature = "tracing")]
+        return tracing::Instrument::instrument(operation, self.span.clone());
+        #[cfg(not(feature = "tracing"))]
+        operation
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for ModuleTrace {
+    fn drop(&mut self) {
+        let _entered = self.span.enter();
+        tracing::debug!(
+            target: "conferencier",
+            op = self.op,
+            module = self.module,
+            section = self.section,
+            keys = self.keys,
+            elapsed_us = self.started.elapsed().as_micros() as u64,
+            "module operation"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    use crate::Confer;
+
+    type FieldList = Vec<(String, String)>;
+
+    #[derive(Default)]
+    struct Recorded {
+        spans: Vec<(&'static str, FieldList)>,
+        stack: Vec<u64>,
+        events: Vec<(Option<&'static str>, FieldList)>,
+    }
+
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Recorded>>);
+
+    struct Fields<'a>(&'a mut FieldList);
+
+    impl Visit for Fields<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.push((field.name().to_owned(), format!("{value:?}")));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut recorded = self.0.lock().unwrap();
+            let mut fields = Vec::new();
+            span.record(&mut Fields(&mut fields));
+            recorded.spans.push((span.metadata().name(), fields));
+            Id::from_u64(recorded.spans.len() as u64)
+        }
+
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            let mut recorded = self.0.lock().unwrap();
+            let index = span.into_u64() as usize - 1;
+            values.record(&mut Fields(&mut recorded.spans[index].1));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut recorded = self.0.lock().unwrap();
+            let mut fields = Vec::new();
+            event.record(&mut Fields(&mut fields));
+            let parent = recorded.stack.last().map(|id| recorded.spans[*id as usize - 1].0);
+            recorded.events.push((parent, fields));
+        }
+
+        fn enter(&self, span: &Id) {
+            self.0.lock().unwrap().stack.push(span.into_u64());
+        }
+
+        fn exit(&self, _: &Id) {
+            self.0.lock().unwrap().stack.pop();
+        }
+    }
+
+    fn field<'a>(fields: &'a [(String, String)], name: &str) -> Option<&'a str> {
+        fields.iter().rev().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    #[tokio::test]
+    async fn load_runs_inside_a_store_operation_span() {
+        let recorder = Recorder::default();
+        let _default = tracing::subscriber::set_default(recorder.clone());
+
+        let store = Confer::new();
+        store.load_str("[Server]\nport = 80\n[Client]\nretries = 3\n").await.unwrap();
+
+        let recorded = recorder.0.lock().unwrap();
+        let (_, span) = recorded
+            .spans
+            .iter()
+            .find(|(name, _)| *name == "store_operation")
+            .expect("store_operation span");
+        assert_eq!(field(span, "op"), Some("\"load\""));
+        assert_eq!(field(span, "sections"), Some("2"));
+
+        let (parent, event) = recorded
+            .events
+            .iter()
+            .find(|(_, fields)| field(fields, "message") == Some("store operation"))
+            .expect("store operation event");
+        assert_eq!(*parent, Some("store_operation"));
+        assert_eq!(field(event, "sections"), Some("2"));
+        assert!(field(event, "elapsed_us").is_some());
+    }
+}
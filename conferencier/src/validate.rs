@@ -0,0 +1,148 @@
+//! Structural checks behind [`Confer::validate`](crate::Confer::validate).
+
+use toml::{Table, Value};
+
+use crate::error::{ConferError, Result};
+use crate::flat::encode_key;
+use crate::value_conversion::{annotate_with_index, describe};
+
+/// Reports the first structural inconsistency below `root`, in table order.
+///
+/// Every section must be a table. With `case_insensitive`, no table may hold two keys that
+/// differ only in ASCII case. Arrays may not mix tables with other values, and the tables of
+/// an array must agree on the type of each key they share; integers and floats are compatible.
+pub(crate) fn check_structure(root: &Table, case_insensitive: bool) -> Result<()> {
+    if case_insensitive {
+        check_case_collisions(root, "", "<section>")?;
+    }
+    for (section, value) in root {
+        let Value::Table(table) = value else {
+            return Err(ConferError::type_mismatch(section, "<section>", "table", describe(value)));
+        };
+        check_table(section, table, "", case_insensitive)?;
+    }
+    Ok(())
+}
+
+/// Checks the entries of `table`, whose path within `section` is `prefix`.
+fn check_table(section: &str, table: &Table, prefix: &str, case_insensitive: bool) -> Result<()> {
+    if case_insensitive {
+        check_case_collisions(table, section, prefix)?;
+    }
+    for (key, value) in table {
+        check_value(section, &join(prefix, key), value, case_insensitive)?;
+    }
+    Ok(())
+}
+
+/// Recurses into tables and arrays stored at `section.path`.
+fn check_value(section: &str, path: &str, value: &Value, case_insensitive: bool) -> Result<()> {
+    match value {
+        Value::Table(table) => check_table(section, table, path, case_insensitive),
+        Value::Array(items) => {
+            check_array_shape(section, path, items)?;
+            for (index, item) in items.iter().enumerate() {
+                check_value(section, &format!("{path}[{index}]"), item, case_insensitive)?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Rejects arrays mixing tables with other values, or whose tables disagree on a key's type.
+fn check_array_shape(section: &str, path: &str, items: &[Value]) -> Result<()> {
+    let Some(first) = items.first() else {
+        return Ok(());
+    };
+    let mut seen = Table::new();
+    for (index, item) in items.iter().enumerate() {
+        if item.is_table() != first.is_table() {
+            return Err(mismatch(section, path, describe(first), item, index));
+        }
+        let Value::Table(table) = item else {
+            continue;
+        };
+        for (key, value) in table {
+            match seen.get(key) {
+                Some(earlier) if type_class(earlier) != type_class(value) => {
+                    let path = format!("{path}[{index}].{}", encode_key(key));
+                    return Err(mismatch(section, &path, describe(earlier), value, index));
+                }
+                Some(_) => {}
+                None => {
+                    seen.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Rejects keys of `table` that only differ in ASCII case.
+fn check_case_collisions(table: &Table, section: &str, prefix: &str) -> Result<()> {
+    let mut keys: Vec<&String> = table.keys().collect();
+    keys.sort_by_key(|key| key.to_ascii_lowercase());
+    for pair in keys.windows(2) {
+        if pair[0].eq_ignore_ascii_case(pair[1]) {
+            let (section, key) = if section.is_empty() {
+                (pair[1].as_str(), prefix.to_owned())
+            } else {
+                (section, join(prefix, pair[1]))
+            };
+            return Err(ConferError::value_parse_owned(
+                section,
+                key,
+                format!("collides with `{}` when keys are case-insensitive", pair[0]),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Builds a [`ConferError::TypeMismatch`] for element `index` of an array.
+fn mismatch(section: &str, path: &str, expected: &'static str, found: &Value, index: usize) -> ConferError {
+    annotate_with_index(ConferError::type_mismatch(section, path, expected, describe(found)), index)
+}
+
+/// Groups value types that typed accessors treat interchangeably.
+fn type_class(value: &Value) -> &'static str {
+    match value {
+        Value::Integer(_) | Value::Float(_) => "number",
+        other => describe(other),
+    }
+}
+
+/// Appends `key` to a dotted `prefix`.
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        encode_key(key)
+    } else {
+        format!("{prefix}.{}", encode_key(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(source: &str, case_insensitive: bool) -> Result<()> {
+        check_structure(&toml::from_str(source).expect("table"), case_insensitive)
+    }
+
+    #[test]
+    fn structure_checks_report_the_first_inconsistency() {
+        assert!(check("[App]\nports = [80, 443.5]\n[[App.jobs]]\nat = 1\n[[App.jobs]]\nat = 2\n", false).is_ok());
+
+        let err = check("[App]\n[[App.jobs]]\nat = 1\n[[App.jobs]]\nat = \"noon\"\n", false).unwrap_err();
+        assert!(matches!(err, ConferError::TypeMismatch { index: Some(1), .. }));
+        assert_eq!(err.key(), Some("jobs[1].at"));
+
+        let err = check("[App]\nmixed = [{ a = 1 }, 2]\n", false).unwrap_err();
+        assert_eq!((err.section(), err.key()), (Some("App"), Some("mixed")));
+
+        assert!(check("flag = 1\n", false).unwrap_err().is_type_mismatch());
+        assert!(check("[App]\nName = 1\nname = 2\n", false).is_ok());
+        assert!(check("[App]\nName = 1\nname = 2\n", true).unwrap_err().is_value_parse());
+    }
+}
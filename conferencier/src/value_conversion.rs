@@ -2,9 +2,11 @@
 
 use std::str::FromStr;
 
-use toml::value::Datetime;
-use toml::Value;
+use toml::value::{Date, Datetime, Time};
+use toml::{Table, Value};
 
+use crate::binary;
+use crate::byte_size::{ByteSize, ParseByteSizeError};
 use crate::error::{ConferError, Result};
 
 /// Human-readable description of a TOML [`Value`] type.
@@ -20,6 +22,24 @@ pub fn describe(value: &Value) -> &'static str {
     }
 }
 
+/// Ensures `value` contains no control characters that TOML strings cannot represent.
+///
+/// Tab, line feed, and carriage return are accepted since they survive a round-trip through
+/// basic and multi-line strings; every other C0 control character and DEL is rejected.
+pub fn validate_string(section: &str, key: &str, value: &str) -> Result<()> {
+    let forbidden = |ch: &char| {
+        matches!(ch, '\u{0}'..='\u{8}' | '\u{b}' | '\u{c}' | '\u{e}'..='\u{1f}' | '\u{7f}')
+    };
+    match value.chars().find(forbidden) {
+        Some(ch) => Err(ConferError::value_parse(
+            section,
+            key,
+            format!("string contains forbidden control character U+{:04X}", ch as u32),
+        )),
+        None => Ok(()),
+    }
+}
+
 /// Converts a TOML value to `String`, producing a type-mismatch error when incompatible.
 pub fn string(section: &str, key: &str, value: Value) -> Result<String> {
     match value {
@@ -36,6 +56,26 @@ pub fn integer(section: &str, key: &str, value: Value) -> Result<i64> {
     }
 }
 
+/// Converts a TOML value to `i64`, also accepting whole floats and strings holding an integer.
+pub fn integer_coerce(section: &str, key: &str, value: Value) -> Result<i64> {
+    match value {
+        Value::Integer(v) => Ok(v),
+        // `i64::MAX as f64` rounds up to 2^63, so the upper bound must be exclusive.
+        Value::Float(v) if v.fract() == 0.0 && v >= i64::MIN as f64 && v < i64::MAX as f64 => {
+            Ok(v as i64)
+        }
+        Value::Float(v) => Err(ConferError::value_parse(
+            section,
+            key,
+            format!("float {v} is not a whole number within integer range"),
+        )),
+        Value::String(s) => s.trim().parse().map_err(|err| {
+            ConferError::value_parse(section, key, format!("failed to parse integer from {s:?}: {err}"))
+        }),
+        other => Err(ConferError::type_mismatch(section, key, "integer", describe(&other))),
+    }
+}
+
 /// Converts a TOML value to `f64`, accepting integers and floats.
 pub fn float(section: &str, key: &str, value: Value) -> Result<f64> {
     match value {
@@ -53,6 +93,38 @@ pub fn boolean(section: &str, key: &str, value: Value) -> Result<bool> {
     }
 }
 
+/// Converts a TOML value to `bool`, also accepting the integers `0`/`1` and the strings
+/// `true`/`false`, `yes`/`no`, `on`/`off`, `1`/`0` in any ASCII case.
+pub fn boolean_lenient(section: &str, key: &str, value: Value) -> Result<bool> {
+    match &value {
+        Value::Boolean(v) => return Ok(*v),
+        Value::Integer(0) => return Ok(false),
+        Value::Integer(1) => return Ok(true),
+        Value::String(s) => {
+            const WORDS: [(&str, bool); 8] = [
+                ("true", true),
+                ("false", false),
+                ("yes", true),
+                ("no", false),
+                ("on", true),
+                ("off", false),
+                ("1", true),
+                ("0", false),
+            ];
+            let word = s.trim();
+            if let Some((_, v)) = WORDS.iter().find(|(name, _)| name.eq_ignore_ascii_case(word)) {
+                return Ok(*v);
+            }
+        }
+        _ => {}
+    }
+    Err(ConferError::value_parse(
+        section,
+        key,
+        format!("cannot interpret {} {value} as a boolean", describe(&value)),
+    ))
+}
+
 /// Converts a TOML value to [`Datetime`], parsing strings when necessary.
 pub fn datetime(section: &str, key: &str, value: Value) -> Result<Datetime> {
     match value {
@@ -62,6 +134,43 @@ pub fn datetime(section: &str, key: &str, value: Value) -> Result<Datetime> {
     }
 }
 
+/// Converts a TOML value like [`datetime`], rejecting values without an explicit UTC offset.
+pub fn datetime_offset(section: &str, key: &str, value: Value) -> Result<Datetime> {
+    let dt = datetime(section, key, value)?;
+    if dt.offset.is_none() {
+        return Err(ConferError::value_parse_owned(
+            section,
+            key,
+            format!("datetime `{dt}` has no UTC offset"),
+        ));
+    }
+    Ok(dt)
+}
+
+/// Converts a TOML value like [`datetime`], accepting only a local date such as `2025-01-01`.
+pub fn date(section: &str, key: &str, value: Value) -> Result<Date> {
+    match datetime(section, key, value)? {
+        Datetime { date: Some(date), time: None, offset: None } => Ok(date),
+        other => Err(ConferError::value_parse_owned(
+            section,
+            key,
+            format!("expected a date without a time, found `{other}`"),
+        )),
+    }
+}
+
+/// Converts a TOML value like [`datetime`], accepting only a local time such as `08:30:00`.
+pub fn time(section: &str, key: &str, value: Value) -> Result<Time> {
+    match datetime(section, key, value)? {
+        Datetime { date: None, time: Some(time), offset: None } => Ok(time),
+        other => Err(ConferError::value_parse_owned(
+            section,
+            key,
+            format!("expected a time without a date, found `{other}`"),
+        )),
+    }
+}
+
 /// Converts a TOML value to `Vec<String>`, validating element types.
 pub fn string_vec(section: &str, key: &str, value: Value) -> Result<Vec<String>> {
     to_vec(section, key, value, |section, key, element| match element {
@@ -78,6 +187,14 @@ pub fn integer_vec(section: &str, key: &str, value: Value) -> Result<Vec<i64>> {
     })
 }
 
+/// Converts a TOML value to `Vec<i64>`, also accepting float elements with no fractional part.
+pub fn integer_vec_coerce(section: &str, key: &str, value: Value) -> Result<Vec<i64>> {
+    to_vec(section, key, value, |section, key, element| match element {
+        element @ (Value::Integer(_) | Value::Float(_)) => integer_coerce(section, key, element),
+        other => Err(element_mismatch(section, key, "integer", &other)),
+    })
+}
+
 /// Converts a TOML value to `Vec<f64>`, upcasting integer elements when needed.
 pub fn float_vec(section: &str, key: &str, value: Value) -> Result<Vec<f64>> {
     to_vec(section, key, value, |section, key, element| match element {
@@ -95,6 +212,48 @@ pub fn boolean_vec(section: &str, key: &str, value: Value) -> Result<Vec<bool>>
     })
 }
 
+/// Converts a TOML value to a [`ByteSize`], accepting non-negative integers as raw bytes and
+/// strings with a unit suffix.
+pub fn byte_size(section: &str, key: &str, value: Value) -> Result<ByteSize> {
+    match value {
+        Value::Integer(bytes) => u64::try_from(bytes).map(ByteSize).map_err(|_| {
+            ConferError::value_parse(section, key, "byte size must not be negative")
+        }),
+        Value::String(raw) => raw
+            .parse()
+            .map_err(|err: ParseByteSizeError| ConferError::value_parse_owned(section, key, err.to_string())),
+        other => Err(ConferError::type_mismatch(section, key, "byte size", describe(&other))),
+    }
+}
+
+/// Decodes a base64 string into bytes; invalid input fails with [`ConferError::ValueParse`].
+pub fn base64_bytes(section: &str, key: &str, value: Value) -> Result<Vec<u8>> {
+    let text = string(section, key, value)?;
+    binary::decode_base64(&text).map_err(|message| ConferError::value_parse_owned(section, key, message))
+}
+
+/// Decodes a hexadecimal string into bytes; invalid input fails with [`ConferError::ValueParse`].
+pub fn hex_bytes(section: &str, key: &str, value: Value) -> Result<Vec<u8>> {
+    let text = string(section, key, value)?;
+    binary::decode_hex(&text).map_err(|message| ConferError::value_parse_owned(section, key, message))
+}
+
+/// Converts a TOML array to `Vec<ByteSize>`, element by element like [`byte_size`].
+pub fn byte_size_vec(section: &str, key: &str, value: Value) -> Result<Vec<ByteSize>> {
+    to_vec(section, key, value, |section, key, element| match element {
+        element @ (Value::Integer(_) | Value::String(_)) => byte_size(section, key, element),
+        other => Err(element_mismatch(section, key, "byte size", &other)),
+    })
+}
+
+/// Converts a TOML value to a nested [`Table`], keeping its values as they are.
+pub fn table(section: &str, key: &str, value: Value) -> Result<Table> {
+    match value {
+        Value::Table(table) => Ok(table),
+        other => Err(ConferError::type_mismatch(section, key, "table", describe(&other))),
+    }
+}
+
 /// Converts a TOML value to `Vec<Datetime>`, parsing string elements when necessary.
 pub fn datetime_vec(section: &str, key: &str, value: Value) -> Result<Vec<Datetime>> {
     to_vec(section, key, value, |section, key, element| match element {
@@ -104,6 +263,42 @@ pub fn datetime_vec(section: &str, key: &str, value: Value) -> Result<Vec<Dateti
     })
 }
 
+/// Converts a TOML array to `Vec<Date>`, validating each element like [`date`].
+pub fn date_vec(section: &str, key: &str, value: Value) -> Result<Vec<Date>> {
+    to_vec(section, key, value, |section, key, element| match element {
+        element @ (Value::Datetime(_) | Value::String(_)) => date(section, key, element),
+        other => Err(element_mismatch(section, key, "date", &other)),
+    })
+}
+
+/// Converts a TOML array to `Vec<Time>`, validating each element like [`time`].
+pub fn time_vec(section: &str, key: &str, value: Value) -> Result<Vec<Time>> {
+    to_vec(section, key, value, |section, key, element| match element {
+        element @ (Value::Datetime(_) | Value::String(_)) => time(section, key, element),
+        other => Err(element_mismatch(section, key, "time", &other)),
+    })
+}
+
+/// Converts a TOML array with a scalar conversion, reporting element mismatches like the typed vec helpers.
+pub fn vec_of<T>(
+    section: &str,
+    key: &str,
+    value: Value,
+    convert: fn(&str, &str, Value) -> Result<T>,
+) -> Result<Vec<T>> {
+    to_vec(section, key, value, |section, key, element| {
+        let found = describe(&element);
+        convert(section, key, element).map_err(|err| match err {
+            ConferError::TypeMismatch { expected, .. } => ConferError::value_parse(
+                section,
+                key,
+                format!("expected array elements of type {expected}, found {found}"),
+            ),
+            other => other,
+        })
+    })
+}
+
 /// Parses a TOML datetime from `raw`, annotating errors with section/key context.
 fn parse_datetime(section: &str, key: &str, raw: &str) -> Result<Datetime> {
     Datetime::from_str(raw).map_err(|err| {
@@ -151,18 +346,19 @@ fn element_mismatch(section: &str, key: &str, expected: &'static str, value: &Va
 }
 
 /// Adds index context to element-related errors to aid debugging.
-fn annotate_with_index(error: ConferError, index: usize) -> ConferError {
+pub fn annotate_with_index(error: ConferError, index: usize) -> ConferError {
     match error {
         ConferError::ValueParse { section, key, message } => ConferError::ValueParse {
             section,
             key,
             message: format!("{message} (at index {index})"),
         },
-        ConferError::TypeMismatch { section, key, expected, found } => ConferError::TypeMismatch {
+        ConferError::TypeMismatch { section, key, expected, found, .. } => ConferError::TypeMismatch {
             section,
             key,
             expected,
             found,
+            index: Some(index),
         },
         other => other,
     }
@@ -0,0 +1,29 @@
+#![cfg(feature = "blocking")]
+
+use conferencier::{BlockingConfer, Result};
+
+#[test]
+fn blocking_accessors_work_without_a_runtime() -> Result<()> {
+    let store = BlockingConfer::from_string("[App]\nname = \"demo\"\n")?;
+    assert_eq!(store.get_string("App", "name")?, "demo");
+
+    store.set_integer("App", "port", 8080)?;
+    store.set("App", "langs", vec!["en", "de"])?;
+    assert_eq!(store.get::<u16>("App", "port")?, 8080);
+    assert_eq!(store.list_keys("App")?, ["langs", "name", "port"]);
+
+    let temp = tempfile::NamedTempFile::new().expect("temp file");
+    store.save_file(temp.path())?;
+    let reloaded = BlockingConfer::from_file(temp.path())?;
+    assert_eq!(reloaded.get_string_vec("App", "langs")?, ["en", "de"]);
+    Ok(())
+}
+
+#[test]
+fn blocking_reader_and_writer_round_trip() -> Result<()> {
+    let store = BlockingConfer::from_reader(&b"[App]\nport = 9\n"[..])?;
+    let mut output = Vec::new();
+    store.to_writer(&mut output)?;
+    assert_eq!(output, b"[App]\nport = 9\n");
+    Ok(())
+}
@@ -55,3 +55,19 @@ async fn concurrent_readers_and_writer() -> Result<()> {
     assert_eq!(store.get_integer("Metrics", "counter").await?, 9);
     Ok(())
 }
+
+#[tokio::test]
+async fn generic_get_and_set() -> Result<()> {
+    let store = Confer::new();
+    store.set("Net", "port", 8080u16).await?;
+    store.set("Net", "hosts", vec!["a", "b"]).await?;
+    store.set("Net", "ratio", 0.5f32).await?;
+
+    assert_eq!(store.get::<u16>("Net", "port").await?, 8080);
+    assert_eq!(store.get::<i64>("Net", "port").await?, 8080);
+    assert_eq!(store.get::<Vec<String>>("Net", "hosts").await?, ["a", "b"]);
+    assert_eq!(store.get::<f32>("Net", "ratio").await?, 0.5);
+    assert!(store.get::<u8>("Net", "port").await.is_err());
+    assert!(store.get::<bool>("Net", "port").await.is_err());
+    Ok(())
+}
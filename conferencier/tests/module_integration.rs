@@ -1,5 +1,9 @@
-use conferencier::{confer_module::ConferModule, Confer, Result};
-use toml::value::Datetime;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use conferencier::{confer_module::ConferModule, Confer, ConferError, Result, SharedConfer, SharedConferModule};
+use tokio::sync::RwLock;
+use toml::value::{Date, Datetime, Time};
 
 #[derive(conferencier::ConferModule)]
 #[confer(section = "Srv")]
@@ -71,6 +75,25 @@ notes = "temp"
     Ok(())
 }
 
+#[tokio::test]
+async fn failed_load_leaves_module_untouched() -> Result<()> {
+    let store = Confer::from_string("[Srv]\np = 8080\n")?;
+    let module = Server::from_confer(store.clone()).await?;
+    module.write().await.cache = vec![1];
+
+    store.set_integer("Srv", "p", 9090).await?;
+    store.set_string("Srv", "retries", "many".into()).await?;
+    assert!(Server::load(&module, store.clone()).await.is_err());
+    assert_eq!(module.read().await.port, 8080);
+
+    store.set_integer("Srv", "retries", 5).await?;
+    Server::load(&module, store.clone()).await?;
+    let guard = module.read().await;
+    assert_eq!((guard.port, guard.retries), (9090, 5));
+    assert_eq!(guard.cache, [1]);
+    Ok(())
+}
+
 #[derive(conferencier::ConferModule)]
 #[confer(section = "Nested")]
 struct NestedModule {
@@ -124,3 +147,1087 @@ counters = [10, 20]
 
     Ok(())
 }
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Shared")]
+struct SharedCounter {
+    #[confer(default = 0)]
+    value: i64,
+}
+
+#[tokio::test]
+async fn save_if_unchanged_detects_concurrent_writes() -> Result<()> {
+    let store = Confer::from_string("[Shared]\nvalue = 1\n")?;
+
+    let first = SharedCounter::from_confer(store.clone()).await?;
+    let second = SharedCounter::from_confer(store.clone()).await?;
+
+    first.write().await.value = 10;
+    assert!(SharedCounter::save_if_unchanged(&first, store.clone()).await?);
+
+    second.write().await.value = 20;
+    assert!(!SharedCounter::save_if_unchanged(&second, store.clone()).await?);
+    assert_eq!(store.get_integer("Shared", "value").await?, 10);
+
+    SharedCounter::load(&second, store.clone()).await?;
+    second.write().await.value += 5;
+    assert!(SharedCounter::save_if_unchanged(&second, store.clone()).await?);
+    assert_eq!(store.get_integer("Shared", "value").await?, 15);
+
+    SharedCounter::load(&first, store.clone()).await?;
+    first.write().await.value = 30;
+    store.set_integer("Shared", "value", 16).await?;
+    assert!(!SharedCounter::save_if_unchanged(&first, store.clone()).await?);
+    assert_eq!(store.get_integer("Shared", "value").await?, 16);
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Net")]
+struct NetworkModule {
+    #[confer(default = "0.0.0.0:80")]
+    bind: SocketAddr,
+    #[confer(default = ["10.0.0.1", "::1"])]
+    allow: Vec<IpAddr>,
+    admin: Option<SocketAddr>,
+}
+
+#[tokio::test]
+async fn network_address_fields_roundtrip() -> Result<()> {
+    let store = Confer::from_string(
+        r#"[Net]
+admin = "[::1]:9000"
+"#,
+    )?;
+
+    let module = NetworkModule::from_confer(store.clone()).await?;
+    {
+        let guard = module.read().await;
+        assert_eq!(guard.bind, "0.0.0.0:80".parse().unwrap());
+        assert_eq!(
+            guard.allow,
+            vec![
+                "10.0.0.1".parse::<IpAddr>().unwrap(),
+                "::1".parse::<IpAddr>().unwrap()
+            ]
+        );
+        assert_eq!(guard.admin, Some("[::1]:9000".parse().unwrap()));
+    }
+
+    {
+        let mut guard = module.write().await;
+        guard.bind = "127.0.0.1:8080".parse().unwrap();
+        guard.admin = None;
+    }
+    NetworkModule::save(&module, store.clone()).await?;
+
+    assert_eq!(store.get_string("Net", "bind").await?, "127.0.0.1:8080");
+    assert_eq!(
+        store.get_string_vec("Net", "allow").await?,
+        vec!["10.0.0.1".to_string(), "::1".to_string()]
+    );
+    assert!(store.get_value("Net", "admin").await.is_none());
+
+    store.set_string_vec("Net", "allow", vec!["10.0.0.1".into(), "nope".into()]).await?;
+    let err = NetworkModule::load(&module, store.clone()).await.unwrap_err();
+    assert!(err.to_string().contains("(at index 1)"));
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Credentials")]
+struct Credentials {
+    user: String,
+    #[confer(secret)]
+    api_key: String,
+}
+
+#[tokio::test]
+async fn secret_fields_are_redacted_in_debug() -> Result<()> {
+    let store = Confer::from_string(
+        r#"[Credentials]
+user = "svc"
+api_key = "s3cr3t"
+"#,
+    )?;
+
+    let module = Credentials::from_confer(store.clone()).await?;
+    let rendered = format!("{:?}", *module.read().await);
+    assert_eq!(rendered, r#"Credentials { user: "svc", api_key: *** }"#);
+
+    Credentials::save(&module, store.clone()).await?;
+    assert_eq!(store.get_string("Credentials", "api_key").await?, "s3cr3t");
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Live")]
+struct LiveSettings {
+    #[confer(default = 1)]
+    workers: u8,
+    #[confer(default = "info")]
+    level: String,
+    tags: Option<Vec<String>>,
+    #[confer(ignore)]
+    hits: u64,
+}
+
+#[tokio::test]
+async fn reload_reports_changed_fields() -> Result<()> {
+    let store = Confer::from_string("[Live]\nworkers = 2\n")?;
+    let module = LiveSettings::from_confer(store.clone()).await?;
+    module.write().await.hits = 42;
+
+    assert!(LiveSettings::reload(&module, store.clone()).await?.is_empty());
+
+    store.set_integer("Live", "workers", 4).await?;
+    store.set_string_vec("Live", "tags", vec!["x".into()]).await?;
+    let changed = LiveSettings::reload(&module, store.clone()).await?;
+    assert_eq!(changed, vec!["workers", "tags"]);
+
+    let guard = module.read().await;
+    assert_eq!(guard.workers, 4);
+    assert_eq!(guard.level, "info");
+    assert_eq!(guard.hits, 42);
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Cache", version = 2, migrate = "Self::migrate")]
+struct CacheSettings {
+    #[confer(default = 60)]
+    ttl_secs: u32,
+}
+
+impl CacheSettings {
+    fn migrate(table: &mut toml::Table, from: u32) -> Result<()> {
+        if from < 2
+            && let Some(minutes) = table.remove("ttl_minutes")
+        {
+            let minutes = minutes.as_integer().unwrap_or_default();
+            table.insert("ttl_secs".into(), toml::Value::Integer(minutes * 60));
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn versioned_module_migrates_and_stamps_version() -> Result<()> {
+    let store = Confer::from_string("[Cache]\nttl_minutes = 5\n")?;
+    let module = CacheSettings::from_confer(store.clone()).await?;
+    assert_eq!(module.read().await.ttl_secs, 300);
+    assert_eq!(store.get_integer("Cache", "__version").await?, 2);
+    assert!(store.get_value("Cache", "ttl_minutes").await.is_none());
+
+    let fresh = Confer::new();
+    let module = CacheSettings::from_confer(fresh.clone()).await?;
+    CacheSettings::save(&module, fresh.clone()).await?;
+    assert_eq!(fresh.get_integer("Cache", "__version").await?, 2);
+    assert_eq!(fresh.get_integer("Cache", "ttl_secs").await?, 60);
+
+    fresh.set_integer("Cache", "__version", 3).await?;
+    assert!(CacheSettings::load(&module, fresh.clone()).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn load_strict_checks_keys_after_migrating() -> Result<()> {
+    let store = Confer::from_string("[Cache]\nttl_minutes = 2\n")?;
+    let module = CacheSettings::from_confer(Confer::new()).await?;
+    CacheSettings::load_strict(&module, store.clone()).await?;
+    assert_eq!(module.read().await.ttl_secs, 120);
+
+    store.set_integer("Cache", "ttl_hours", 1).await?;
+    assert!(CacheSettings::load_strict(&module, store.clone()).await.is_err());
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Http", rename_all = "kebab-case")]
+struct HttpSettings {
+    #[confer(default = 30)]
+    read_timeout: u32,
+    #[confer(rename = "MaxConns", default = 64)]
+    max_connections: u32,
+    #[confer(default = true)]
+    keep_alive: bool,
+}
+
+#[tokio::test]
+async fn rename_all_applies_to_unrenamed_fields() -> Result<()> {
+    let store = Confer::from_string("[Http]\nread-timeout = 5\nMaxConns = 8\n")?;
+    let module = HttpSettings::from_confer(store.clone()).await?;
+    {
+        let guard = module.read().await;
+        assert_eq!(guard.read_timeout, 5);
+        assert_eq!(guard.max_connections, 8);
+        assert!(guard.keep_alive);
+    }
+
+    HttpSettings::save(&module, store.clone()).await?;
+    assert_eq!(
+        store.list_keys("Http").await?,
+        vec!["MaxConns".to_string(), "keep-alive".to_string(), "read-timeout".to_string()]
+    );
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Grpc", rename_all = "PascalCase")]
+struct GrpcSettings {
+    #[confer(default = 4)]
+    max_streams: u32,
+    #[confer(default = false)]
+    _internal: bool,
+}
+
+#[tokio::test]
+async fn pascal_case_capitalizes_after_leading_underscores() -> Result<()> {
+    let store = Confer::from_string("[Grpc]
+MaxStreams = 8
+Internal = true
+")?;
+    let module = GrpcSettings::from_confer(store.clone()).await?;
+    {
+        let guard = module.read().await;
+        assert_eq!(guard.max_streams, 8);
+        assert!(guard._internal);
+    }
+    assert_eq!(GrpcSettings::owned_keys(), ["MaxStreams", "Internal"]);
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Db")]
+struct DbPrimary {
+    #[confer(default = "localhost")]
+    host: String,
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Db")]
+struct DbReplica {
+    #[confer(default = "replica")]
+    host: String,
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Db")]
+struct DbPool {
+    #[confer(default = 4)]
+    pool_size: u16,
+}
+
+#[tokio::test]
+async fn overlapping_module_sections_are_rejected() -> Result<()> {
+    let store = Confer::builder().detect_section_conflicts(true).build();
+    DbPrimary::from_confer(store.clone()).await?;
+    DbPrimary::from_confer(store.clone()).await?;
+    DbPool::from_confer(store.clone()).await?;
+
+    let Err(err) = DbReplica::from_confer(store.clone()).await else {
+        panic!("overlapping section keys should be rejected");
+    };
+    assert!(matches!(
+        &err,
+        conferencier::ConferError::SectionConflict { section, key, .. } if section == "Db" && key == "host"
+    ));
+    assert!(err.to_string().contains("DbPrimary"));
+    assert!(err.to_string().contains("DbReplica"));
+
+    store.register_module_section("tests::Other", "Db", &["timeout"])?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn overlapping_module_sections_are_allowed_by_default() -> Result<()> {
+    let store = Confer::new();
+    DbPrimary::from_confer(store.clone()).await?;
+    DbReplica::from_confer(store.clone()).await?;
+    store.register_module_section("tests::Other", "Db", &["host"])?;
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Mixed", shared_section)]
+struct MixedLogging {
+    #[confer(default = "info")]
+    level: String,
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Mixed", shared_section)]
+struct MixedMetrics {
+    #[confer(default = 9100)]
+    metrics_port: u16,
+}
+
+#[tokio::test]
+async fn shared_section_save_keeps_foreign_keys() -> Result<()> {
+    let store = Confer::from_string("[Mixed]\nmanual = true\n")?;
+    let logging = MixedLogging::from_confer(store.clone()).await?;
+    let metrics = MixedMetrics::from_confer(store.clone()).await?;
+
+    MixedLogging::save(&logging, store.clone()).await?;
+    MixedMetrics::save(&metrics, store.clone()).await?;
+    logging.write().await.level = "debug".into();
+    MixedLogging::save(&logging, store.clone()).await?;
+
+    assert_eq!(store.get_string("Mixed", "level").await?, "debug");
+    assert_eq!(store.get_integer("Mixed", "metrics_port").await?, 9100);
+    assert!(store.get_boolean("Mixed", "manual").await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn concurrent_module_loads_see_the_same_values() -> Result<()> {
+    let store = Confer::from_string("[Mixed]\nlevel = \"warn\"\n[Db]\npool_size = 8\n")?;
+    let (logging, metrics, pool) = tokio::try_join!(
+        MixedLogging::from_confer(store.clone()),
+        MixedMetrics::from_confer(store.clone()),
+        DbPool::from_confer(store.clone()),
+    )?;
+    assert_eq!(logging.read().await.level, "warn");
+    assert_eq!(metrics.read().await.metrics_port, 9100);
+    assert_eq!(pool.read().await.pool_size, 8);
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+struct RetryPolicy {
+    #[confer(default = 3)]
+    attempts: u32,
+    #[confer(default = 1.5)]
+    backoff: f64,
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Client")]
+struct ClientSettings {
+    #[confer(default = "api.example.com")]
+    endpoint: String,
+    #[confer(flatten)]
+    retry: RetryPolicy,
+}
+
+#[tokio::test]
+async fn flatten_inlines_nested_struct_keys() -> Result<()> {
+    let store = Confer::from_string("[Client]\nattempts = 5\nstale = 1\n")?;
+    let module = ClientSettings::from_confer(store.clone()).await?;
+    {
+        let guard = module.read().await;
+        assert_eq!(guard.endpoint, "api.example.com");
+        assert_eq!(guard.retry.attempts, 5);
+        assert_eq!(guard.retry.backoff, 1.5);
+    }
+
+    module.write().await.retry.backoff = 2.0;
+    ClientSettings::save(&module, store.clone()).await?;
+    assert_eq!(
+        store.list_keys("Client").await?,
+        vec!["attempts".to_string(), "backoff".to_string(), "endpoint".to_string()]
+    );
+    assert_eq!(store.get_float("Client", "backoff").await?, 2.0);
+
+    store.set_integer("Client", "attempts", 9).await?;
+    assert_eq!(ClientSettings::reload(&module, store.clone()).await?, vec!["retry"]);
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Dirty")]
+struct DirtySettings {
+    #[confer(default = 1)]
+    first: i64,
+    #[confer(default = 2)]
+    second: i64,
+    label: Option<String>,
+}
+
+#[tokio::test]
+async fn save_changes_writes_only_modified_keys() -> Result<()> {
+    let store = Confer::from_string("[Dirty]\nfirst = 1\nsecond = 2\nlabel = \"x\"\nforeign = true\n")?;
+    let module = DirtySettings::from_confer(store.clone()).await?;
+
+    store.set_integer("Dirty", "second", 20).await?;
+    {
+        let mut guard = module.write().await;
+        guard.first = 10;
+        guard.label = None;
+    }
+    DirtySettings::save_changes(&module, store.clone()).await?;
+
+    assert_eq!(store.get_integer("Dirty", "first").await?, 10);
+    assert_eq!(store.get_integer("Dirty", "second").await?, 20);
+    assert!(store.get_value("Dirty", "label").await.is_none());
+    assert!(store.get_boolean("Dirty", "foreign").await?);
+
+    DirtySettings::save(&module, store.clone()).await?;
+    assert_eq!(store.get_integer("Dirty", "second").await?, 2);
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Paths", post_load = "Self::derive_full", pre_save = "Self::normalize")]
+struct PathSettings {
+    #[confer(default = "/srv/")]
+    root: String,
+    #[confer(default = "data")]
+    name: String,
+    #[confer(ignore)]
+    full: String,
+}
+
+impl PathSettings {
+    fn derive_full(&mut self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(conferencier::ConferError::value_parse("Paths", "name", "must not be empty"));
+        }
+        self.full = format!("{}{}", self.root, self.name);
+        Ok(())
+    }
+
+    fn normalize(&mut self) -> Result<()> {
+        self.root = self.root.trim_end_matches('/').to_string();
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn lifecycle_hooks_run_around_load_and_save() -> Result<()> {
+    let store = Confer::new();
+    let module = PathSettings::from_confer(store.clone()).await?;
+    assert_eq!(module.read().await.full, "/srv/data");
+
+    PathSettings::save(&module, store.clone()).await?;
+    assert_eq!(module.read().await.root, "/srv");
+    assert_eq!(store.get_string("Paths", "root").await?, "/srv");
+
+    store.set_string("Paths", "name", String::new()).await?;
+    let err = PathSettings::load(&module, store.clone()).await.unwrap_err();
+    assert!(matches!(err, conferencier::ConferError::ValueParse { .. }));
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Strict")]
+struct StrictSettings {
+    #[confer(default = 80)]
+    port: u16,
+}
+
+#[tokio::test]
+async fn load_strict_rejects_unknown_keys() -> Result<()> {
+    let store = Confer::from_string("[Strict]\nport = 81\n")?;
+    let module = StrictSettings::from_confer(store.clone()).await?;
+    StrictSettings::load_strict(&module, store.clone()).await?;
+
+    store.set_integer("Strict", "prot", 8080).await?;
+    store.set_integer("Strict", "port", 82).await?;
+    let err = StrictSettings::load_strict(&module, store.clone()).await.unwrap_err();
+    assert!(matches!(err, ConferError::UnknownKey { ref key, .. } if key == "prot"));
+    assert_eq!(err.to_string(), "unknown key Strict.prot");
+    assert_eq!(module.read().await.port, 81);
+    StrictSettings::load(&module, store.clone()).await?;
+    assert_eq!(module.read().await.port, 82);
+    Ok(())
+}
+
+#[tokio::test]
+async fn load_strict_matches_keys_case_insensitively_when_enabled() -> Result<()> {
+    let store = Confer::builder()
+        .case_insensitive(true)
+        .build_from_str("[strict]
+PORT = 81
+")?;
+    let module = StrictSettings::from_confer(store.clone()).await?;
+    StrictSettings::load_strict(&module, store.clone()).await?;
+    assert_eq!(module.read().await.port, 81);
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Theme")]
+struct ThemeSettings {
+    #[confer(default = (255, 128, 0))]
+    accent: (u8, u8, u8),
+    #[confer(default = ("left", "right"))]
+    sides: (String, String),
+}
+
+#[tokio::test]
+async fn tuple_fields_roundtrip_as_fixed_length_arrays() -> Result<()> {
+    let store = Confer::new();
+    let module = ThemeSettings::from_confer(store.clone()).await?;
+    assert_eq!(module.read().await.accent, (255, 128, 0));
+    assert_eq!(module.read().await.sides, ("left".to_string(), "right".to_string()));
+
+    module.write().await.accent = (1, 2, 3);
+    ThemeSettings::save(&module, store.clone()).await?;
+    assert_eq!(store.get_integer_vec("Theme", "accent").await?, vec![1, 2, 3]);
+
+    store.set_integer_vec("Theme", "accent", vec![1, 2]).await?;
+    let err = ThemeSettings::load(&module, store.clone()).await.unwrap_err();
+    assert!(err.to_string().contains("expected 3 elements, found 2"), "{err}");
+
+    store.set_integer_vec("Theme", "accent", vec![1, 2, 300]).await?;
+    let err = ThemeSettings::load(&module, store.clone()).await.unwrap_err();
+    assert!(matches!(err, ConferError::ValueParse { .. }));
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Alerts")]
+struct AlertSettings {
+    #[confer(default = [0.5, 0.75, 0.9, 0.99])]
+    thresholds: [f64; 4],
+    #[confer(default = ["ops", "dev"])]
+    channels: [String; 2],
+    #[confer(default = [1; 3])]
+    levels: [u8; 3],
+}
+
+#[tokio::test]
+async fn fixed_size_array_fields_require_exact_length() -> Result<()> {
+    let store = Confer::new();
+    let module = AlertSettings::from_confer(store.clone()).await?;
+    assert_eq!(module.read().await.thresholds, [0.5, 0.75, 0.9, 0.99]);
+    assert_eq!(module.read().await.levels, [1, 1, 1]);
+
+    module.write().await.channels = ["pager".to_string(), "mail".to_string()];
+    AlertSettings::save(&module, store.clone()).await?;
+    assert_eq!(store.get_string_vec("Alerts", "channels").await?, vec!["pager", "mail"]);
+
+    store.set_float_vec("Alerts", "thresholds", vec![0.1, 0.2, 0.3, 0.4, 0.5]).await?;
+    let err = AlertSettings::load(&module, store.clone()).await.unwrap_err();
+    assert!(err.to_string().contains("expected 4 elements, found 5"), "{err}");
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Pool")]
+struct PoolSettings {
+    #[confer(default = 16)]
+    max_connections: std::num::NonZeroU32,
+    shards: Vec<std::num::NonZero<u16>>,
+    idle: Option<std::num::NonZeroU64>,
+}
+
+#[tokio::test]
+async fn non_zero_fields_reject_zero() -> Result<()> {
+    let store = Confer::from_string("[Pool]\nshards = [1, 2]\n")?;
+    let module = PoolSettings::from_confer(store.clone()).await?;
+    assert_eq!(module.read().await.max_connections.get(), 16);
+    assert_eq!(module.read().await.idle, None);
+
+    module.write().await.idle = std::num::NonZeroU64::new(30);
+    PoolSettings::save(&module, store.clone()).await?;
+    assert_eq!(store.get_integer("Pool", "idle").await?, 30);
+    assert_eq!(store.get_integer_vec("Pool", "shards").await?, vec![1, 2]);
+
+    store.set_integer("Pool", "max_connections", 0).await?;
+    let err = PoolSettings::load(&module, store.clone()).await.unwrap_err();
+    assert!(err.to_string().contains("must be non-zero"), "{err}");
+
+    store.set_integer("Pool", "max_connections", 4).await?;
+    store.set_integer_vec("Pool", "shards", vec![3, 0]).await?;
+    let err = PoolSettings::load(&module, store.clone()).await.unwrap_err();
+    assert!(err.to_string().contains("at index 1"), "{err}");
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Upload")]
+struct UploadSettings {
+    #[confer(default = "10MB")]
+    max_body: conferencier::ByteSize,
+    #[confer(default = 4096)]
+    chunk: conferencier::ByteSize,
+    tiers: Vec<conferencier::ByteSize>,
+}
+
+#[tokio::test]
+async fn byte_size_fields_parse_units() -> Result<()> {
+    let store = Confer::from_string("[Upload]\ntiers = [\"1KiB\", 2048]\n")?;
+    let module = UploadSettings::from_confer(store.clone()).await?;
+    assert_eq!(module.read().await.max_body.as_u64(), 10_000_000);
+    assert_eq!(module.read().await.chunk.as_u64(), 4096);
+    assert_eq!(
+        module.read().await.tiers,
+        [conferencier::ByteSize(1024), conferencier::ByteSize(2048)]
+    );
+
+    module.write().await.chunk = conferencier::ByteSize(8 << 20);
+    UploadSettings::save(&module, store.clone()).await?;
+    assert_eq!(store.get_string("Upload", "chunk").await?, "8MiB");
+    assert_eq!(store.get_string_vec("Upload", "tiers").await?, ["1KiB", "2KiB"]);
+
+    store.set_string("Upload", "max_body", "lots".into()).await?;
+    let err = UploadSettings::load(&module, store.clone()).await.unwrap_err();
+    assert!(err.to_string().contains("invalid byte size"), "{err}");
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Deploy")]
+struct DeploySettings {
+    #[confer(required)]
+    region: Option<String>,
+    zone: Option<String>,
+}
+
+#[tokio::test]
+async fn required_option_fields_must_be_present() -> Result<()> {
+    let store = Confer::from_string("[Deploy]\nzone = \"b\"\n")?;
+    let Err(err) = DeploySettings::from_confer(store.clone()).await else {
+        panic!("missing required key should fail");
+    };
+    assert!(matches!(err, ConferError::MissingKey { ref key, .. } if key == "region"));
+
+    store.set_string("Deploy", "region", "eu-west".into()).await?;
+    let module = DeploySettings::from_confer(store.clone()).await?;
+    assert_eq!(module.read().await.region.as_deref(), Some("eu-west"));
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Buffers")]
+struct BufferSettings {
+    #[confer(default)]
+    size: u32,
+    #[confer(default)]
+    tags: Vec<String>,
+    #[confer(default)]
+    peers: (u8, u8),
+    name: String,
+}
+
+#[tokio::test]
+async fn bare_default_uses_default_trait() -> Result<()> {
+    let store = Confer::from_string("[Buffers]\nname = \"edge\"\n")?;
+    let module = BufferSettings::from_confer(store.clone()).await?;
+    let guard = module.read().await;
+    assert_eq!(guard.size, 0);
+    assert!(guard.tags.is_empty());
+    assert_eq!(guard.peers, (0, 0));
+
+    store.remove_key("Buffers", "name").await?;
+    let Err(err) = BufferSettings::from_confer(store.clone()).await else {
+        panic!("fields without a default stay required");
+    };
+    assert!(matches!(err, ConferError::MissingKey { .. }));
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Worker")]
+struct WorkerSettings {
+    name: String,
+    #[confer(default)]
+    limits: std::collections::HashMap<String, toml::Value>,
+}
+
+#[tokio::test]
+async fn map_field_round_trips_nested_table() -> Result<()> {
+    let store = Confer::from_string(
+        "[Worker]\nname = \"batch\"\n[Worker.limits]\ncpu = 2\nmem = \"4Gi\"\nburst = true\n",
+    )?;
+    let module = WorkerSettings::from_confer(store.clone()).await?;
+    {
+        let mut guard = module.write().await;
+        assert_eq!(guard.limits.get("cpu"), Some(&toml::Value::Integer(2)));
+        assert_eq!(guard.limits.get("mem"), Some(&toml::Value::String("4Gi".into())));
+        guard.limits.remove("burst");
+        guard.limits.insert("gpu".into(), toml::Value::Float(0.5));
+    }
+    WorkerSettings::save(&module, store.clone()).await?;
+
+    let limits = store.get_table("Worker", "limits").await?;
+    assert_eq!(limits.len(), 3);
+    assert_eq!(limits.get("gpu"), Some(&toml::Value::Float(0.5)));
+    assert!(!limits.contains_key("burst"));
+
+    let fresh = Confer::from_string("[Worker]\nname = \"idle\"\n")?;
+    let module = WorkerSettings::from_confer(fresh).await?;
+    assert!(module.read().await.limits.is_empty());
+    Ok(())
+}
+
+#[test]
+fn schema_describes_fields_without_a_store() {
+    let schema = Server::schema();
+    let keys: Vec<_> = schema.iter().map(|field| field.key).collect();
+    assert_eq!(keys, ["p", "host", "retries", "features", "started_at", "endpoint", "notes"]);
+
+    let port = &schema[0];
+    assert_eq!((port.section, port.type_name, port.required), ("Srv", "u16", true));
+    assert_eq!(port.default_repr, None);
+    assert_eq!(schema[1].default_repr, Some("\"0.0.0.0\""));
+    assert!(!schema[1].required);
+    assert_eq!(schema[6].type_name, "Option<String>");
+    assert!(!schema[6].required);
+
+    let client = ClientSettings::schema();
+    let keys: Vec<_> = client.iter().map(|field| (field.section, field.key)).collect();
+    assert_eq!(keys, [("Client", "endpoint"), ("Client", "attempts"), ("Client", "backoff")]);
+    assert!(DeploySettings::schema().iter().any(|field| field.required));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_schema_maps_field_types() {
+    let schema = Server::json_schema();
+    assert_eq!(schema["title"], "Srv");
+    assert_eq!(schema["properties"]["p"]["type"], "integer");
+    assert_eq!(schema["properties"]["features"]["type"], "array");
+    assert_eq!(schema["properties"]["features"]["items"]["type"], "string");
+    assert_eq!(schema["properties"]["started_at"]["format"], "date-time");
+    assert_eq!(schema["required"], serde_json::json!(["p"]));
+
+    let theme = ThemeSettings::json_schema();
+    let accent = &theme["properties"]["accent"];
+    assert_eq!((accent["minItems"].as_u64(), accent["maxItems"].as_u64()), (Some(3), Some(3)));
+    assert_eq!(WorkerSettings::json_schema()["properties"]["limits"]["type"], "object");
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Lease")]
+struct LeaseSettings {
+    #[confer(default = 30)]
+    ttl: u32,
+    #[confer(skip_save)]
+    holder: Option<String>,
+}
+
+#[tokio::test]
+async fn skip_save_fields_load_but_are_never_written() -> Result<()> {
+    let store = Confer::from_string("[Lease]\nholder = \"node-a\"\n")?;
+    let module = LeaseSettings::from_confer(store.clone()).await?;
+    assert_eq!(module.read().await.holder.as_deref(), Some("node-a"));
+
+    {
+        let mut guard = module.write().await;
+        guard.ttl = 60;
+        guard.holder = Some("node-b".into());
+    }
+    LeaseSettings::save(&module, store.clone()).await?;
+    assert_eq!(store.get_integer("Lease", "ttl").await?, 60);
+    assert_eq!(store.get_string("Lease", "holder").await?, "node-a");
+
+    module.write().await.holder = None;
+    LeaseSettings::save_changes(&module, store.clone()).await?;
+    assert_eq!(store.get_string("Lease", "holder").await?, "node-a");
+
+    assert_eq!(LeaseSettings::reload(&module, store.clone()).await?, vec!["holder"]);
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Stamp")]
+struct StampSettings {
+    #[confer(skip_load, init = "String::from(\"2.0.0\")")]
+    written_by: String,
+    #[confer(default = 1)]
+    revision: u32,
+}
+
+#[tokio::test]
+async fn skip_load_fields_keep_their_initial_value() -> Result<()> {
+    let store = Confer::from_string("[Stamp]\nwritten_by = \"1.4.0\"\nrevision = 7\n")?;
+    let module = StampSettings::from_confer(store.clone()).await?;
+    assert_eq!(module.read().await.written_by, "2.0.0");
+    assert_eq!(module.read().await.revision, 7);
+
+    StampSettings::save(&module, store.clone()).await?;
+    assert_eq!(store.get_string("Stamp", "written_by").await?, "2.0.0");
+
+    store.set_string("Stamp", "written_by", "0.1.0".into()).await?;
+    StampSettings::load(&module, store.clone()).await?;
+    assert_eq!(module.read().await.written_by, "2.0.0");
+    Ok(())
+}
+
+mod hex_blob {
+    use conferencier::{ConferError, Result};
+    use toml::Value;
+
+    pub fn load(value: Value) -> Result<Vec<u8>> {
+        let invalid = || ConferError::value_parse("Blob", "key", "expected a hex string");
+        let text = value.as_str().ok_or_else(invalid)?;
+        (0..text.len())
+            .step_by(2)
+            .map(|at| text.get(at..at + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+            .collect::<Option<_>>()
+            .ok_or_else(invalid)
+    }
+
+    pub fn store(bytes: &[u8]) -> Value {
+        Value::String(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+    }
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Blob")]
+struct BlobSettings {
+    #[confer(with = "hex_blob")]
+    key: Vec<u8>,
+    #[confer(with = "hex_blob", default = vec![0xff])]
+    salt: Vec<u8>,
+}
+
+#[tokio::test]
+async fn with_module_converts_custom_encodings() -> Result<()> {
+    let store = Confer::from_string("[Blob]\nkey = \"00a1ff\"\n")?;
+    let module = BlobSettings::from_confer(store.clone()).await?;
+    assert_eq!(module.read().await.key, [0x00, 0xa1, 0xff]);
+    assert_eq!(module.read().await.salt, [0xff]);
+
+    module.write().await.key = vec![0xde, 0xad];
+    BlobSettings::save(&module, store.clone()).await?;
+    assert_eq!(store.get_string("Blob", "key").await?, "dead");
+    assert_eq!(store.get_string("Blob", "salt").await?, "ff");
+
+    store.set_string("Blob", "key", "xyz".into()).await?;
+    let err = BlobSettings::load(&module, store.clone()).await.unwrap_err();
+    assert!(matches!(err, ConferError::ValueParse { .. }));
+
+    store.remove_key("Blob", "key").await?;
+    let err = BlobSettings::load(&module, store.clone()).await.unwrap_err();
+    assert!(matches!(err, ConferError::MissingKey { .. }));
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Tls")]
+struct TlsSettings {
+    #[confer(bytes = "base64")]
+    key: Vec<u8>,
+    #[confer(bytes = "hex", default = [0x00, 0x01])]
+    fingerprint: Vec<u8>,
+    #[confer(bytes = "base64")]
+    ticket: Option<Vec<u8>>,
+}
+
+#[tokio::test]
+async fn bytes_module_decodes_encoded_strings() -> Result<()> {
+    let store = Confer::from_string("[Tls]\nkey = \"c2VjcmV0\"\n")?;
+    let module = TlsSettings::from_confer(store.clone()).await?;
+    assert_eq!(module.read().await.key, b"secret");
+    assert_eq!(module.read().await.fingerprint, [0x00, 0x01]);
+    assert_eq!(module.read().await.ticket, None);
+
+    module.write().await.ticket = Some(vec![0xff, 0xfe]);
+    TlsSettings::save(&module, store.clone()).await?;
+    assert_eq!(store.get_string("Tls", "fingerprint").await?, "0001");
+    assert_eq!(store.get_string("Tls", "ticket").await?, "//4=");
+
+    store.set_string("Tls", "key", "not base64!".into()).await?;
+    let err = TlsSettings::load(&module, store.clone()).await.unwrap_err();
+    assert!(matches!(err, ConferError::ValueParse { .. }));
+    Ok(())
+}
+
+const TENANT_SECTION: &str = "TenantA";
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = TENANT_SECTION)]
+struct TenantSettings {
+    #[confer(default = 8080)]
+    port: u16,
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = concat!("Tenant", "B"))]
+struct OtherTenantSettings {
+    #[confer(default = 9090)]
+    port: u16,
+}
+
+#[tokio::test]
+async fn section_expressions_resolve_at_compile_time() -> Result<()> {
+    let store = Confer::from_string("[TenantA]\nport = 1\n")?;
+    let tenant = TenantSettings::from_confer(store.clone()).await?;
+    let other = OtherTenantSettings::from_confer(store.clone()).await?;
+    assert_eq!(tenant.read().await.port, 1);
+    assert_eq!(other.read().await.port, 9090);
+    assert_eq!(TenantSettings::SECTION, "TenantA");
+    assert_eq!(OtherTenantSettings::SECTION, "TenantB");
+
+    OtherTenantSettings::save(&other, store.clone()).await?;
+    assert_eq!(store.get_integer("TenantB", "port").await?, 9090);
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Release")]
+struct ReleaseSettings {
+    #[confer(default = "2025-01-01")]
+    date: Date,
+    #[confer(default = "08:30:00")]
+    cutoff: Time,
+    freezes: Vec<Date>,
+    #[confer(default = "2025-03-01T09:00:00.25-05:30")]
+    opens_at: Datetime,
+}
+
+#[tokio::test]
+async fn date_and_time_fields_reject_other_temporal_forms() -> Result<()> {
+    let store = Confer::from_string("[Release]\nfreezes = [2025-12-24, 2025-12-31]\n")?;
+    let module = ReleaseSettings::from_confer(store.clone()).await?;
+    assert_eq!(module.read().await.date.to_string(), "2025-01-01");
+    assert_eq!(module.read().await.cutoff.hour, 8);
+    assert_eq!(module.read().await.freezes.len(), 2);
+    assert_eq!(module.read().await.opens_at.to_string(), "2025-03-01T09:00:00.25-05:30");
+
+    ReleaseSettings::save(&module, store.clone()).await?;
+    assert_eq!(store.get_date("Release", "date").await?.day, 1);
+
+    store.set_datetime("Release", "date", "2025-01-01T00:00:00".parse().expect("datetime")).await?;
+    let err = ReleaseSettings::load(&module, store.clone()).await.unwrap_err();
+    assert!(matches!(err, ConferError::ValueParse { .. }));
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Legacy")]
+struct LegacySettings {
+    #[confer(default = 30, deprecated = "use `timeout_ms` instead")]
+    timeout: u32,
+    #[confer(default = 30_000)]
+    timeout_ms: u32,
+}
+
+#[tokio::test]
+async fn deprecated_keys_still_load() -> Result<()> {
+    let store = Confer::from_string("[Legacy]\ntimeout = 5\n")?;
+    let module = LegacySettings::from_confer(store.clone()).await?;
+    assert_eq!(module.read().await.timeout, 5);
+    assert_eq!(module.read().await.timeout_ms, 30_000);
+
+    store.remove_key("Legacy", "timeout").await?;
+    LegacySettings::load(&module, store.clone()).await?;
+    assert_eq!(module.read().await.timeout, 30);
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "Sunset")]
+struct SunsetSettings {
+    #[confer(default = 1, deprecated = "use `retries` instead")]
+    attempts: u32,
+    #[confer(skip_load, default = 2, deprecated = "written for older readers only")]
+    legacy_retries: u32,
+}
+
+/// Deprecation warnings go to stderr without `tracing`, so the load runs in a child test process.
+#[cfg(not(feature = "tracing"))]
+#[tokio::test]
+async fn deprecation_warnings_skip_fields_that_are_not_loaded() -> Result<()> {
+    const CHILD: &str = "CONFERENCIER_DEPRECATION_CHILD";
+    if std::env::var_os(CHILD).is_some() {
+        let store = Confer::from_string("[Sunset]\nattempts = 3\nlegacy_retries = 4\n")?;
+        SunsetSettings::from_confer(store).await?;
+        return Ok(());
+    }
+
+    let output = std::process::Command::new(std::env::current_exe().expect("test binary"))
+        .args(["deprecation_warnings_skip_fields_that_are_not_loaded", "--exact", "--nocapture"])
+        .env(CHILD, "1")
+        .output()
+        .expect("child test run");
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Sunset.attempts is deprecated"));
+    assert!(!stderr.contains("Sunset.legacy_retries"));
+    Ok(())
+}
+
+#[derive(conferencier::ConferModule)]
+struct ConferMetrics {
+    #[confer(default = true)]
+    enabled: bool,
+}
+
+#[tokio::test]
+async fn section_const_reports_default_section_name() -> Result<()> {
+    assert_eq!(ConferMetrics::SECTION, "Metrics");
+    let store = Confer::new();
+    let module = ConferMetrics::from_confer(store.clone()).await?;
+    ConferMetrics::save(&module, store.clone()).await?;
+    assert!(store.get_boolean(ConferMetrics::SECTION, "enabled").await?);
+    Ok(())
+}
+
+#[test]
+fn owned_keys_include_flattened_and_version_keys() {
+    assert_eq!(ClientSettings::owned_keys(), ["endpoint", "attempts", "backoff"]);
+    assert_eq!(CacheSettings::owned_keys(), ["ttl_secs", "__version"]);
+    assert!(std::ptr::eq(ClientSettings::owned_keys(), ClientSettings::owned_keys()));
+}
+
+#[derive(conferencier::ConferModule)]
+#[confer(section = "App")]
+struct CasedSettings {
+    #[confer(default = 80)]
+    port: u16,
+}
+
+#[tokio::test]
+async fn case_insensitive_save_keeps_differently_cased_keys() -> Result<()> {
+    let store = Confer::builder()
+        .case_insensitive(true)
+        .build_from_str("[app]\nPort = 5\nstale = 1\n")?;
+    let module = CasedSettings::from_confer(store.clone()).await?;
+    assert_eq!(module.read().await.port, 5);
+
+    CasedSettings::save(&module, store.clone()).await?;
+    assert_eq!(store.save_str().await?, "[app]\nPort = 5\n");
+    Ok(())
+}
+
+struct ManualSettings {
+    level: i64,
+}
+
+#[async_trait::async_trait]
+impl ConferModule for ManualSettings {
+    async fn from_confer(store: SharedConfer) -> Result<SharedConferModule<Self>> {
+        let module = Arc::new(RwLock::new(ManualSettings { level: 0 }));
+        Self::load(&module, store).await?;
+        Ok(module)
+    }
+
+    async fn load(module: &SharedConferModule<Self>, store: SharedConfer) -> Result<()> {
+        module.write().await.level = store.get_integer("Manual", "level").await?;
+        Ok(())
+    }
+
+    async fn save(module: &SharedConferModule<Self>, store: SharedConfer) -> Result<()> {
+        let level = module.read().await.level;
+        store.set_integer("Manual", "level", level).await
+    }
+}
+
+#[tokio::test]
+async fn manual_modules_inherit_default_methods() -> Result<()> {
+    let store = Confer::from_string("[Manual]\nlevel = 1\n")?;
+    let module = ManualSettings::from_confer(store.clone()).await?;
+    module.write().await.level = 2;
+
+    assert!(ManualSettings::save_if_unchanged(&module, store.clone()).await?);
+    assert_eq!(store.get_integer("Manual", "level").await?, 2);
+
+    store.set_integer("Manual", "level", 3).await?;
+    assert!(ManualSettings::reload(&module, store.clone()).await?.is_empty());
+    assert_eq!(module.read().await.level, 3);
+
+    module.write().await.level = 4;
+    ManualSettings::save_changes(&module, store.clone()).await?;
+    assert_eq!(store.get_integer("Manual", "level").await?, 4);
+
+    store.set_integer("Manual", "extra", 1).await?;
+    ManualSettings::load_strict(&module, store.clone()).await?;
+    assert_eq!(ManualSettings::SECTION, "");
+    assert!(ManualSettings::owned_keys().is_empty());
+    Ok(())
+}